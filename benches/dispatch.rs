@@ -0,0 +1,38 @@
+//! Measures how many instructions per second the CPU executes on a small loop exercising
+//! common instructions. Run with `cargo bench --bench dispatch`.
+
+use std::time::Instant;
+
+use mos_6502::cpu::{CPU, StopReason, RESET_VECTOR};
+
+const INSTRUCTIONS: usize = 20_000_000;
+
+fn main() {
+    let mut cpu = CPU::new();
+    let program = [
+        0xA2, 0x00,       // LDX #$00
+        0xBD, 0x00, 0x03, // LDA $0300,X
+        0x69, 0x01,       // ADC #$01
+        0x9D, 0x00, 0x03, // STA $0300,X
+        0x48,             // PHA
+        0x68,             // PLA
+        0xE8,             // INX
+        0xD0, 0xF3,       // BNE $0202
+        0x4C, 0x00, 0x02, // JMP $0200
+    ];
+    for (addr, byte) in (0x0200..).zip(program) {
+        cpu.mem.write_byte(addr, byte);
+    }
+    cpu.mem.write_word(RESET_VECTOR, 0x0200);
+    cpu.reset();
+
+    let start = Instant::now();
+    let reason = cpu.run_for_instructions(INSTRUCTIONS);
+    let elapsed = start.elapsed();
+
+    assert_eq!(reason, Ok(StopReason::InstructionBudget));
+    println!(
+        "{INSTRUCTIONS} instructions in {elapsed:.2?}: {:.1} M instructions/s",
+        INSTRUCTIONS as f64 / elapsed.as_secs_f64() / 1e6,
+    );
+}
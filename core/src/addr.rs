@@ -0,0 +1,83 @@
+use crate::Word;
+
+/// Addressing type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Addr {
+    /// For many 6502 instructions the source and destination of the information to be
+    /// manipulated is implied directly by the function of the instruction itself and no
+    /// further operand needs to be specified. Operations like 'Clear Carry Flag' (CLC)
+    /// and 'Return from Subroutine' (RTS) are implicit.
+    Implicit,
+    /// Some instructions have an option to operate directly upon the accumulator. This
+    /// may be specified in code by using a special operand value, `A`.
+    Accummulator,
+    /// These instructions have their data defined in the next byte after the opcode.
+    Immediate,
+    /// An instruction using zero page addressing mode has only an 8-bit address operand.
+    /// This limits the addressing to the first 256 bytes of memory (i.e. page zero,
+    /// `0x0000` to `0x00FF`), where the most significant byte of the address is always
+    /// zero.
+    ZeroPage,
+    /// The address is calculated by taking a Zero Page address and adding the value in
+    /// the X register, wrapping if it goes over 0xFF.
+    ZeroPageX,
+    /// The address is calculated by taking a Zero Page address and adding the value in
+    /// the Y register, wrapping if it goes over 0xFF.
+    ZeroPageY,
+    /// Relative addressing mode is used by branch instructions (e.g. BEQ, BNE, etc.) which
+    /// contain a signed 8-bit relative offset (e.g. -128 to +127) which is added to the
+    /// program counter if the condition is true. As the program counter itself is
+    /// incremented during instruction execution by two, the effective address range for
+    /// the target instruction must be within -126 to +129 bytes of the branch.
+    Relative,
+    /// Uses a full 16-bit address to identify the target location.
+    Absolute,
+    /// The address is computed by taking the 16-bit address from the instruction and
+    /// adding the contents of the X register. For example if X contains `0x92`, then the
+    /// `STA $2000,X` instruction will store the accumulator at `0x2092` (i.e. `0x2000 +
+    /// 0x92`).
+    AbsoluteX,
+    /// The address is computed by taking the 16-bit address from the instruction and
+    /// adding the contents of the Y register. For example if Y contains `0x92`, then the
+    /// `STA $2000,Y` instruction will store the accumulator at `0x2092` (i.e. `0x2000 +
+    /// 0x92`).
+    AbsoluteY,
+    /// Data is accessed using a pointer. The 16-bit address of the pointer is given in
+    /// the two bytes following the opcode.
+    Indirect,
+    /// An 8-bit zero-page address and the X register are added, without carry (if the
+    /// addition overflows, the address wraps around within page 0). The resulting address
+    /// is used as a pointer to the data being accessed. Note that, effectively, this
+    /// makes the X register an index into a list of pointers. Also note that pointers
+    /// are two bytes long, so the X register should be an even number when accessing a
+    /// list of pointers (otherwise you'll get half of one pointer and half of another).
+    XIndirect,
+    /// An 8-bit address identifies a pointer. The value of the Y register is added to the
+    /// address contained in the pointer. Effectively, the pointer is the base address
+    /// and the Y register is an index past that base address.
+    IndirectY,
+    /// An 8-bit address identifies a pointer to the data being accessed, without any
+    /// indexing. Only available on the 65C02.
+    ZeroPageIndirect,
+    /// The 16-bit address from the instruction and the X register are added, and the
+    /// result is used as a pointer to the target location. Only available on the 65C02,
+    /// for `JMP ($xxxx,X)`.
+    AbsoluteXIndirect,
+    /// An 8-bit zero page address identifies the byte being tested, and is followed by a
+    /// signed 8-bit relative offset like in [`Addr::Relative`], which is added to the
+    /// address of the next instruction if the branch is taken. Only available on the
+    /// Rockwell and WDC 65C02, for `BBR` and `BBS`.
+    ZeroPageRelative,
+}
+
+impl Addr {
+    /// Number of bytes following the opcode of an instruction using this addressing mode.
+    pub fn operand_len(&self) -> Word {
+        match self {
+            Addr::Implicit | Addr::Accummulator => 0,
+            Addr::Absolute | Addr::AbsoluteX | Addr::AbsoluteY | Addr::Indirect
+            | Addr::AbsoluteXIndirect | Addr::ZeroPageRelative => 2,
+            _ => 1,
+        }
+    }
+}
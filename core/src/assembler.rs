@@ -0,0 +1,994 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use miette::{Diagnostic, SourceSpan};
+
+use crate::{Addr, Byte, Variant, Word};
+use crate::opcodes;
+use crate::parser::{
+    BinaryOp, Data, Directive, DirectiveKind, Expr, ExprKind, Instruction, Operand, ParseError,
+    Parser, Statement, UnaryOp,
+};
+
+/// A two-pass assembler. The first pass picks the encoding of every instruction and
+/// records the address of every label, so that the second pass can emit the machine code
+/// with all label references (forward ones included) resolved.
+pub struct Assembler {
+    /// Address at which the first instruction is located, unless moved by `.org`.
+    pub origin: Word,
+    /// The chip whose instruction set is assembled.
+    pub variant: Variant,
+}
+
+/// The machine code produced by the [`Assembler`], along with its symbol table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assembly {
+    /// Address of the first byte of the image.
+    pub origin: Word,
+    /// The image, in which the gaps left by `.org` are filled with zeros.
+    pub bytes: Vec<Byte>,
+    /// Address of every label.
+    pub symbols: BTreeMap<String, Word>,
+    /// Every file read, i.e. the assembled one (if any) and those named by `.include` and
+    /// `.incbin`.
+    pub files: Vec<PathBuf>,
+}
+
+/// A file of the program, i.e. the source given to the assembler or an included one.
+struct Source {
+    /// Path of the file, if the source was read from one.
+    path: Option<PathBuf>,
+    /// Directory in which the files it includes are looked up.
+    dir: PathBuf,
+    text: String,
+    /// The source, and the span in it, of the `.include` directive that included this one.
+    included_from: Option<(usize, SourceSpan)>,
+}
+
+/// A piece of the output, laid out by the first pass and filled in by the second.
+enum Chunk<'a> {
+    Instruction(&'a Instruction, Byte, Addr),
+    Bytes(&'a [Data]),
+    Words(&'a [Expr]),
+    /// A number of bytes, set to the optional value (or 0).
+    Fill(Word, Option<&'a Expr>),
+    /// The contents of the file at the path.
+    Binary(PathBuf, Vec<Byte>),
+}
+
+impl Assembler {
+    /// Creates an assembler for the NMOS 6502 whose output is located at `origin`.
+    pub fn new(origin: Word) -> Self {
+        Assembler {
+            origin,
+            variant: Variant::default(),
+        }
+    }
+
+    /// Assembles the source, in which files are included relative to the working
+    /// directory.
+    pub fn assemble(&self, src: &str) -> Result<Assembly, AssemblyError> {
+        self.assemble_in(src, "")
+    }
+
+    /// Assembles the source, in which files are included relative to `dir`.
+    pub fn assemble_in(&self, src: &str, dir: impl AsRef<Path>) -> Result<Assembly, AssemblyError> {
+        self.assemble_source(Source {
+            path: None,
+            dir: dir.as_ref().to_owned(),
+            text: src.to_owned(),
+            included_from: None,
+        })
+    }
+
+    /// Assembles a source file, in which files are included relative to its directory.
+    pub fn assemble_file(&self, path: impl AsRef<Path>) -> Result<Assembly, AssemblyError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| AssemblyError::Io {
+            path: path.display().to_string(),
+            source,
+            src: String::new(),
+            span: None,
+        })?;
+        self.assemble_source(Source {
+            path: Some(path.to_owned()),
+            dir: parent_dir(path),
+            text,
+            included_from: None,
+        })
+    }
+
+    fn assemble_source(&self, main: Source) -> Result<Assembly, AssemblyError> {
+        let mut sources = vec![main];
+        let mut statements = vec![];
+        load(&mut sources, 0, &mut statements)?;
+
+        // First pass: choose the encoding of each instruction, which fixes its length and
+        // thus the address of the labels that follow.
+        let mut symbols = BTreeMap::new();
+        let mut chunks = vec![];
+        let mut origin = self.origin;
+        // The location counter reaches $10000 once the last byte of memory is used, past
+        // which nothing else can be located.
+        let mut end = self.origin as usize;
+        for (index, statement) in &statements {
+            let src = &sources[*index].text;
+            let Ok(pc) = Word::try_from(end) else {
+                return Err(included(&sources, *index, AssemblyError::AddressOverflow {
+                    src: src.to_owned(),
+                    span: statement.span(),
+                }));
+            };
+            let chunk = match statement {
+                Statement::Label { name, span } => {
+                    if symbols.insert(name.to_string(), pc).is_some() {
+                        return Err(included(&sources, *index, AssemblyError::DuplicateLabel {
+                            name: name.to_string(),
+                            src: src.to_owned(),
+                            span: *span,
+                        }));
+                    }
+                    continue;
+                },
+                Statement::Instruction(ins) => {
+                    let (opcode, mode) = self
+                        .encoding(src, ins, pc, &symbols)
+                        .map_err(|err| included(&sources, *index, err))?;
+                    Chunk::Instruction(ins, opcode, mode)
+                },
+                Statement::Directive(Directive { kind: DirectiveKind::Org(address), span }) => {
+                    let address = word(src, address, pc, &symbols)
+                        .map_err(|err| included(&sources, *index, err))?;
+                    // Until something is emitted, `.org` just moves the image.
+                    if chunks.is_empty() {
+                        origin = address;
+                    } else if address < pc {
+                        return Err(included(&sources, *index, AssemblyError::OrgBackwards {
+                            address,
+                            pc,
+                            src: src.to_owned(),
+                            span: *span,
+                        }));
+                    }
+                    end = address as usize;
+                    continue;
+                },
+                Statement::Directive(directive) => {
+                    match layout(&sources, *index, directive, pc, &symbols) {
+                        Ok(Some(chunk)) => chunk,
+                        Ok(None) => continue,
+                        Err(err) => return Err(included(&sources, *index, err)),
+                    }
+                },
+            };
+            end += chunk.len();
+            if end > 0x10000 {
+                return Err(included(&sources, *index, AssemblyError::AddressOverflow {
+                    src: src.to_owned(),
+                    span: statement.span(),
+                }));
+            }
+            chunks.push((*index, pc, chunk));
+        }
+
+        let binaries = chunks.iter().filter_map(|(_, _, chunk)| match chunk {
+            Chunk::Binary(path, _) => Some(path.clone()),
+            _ => None,
+        });
+        let files = sources.iter().filter_map(|source| source.path.clone()).chain(binaries).collect();
+
+        // Second pass: emit the machine code, now that every label is known.
+        let mut bytes = vec![];
+        for (index, pc, chunk) in chunks {
+            // Fill the gap left by `.org`, if any.
+            bytes.resize((pc - origin) as usize, 0);
+            self.emit(&sources[index].text, pc, &chunk, &symbols, &mut bytes)
+                .map_err(|err| included(&sources, index, err))?;
+        }
+
+        Ok(Assembly { origin, bytes, symbols, files })
+    }
+
+    /// Append the bytes of a chunk located at `pc`.
+    fn emit(&self, src: &str, pc: Word, chunk: &Chunk, symbols: &BTreeMap<String, Word>, bytes: &mut Vec<Byte>) -> Result<(), AssemblyError> {
+        let word = |expr| word(src, expr, pc, symbols);
+        let byte = |expr| byte(src, expr, pc, symbols);
+
+        match chunk {
+            Chunk::Instruction(ins, opcode, mode) => {
+                bytes.push(*opcode);
+                match (mode, &ins.operand) {
+                    (Addr::Relative, Operand::Value(target)) => {
+                        bytes.push(offset(src, ins, pc.wrapping_add(2), word(target)?)?);
+                    },
+                    (Addr::ZeroPageRelative, Operand::BitBranch(zero_page, target)) => {
+                        bytes.push(byte(zero_page)?);
+                        bytes.push(offset(src, ins, pc.wrapping_add(3), word(target)?)?);
+                    },
+                    (mode, Operand::Value(value)) if mode.operand_len() == 1 => bytes.push(byte(value)?),
+                    (_, Operand::Value(value)) => bytes.extend(word(value)?.to_le_bytes()),
+                    (_, _) => {},
+                }
+            },
+            Chunk::Bytes(data) => {
+                for item in data.iter() {
+                    match item {
+                        Data::Byte(expr) => bytes.push(byte(expr)?),
+                        Data::String(string) => bytes.extend(string),
+                    }
+                }
+            },
+            Chunk::Words(words) => {
+                for expr in words.iter() {
+                    bytes.extend(word(expr)?.to_le_bytes());
+                }
+            },
+            Chunk::Fill(len, value) => {
+                let value = value.map(byte).transpose()?.unwrap_or(0);
+                bytes.resize(bytes.len() + *len as usize, value);
+            },
+            Chunk::Binary(_, data) => bytes.extend(data),
+        }
+        Ok(())
+    }
+
+    /// The opcode and addressing mode of an instruction. The zero page modes are preferred
+    /// whenever the instruction supports them and the operand is known to fit in a byte, or
+    /// is written as one. Other forward references are not known yet, so the absolute
+    /// modes are used for them.
+    fn encoding(&self, src: &str, ins: &Instruction, pc: Word, symbols: &BTreeMap<String, Word>) -> Result<(Byte, Addr), AssemblyError> {
+        let (zero_page, absolute) = match ins.mode {
+            Addr::ZeroPage | Addr::Absolute => (Addr::ZeroPage, Addr::Absolute),
+            Addr::ZeroPageX | Addr::AbsoluteX => (Addr::ZeroPageX, Addr::AbsoluteX),
+            Addr::ZeroPageY | Addr::AbsoluteY => (Addr::ZeroPageY, Addr::AbsoluteY),
+            Addr::ZeroPageIndirect | Addr::Indirect => (Addr::ZeroPageIndirect, Addr::Indirect),
+            Addr::XIndirect | Addr::AbsoluteXIndirect => (Addr::XIndirect, Addr::AbsoluteXIndirect),
+            mode => (mode, mode),
+        };
+        // The parser already picked the zero page modes for operands written as a byte,
+        // such as `<label`, even if their value is not known yet.
+        let fits = match &ins.operand {
+            _ if ins.mode == zero_page && zero_page != absolute => true,
+            Operand::Value(value) => matches!(eval(src, value, pc, symbols), Ok(0..=0xFF)),
+            _ => false,
+        };
+
+        let encode = |mode| {
+            opcodes::encode(&ins.mnemonic, mode, self.variant).map(|code| (code, mode))
+        };
+        let encoding = if fits {
+            encode(zero_page).or_else(|| encode(absolute))
+        } else {
+            encode(absolute).or_else(|| encode(zero_page))
+        };
+
+        encoding.ok_or_else(|| AssemblyError::UnsupportedMode {
+            mnemonic: ins.mnemonic.to_string(),
+            mode: ins.mode,
+            variant: self.variant,
+            src: src.to_owned(),
+            span: ins.span,
+        })
+    }
+}
+
+impl Chunk<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Chunk::Instruction(_, _, mode) => 1 + mode.operand_len() as usize,
+            Chunk::Bytes(data) => data
+                .iter()
+                .map(|item| match item {
+                    Data::Byte(_) => 1,
+                    Data::String(string) => string.len(),
+                })
+                .sum(),
+            Chunk::Words(words) => 2 * words.len(),
+            Chunk::Fill(len, _) => *len as usize,
+            Chunk::Binary(_, data) => data.len(),
+        }
+    }
+}
+
+/// Parse the source `index`, appending its statements to `statements` along with the
+/// index of their source. Included files are loaded in place of their `.include`.
+fn load(sources: &mut Vec<Source>, index: usize, statements: &mut Vec<(usize, Statement)>) -> Result<(), AssemblyError> {
+    let parsed = Parser::new(&sources[index].text)
+        .parse()
+        .map_err(|err| included(sources, index, err.into()))?;
+
+    for statement in parsed {
+        let Statement::Directive(Directive { kind: DirectiveKind::Include(path), span }) = &statement else {
+            statements.push((index, statement));
+            continue;
+        };
+
+        let path = relative_path(sources, index, path);
+        let canonical = fs::canonicalize(&path).ok();
+        let mut ancestor = Some(index);
+        while let Some(source) = ancestor.map(|ancestor| &sources[ancestor]) {
+            if canonical.is_some() && source.path.as_ref().and_then(|path| fs::canonicalize(path).ok()) == canonical {
+                return Err(included(sources, index, AssemblyError::IncludeCycle {
+                    path: path.display().to_string(),
+                    src: sources[index].text.clone(),
+                    span: *span,
+                }));
+            }
+            ancestor = source.included_from.map(|(parent, _)| parent);
+        }
+
+        let text = fs::read_to_string(&path).map_err(|source| {
+            included(sources, index, AssemblyError::Io {
+                path: path.display().to_string(),
+                source,
+                src: sources[index].text.clone(),
+                span: Some(*span),
+            })
+        })?;
+        sources.push(Source {
+            dir: parent_dir(&path),
+            path: Some(path),
+            text,
+            included_from: Some((index, *span)),
+        });
+        load(sources, sources.len() - 1, statements)?;
+    }
+    Ok(())
+}
+
+/// The chunk laid out by a data directive at `pc`, if any.
+fn layout<'a>(sources: &[Source], index: usize, directive: &'a Directive, pc: Word, symbols: &BTreeMap<String, Word>) -> Result<Option<Chunk<'a>>, AssemblyError> {
+    let src = &sources[index].text;
+    let chunk = match &directive.kind {
+        DirectiveKind::Byte(data) => Chunk::Bytes(data),
+        DirectiveKind::Word(words) => Chunk::Words(words),
+        // The size of a fill must be known in the first pass, so it can only refer to the
+        // labels defined before it.
+        DirectiveKind::Res(len, value) => Chunk::Fill(word(src, len, pc, symbols)?, value.as_ref()),
+        DirectiveKind::Align(alignment, value) => {
+            let alignment = word(src, alignment, pc, symbols)?;
+            if alignment == 0 {
+                return Err(AssemblyError::ZeroAlignment {
+                    src: src.to_owned(),
+                    span: directive.span,
+                });
+            }
+            Chunk::Fill((alignment - pc % alignment) % alignment, value.as_ref())
+        },
+        DirectiveKind::Incbin(path) => {
+            let path = relative_path(sources, index, path);
+            let data = fs::read(&path).map_err(|source| AssemblyError::Io {
+                path: path.display().to_string(),
+                source,
+                src: src.to_owned(),
+                span: Some(directive.span),
+            })?;
+            Chunk::Binary(path, data)
+        },
+        // `.org` moves the location counter instead, and `.include` is replaced by the
+        // included statements when loading.
+        DirectiveKind::Org(_) | DirectiveKind::Include(_) => return Ok(None),
+    };
+    Ok(Some(chunk))
+}
+
+/// The path of a file included by the source `index`, relative to its directory.
+fn relative_path(sources: &[Source], index: usize, path: &str) -> PathBuf {
+    sources[index].dir.join(path)
+}
+
+/// The directory of a source file, in which the files it includes are looked up.
+fn parent_dir(path: &Path) -> PathBuf {
+    path.parent().unwrap_or(Path::new("")).to_owned()
+}
+
+/// Wrap an error found in the source `index` in the chain of `.include` directives leading
+/// to it, from the outermost one.
+fn included(sources: &[Source], mut index: usize, mut error: AssemblyError) -> AssemblyError {
+    while let Some((parent, span)) = sources[index].included_from {
+        error = AssemblyError::Included {
+            path: sources[index].path.as_deref().unwrap_or(Path::new("")).display().to_string(),
+            src: sources[parent].text.clone(),
+            span,
+            error: Box::new(error),
+        };
+        index = parent;
+    }
+    error
+}
+
+/// Evaluate an expression of the instruction at `pc`. The arithmetic is carried out on 32
+/// bits, failing on overflow, and it is up to the caller to check that the result fits in
+/// the operand.
+fn eval(src: &str, expr: &Expr, pc: Word, symbols: &BTreeMap<String, Word>) -> Result<i32, AssemblyError> {
+    let overflow = || AssemblyError::Overflow {
+        src: src.to_owned(),
+        span: expr.span,
+    };
+
+    match &expr.kind {
+        ExprKind::Number(number) => Ok(*number as i32),
+        ExprKind::Label(name) => symbols.get(name.as_str()).map(|&address| address as i32).ok_or_else(|| {
+            AssemblyError::UndefinedLabel {
+                name: name.to_string(),
+                src: src.to_owned(),
+                span: expr.span,
+            }
+        }),
+        ExprKind::Pc => Ok(pc as i32),
+        ExprKind::Unary(op, operand) => {
+            let value = eval(src, operand, pc, symbols)?;
+            match op {
+                UnaryOp::Neg => value.checked_neg().ok_or_else(overflow),
+                UnaryOp::Not => Ok(!value),
+                UnaryOp::Low => Ok(value & 0xFF),
+                UnaryOp::High => Ok((value >> 8) & 0xFF),
+            }
+        },
+        ExprKind::Binary(op, lhs, rhs) => {
+            let lhs = eval(src, lhs, pc, symbols)?;
+            let rhs = eval(src, rhs, pc, symbols)?;
+            let value = match op {
+                BinaryOp::Add => lhs.checked_add(rhs),
+                BinaryOp::Sub => lhs.checked_sub(rhs),
+                BinaryOp::Mul => lhs.checked_mul(rhs),
+                BinaryOp::Div if rhs == 0 => {
+                    return Err(AssemblyError::DivisionByZero {
+                        src: src.to_owned(),
+                        span: expr.span,
+                    });
+                },
+                BinaryOp::Div => lhs.checked_div(rhs),
+                BinaryOp::And => Some(lhs & rhs),
+                BinaryOp::Or => Some(lhs | rhs),
+                BinaryOp::Xor => Some(lhs ^ rhs),
+                // Shifting bits out of an `i32` is an overflow as well.
+                BinaryOp::ShiftLeft => u32::try_from(rhs)
+                    .ok()
+                    .filter(|&rhs| rhs < 32)
+                    .and_then(|rhs| i32::try_from((lhs as i64) << rhs).ok()),
+                BinaryOp::ShiftRight => u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shr(rhs)),
+            };
+            value.ok_or_else(overflow)
+        },
+    }
+}
+
+/// A value which must fit in a word, e.g. an absolute address or a branch target.
+fn word(src: &str, expr: &Expr, pc: Word, symbols: &BTreeMap<String, Word>) -> Result<Word, AssemblyError> {
+    let value = eval(src, expr, pc, symbols)?;
+    Word::try_from(value).map_err(|_| AssemblyError::ValueOutOfRange {
+        value,
+        width: "a word",
+        src: src.to_owned(),
+        span: expr.span,
+    })
+}
+
+/// A value which must fit in a byte, e.g. an immediate operand or a zero page address.
+fn byte(src: &str, expr: &Expr, pc: Word, symbols: &BTreeMap<String, Word>) -> Result<Byte, AssemblyError> {
+    let value = eval(src, expr, pc, symbols)?;
+    Byte::try_from(value).map_err(|_| AssemblyError::ValueOutOfRange {
+        value,
+        width: "a byte",
+        src: src.to_owned(),
+        span: expr.span,
+    })
+}
+
+/// The relative offset of a branch from `next`, the address of the following instruction.
+fn offset(src: &str, ins: &Instruction, next: Word, target: Word) -> Result<Byte, AssemblyError> {
+    let offset = target.wrapping_sub(next) as i16;
+    i8::try_from(offset).map(|offset| offset as Byte).map_err(|_| AssemblyError::BranchOutOfRange {
+        offset,
+        src: src.to_owned(),
+        span: ins.span,
+    })
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum AssemblyError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error("Label `{name}` is defined more than once.")]
+    DuplicateLabel {
+        name: String,
+        #[source_code]
+        src: String,
+        #[label("Defined again here")]
+        span: SourceSpan,
+    },
+    #[error("Label `{name}` is not defined.")]
+    UndefinedLabel {
+        name: String,
+        #[source_code]
+        src: String,
+        #[label("Used here")]
+        span: SourceSpan,
+    },
+    #[error("`{mnemonic}` does not support the {mode:?} addressing mode on the {variant:?}.")]
+    UnsupportedMode {
+        mnemonic: String,
+        mode: Addr,
+        variant: Variant,
+        #[source_code]
+        src: String,
+        #[label("This instruction")]
+        span: SourceSpan,
+    },
+    #[error("Value {value} does not fit in {width}.")]
+    ValueOutOfRange {
+        value: i32,
+        width: &'static str,
+        #[source_code]
+        src: String,
+        #[label("This value")]
+        span: SourceSpan,
+    },
+    #[error("Could not read `{path}`.")]
+    Io {
+        path: String,
+        source: std::io::Error,
+        #[source_code]
+        src: String,
+        #[label("Read here")]
+        span: Option<SourceSpan>,
+    },
+    #[error("`{path}` is included by itself.")]
+    IncludeCycle {
+        path: String,
+        #[source_code]
+        src: String,
+        #[label("Included again here")]
+        span: SourceSpan,
+    },
+    #[error("In `{path}`, included from here.")]
+    Included {
+        path: String,
+        #[source_code]
+        src: String,
+        #[label("Included here")]
+        span: SourceSpan,
+        #[source]
+        #[diagnostic_source]
+        error: Box<AssemblyError>,
+    },
+    #[error("Cannot move the location counter back from ${pc:04X} to ${address:04X}.")]
+    OrgBackwards {
+        address: Word,
+        pc: Word,
+        #[source_code]
+        src: String,
+        #[label("This directive")]
+        span: SourceSpan,
+    },
+    #[error("The output runs past the end of memory at $FFFF.")]
+    AddressOverflow {
+        #[source_code]
+        src: String,
+        #[label("This does not fit")]
+        span: SourceSpan,
+    },
+    #[error("The alignment must not be zero.")]
+    ZeroAlignment {
+        #[source_code]
+        src: String,
+        #[label("This directive")]
+        span: SourceSpan,
+    },
+    #[error("Arithmetic overflow while evaluating the expression.")]
+    Overflow {
+        #[source_code]
+        src: String,
+        #[label("This expression")]
+        span: SourceSpan,
+    },
+    #[error("Division by zero.")]
+    DivisionByZero {
+        #[source_code]
+        src: String,
+        #[label("This expression")]
+        span: SourceSpan,
+    },
+    #[error("Branch target is {offset} bytes away, out of the -128 to 127 range.")]
+    BranchOutOfRange {
+        offset: i16,
+        #[source_code]
+        src: String,
+        #[label("This branch")]
+        span: SourceSpan,
+    },
+}
+
+/// Lets [`AssemblyError::Included`] report the error it wraps as its diagnostic source.
+impl Borrow<dyn Diagnostic> for Box<AssemblyError> {
+    fn borrow(&self) -> &(dyn Diagnostic + 'static) {
+        self.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assemble(src: &str) -> Assembly {
+        Assembler::new(0x0600).assemble(src).expect("The program should assemble.")
+    }
+
+    #[test]
+    fn test_assembler() {
+        let src = "
+            ldx #$05
+        loop:
+            dex
+            bne loop
+            stx $0200
+            rts";
+        let assembly = assemble(src);
+        assert_eq!(assembly.origin, 0x0600);
+        assert_eq!(assembly.bytes, [0xA2, 0x05, 0xCA, 0xD0, 0xFD, 0x8E, 0x00, 0x02, 0x60]);
+        assert_eq!(assembly.symbols, BTreeMap::from([("loop".to_owned(), 0x0602)]));
+    }
+
+    #[test]
+    fn test_forward_references() {
+        let src = "
+            jmp start
+        data:
+            nop
+        start:
+            lda data
+            beq done
+            jsr done
+        done:
+            rts";
+        let assembly = assemble(src);
+        assert_eq!(
+            assembly.bytes,
+            [0x4C, 0x04, 0x06, 0xEA, 0xAD, 0x03, 0x06, 0xF0, 0x03, 0x20, 0x0C, 0x06, 0x60]
+        );
+        assert_eq!(assembly.symbols["start"], 0x0604);
+        assert_eq!(assembly.symbols["done"], 0x060C);
+    }
+
+    #[test]
+    fn test_zero_page() {
+        let src = "
+            lda ptr
+            lda ptr,x
+            ldx ptr,y
+            lda $0004
+            jmp $0004
+            stx ptr,y
+            lda later
+        later:";
+        let assembly = Assembler::new(0x0000).assemble(&format!("ptr:\n{src}")).unwrap();
+        assert_eq!(
+            assembly.bytes,
+            [
+                // Known zero page addresses
+                0xA5, 0x00, 0xB5, 0x00, 0xB6, 0x00, 0xA5, 0x04,
+                // JMP has no zero page mode
+                0x4C, 0x04, 0x00,
+                // STX has no absolute,Y mode
+                0x96, 0x00,
+                // Forward references are absolute
+                0xAD, 0x10, 0x00,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zero_page_hint() {
+        // `<` and `>` make a forward reference zero page, unlike the label itself.
+        let assembly = Assembler::new(0).assemble("lda <fwd\nlda >fwd,x\nlda fwd\nfwd: nop").unwrap();
+        assert_eq!(assembly.bytes, [0xA5, 0x07, 0xB5, 0x00, 0xAD, 0x07, 0x00, 0xEA]);
+    }
+
+    #[test]
+    fn test_cmos() {
+        let src = "
+        loop:
+            stz $10
+            bbs7 $10, loop
+            lda ($10)";
+        let mut assembler = Assembler::new(0x0600);
+        assembler.variant = Variant::Wdc65C02;
+        let assembly = assembler.assemble(src).unwrap();
+        assert_eq!(assembly.bytes, [0x64, 0x10, 0xFF, 0x10, 0xFB, 0xB2, 0x10]);
+    }
+
+    #[test]
+    fn test_label_named_a() {
+        let assembly = assemble("lda a\nasl a\nasl a,x\na: nop");
+        assert_eq!(assembly.bytes, [0xAD, 0x07, 0x06, 0x0A, 0x1E, 0x07, 0x06, 0xEA]);
+    }
+
+    #[test]
+    fn test_expressions() {
+        let src = "
+            lda #<(table+2)
+            ldx #>table
+            ldy table-1,x
+            bne *+4
+            and #$FF & %00111100
+            cmp #'A'
+            adc #100
+            jmp *
+        table:
+            eor #($0F << 4 | 1) ^ %11
+            sbc #1024/4-1";
+        let assembly = Assembler::new(0x1234).assemble(src).unwrap();
+        assert_eq!(assembly.symbols["table"], 0x1246);
+        assert_eq!(
+            assembly.bytes,
+            [
+                0xA9, 0x48, 0xA2, 0x12, 0xBC, 0x45, 0x12, 0xD0, 0x02, 0x29, 0x3C, 0xC9, 0x41,
+                0x69, 0x64, 0x4C, 0x43, 0x12, 0x49, 0xF2, 0xE9, 0xFF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expression_errors() {
+        for (src, value, width, offset) in [
+            ("lda #$1234", 0x1234, "a byte", 5),
+            ("lda #-1", -1, "a byte", 5),
+            ("lda #~0", -1, "a byte", 5),
+            ("jmp $FFFF+1", 0x10000, "a word", 4),
+            ("bne -1", -1, "a word", 4),
+        ] {
+            let err = Assembler::new(0).assemble(src).expect_err(src);
+            let AssemblyError::ValueOutOfRange { value: found, width: found_width, span, .. } = err else {
+                panic!("Unexpected error for {src}: {err:?}");
+            };
+            assert_eq!((found, found_width), (value, width), "{src}");
+            assert_eq!(span.offset(), offset, "{src}");
+        }
+
+        let err = Assembler::new(0).assemble("lda #1 << 31").unwrap_err();
+        assert!(matches!(err, AssemblyError::Overflow { span, .. } if span == (5, 7).into()));
+
+        let err = Assembler::new(0).assemble("lda $FFFF*$FFFF*$FFFF").unwrap_err();
+        assert!(matches!(err, AssemblyError::Overflow { span, .. } if span == (4, 11).into()));
+
+        let err = Assembler::new(0).assemble("lda #4/(2-2)").unwrap_err();
+        assert!(matches!(err, AssemblyError::DivisionByZero { span, .. } if span == (5, 7).into()));
+
+        let err = Assembler::new(0).assemble("lda #<nowhere").unwrap_err();
+        assert!(matches!(err, AssemblyError::UndefinedLabel { span, .. } if span == (6, 7).into()));
+    }
+
+    #[test]
+    fn test_data_directives() {
+        let src = r#"
+            .org $8000
+        reset:
+            lda msg
+            jmp reset
+        msg: .byte "Hi", 0, <msg, >msg
+            .word msg, *
+            .res 2
+            .ds 3, $EA
+            .align 16, $FF
+        aligned:
+            *= $8020
+            .db 1
+            .org $FFFC
+            .dw reset, 0"#;
+        let assembly = Assembler::new(0).assemble(src).unwrap();
+        assert_eq!(assembly.origin, 0x8000);
+        assert_eq!(assembly.bytes.len(), 0x8000);
+        assert_eq!(assembly.symbols["msg"], 0x8006);
+        assert_eq!(assembly.symbols["aligned"], 0x8020);
+        assert_eq!(
+            assembly.bytes[..0x21],
+            [
+                0xAD, 0x06, 0x80, 0x4C, 0x00, 0x80,
+                b'H', b'i', 0x00, 0x06, 0x80,
+                0x06, 0x80, 0x0B, 0x80,
+                0x00, 0x00,
+                0xEA, 0xEA, 0xEA,
+                0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+                0x01,
+            ]
+        );
+        // The gap up to the vectors is filled with zeros.
+        assert!(assembly.bytes[0x21..0x7FFC].iter().all(|&byte| byte == 0));
+        assert_eq!(assembly.bytes[0x7FFC..], [0x00, 0x80, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_directive_errors() {
+        let err = Assembler::new(0x0600).assemble("nop\n.org $0600").unwrap_err();
+        assert!(matches!(
+            err,
+            AssemblyError::OrgBackwards { address: 0x0600, pc: 0x0601, span, .. } if span == (4, 10).into()
+        ));
+
+        let err = Assembler::new(0).assemble(".align 0").unwrap_err();
+        assert!(matches!(err, AssemblyError::ZeroAlignment { .. }));
+
+        // The size of a fill is needed by the first pass.
+        let err = Assembler::new(0).assemble(".res size\nsize:").unwrap_err();
+        assert!(matches!(err, AssemblyError::UndefinedLabel { .. }));
+
+        // Filling memory up to $FFFF is fine, going any further is not.
+        let assembly = Assembler::new(0xFFF0).assemble(".res 14\nnop\nnop").unwrap();
+        assert_eq!(assembly.bytes.len(), 16);
+        let err = Assembler::new(0x8000).assemble(".res $8000\n.res $8000\n.byte 1, 2, 3").unwrap_err();
+        assert!(matches!(err, AssemblyError::AddressOverflow { span, .. } if span == (11, 10).into()));
+        let err = Assembler::new(0xFFFF).assemble("nop\nend:").unwrap_err();
+        assert!(matches!(err, AssemblyError::AddressOverflow { span, .. } if span == (4, 3).into()));
+        let err = Assembler::new(0xFFFE).assemble("lda $1234").unwrap_err();
+        assert!(matches!(err, AssemblyError::AddressOverflow { span, .. } if span == (0, 9).into()));
+
+        let err = Assembler::new(0).assemble(".byte 256").unwrap_err();
+        assert!(matches!(err, AssemblyError::ValueOutOfRange { value: 256, .. }));
+    }
+
+    /// A fresh directory for the files of a test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mos-6502-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_include() {
+        let dir = test_dir("include");
+        fs::write(dir.join("main.s"), "lda #1\n.include \"lib/util.s\"\n.incbin \"data.bin\"\njsr helper").unwrap();
+        fs::write(dir.join("lib/util.s"), "helper: rts\n.include \"chars.s\"").unwrap();
+        fs::write(dir.join("lib/chars.s"), ".byte 'A'").unwrap();
+        fs::write(dir.join("data.bin"), [1, 2, 3]).unwrap();
+
+        let assembly = Assembler::new(0x0600).assemble_file(dir.join("main.s")).unwrap();
+        assert_eq!(assembly.bytes, [0xA9, 0x01, 0x60, b'A', 0x01, 0x02, 0x03, 0x20, 0x02, 0x06]);
+        assert_eq!(assembly.symbols["helper"], 0x0602);
+        assert_eq!(
+            assembly.files,
+            [dir.join("main.s"), dir.join("lib/util.s"), dir.join("lib/chars.s"), dir.join("data.bin")]
+        );
+    }
+
+    #[test]
+    fn test_include_in() {
+        let dir = test_dir("include-in");
+        fs::write(dir.join("lib/util.s"), "helper: rts\n.incbin \"data.bin\"").unwrap();
+        fs::write(dir.join("lib/data.bin"), [1, 2]).unwrap();
+
+        // Files are looked up in the given directory, and then relative to the includer.
+        let assembly = Assembler::new(0x0600).assemble_in("jsr helper\n.include \"lib/util.s\"", &dir).unwrap();
+        assert_eq!(assembly.bytes, [0x20, 0x03, 0x06, 0x60, 0x01, 0x02]);
+
+        let err = Assembler::new(0x0600).assemble("jsr helper\n.include \"lib/util.s\"").unwrap_err();
+        assert!(matches!(err, AssemblyError::Io { .. }));
+    }
+
+    #[test]
+    fn test_include_non_ascii() {
+        let dir = test_dir("include-non-ascii");
+        fs::write(dir.join("lib/café.s"), ".incbin \"données.bin\"").unwrap();
+        fs::write(dir.join("lib/données.bin"), "é").unwrap();
+
+        let assembly = Assembler::new(0).assemble_in(".include \"lib/café.s\"", &dir).unwrap();
+        assert_eq!(assembly.bytes, "é".as_bytes());
+    }
+
+    #[test]
+    fn test_rom() {
+        // A 16 KiB ROM spread over a few thousand lines and a tree of included files.
+        let dir = test_dir("rom");
+        let mut main = String::from(".org $C000\nreset:\n");
+        for module in 0..8 {
+            main.push_str(&format!("    jsr routine{module}_0\n"));
+            let mut lib = String::new();
+            for routine in 0..100 {
+                lib.push_str(&format!(
+                    "routine{module}_{routine}: ; Copy a page\n    ldx #0\nloop{module}_{routine}:\n    lda table{module},x\n    sta $0200,x\n    inx\n    bne loop{module}_{routine}\n    rts\n"
+                ));
+            }
+            lib.push_str(&format!(".include \"tables/{module}.s\"\n"));
+            fs::write(dir.join(format!("lib/{module}.s")), lib).unwrap();
+            fs::create_dir_all(dir.join("lib/tables")).unwrap();
+            let table = format!("table{module}:\n{}", "    .byte 1, 2, 3, 4, 5, 6, 7, 8\n".repeat(32));
+            fs::write(dir.join(format!("lib/tables/{module}.s")), table).unwrap();
+            main.push_str(&format!(".include \"lib/{module}.s\"\n"));
+        }
+        main.push_str("nmi:\nirq:\n    rti\n    .org $FFFA\n    .word nmi, reset, irq\n");
+        fs::write(dir.join("main.s"), main).unwrap();
+
+        let assembly = Assembler::new(0).assemble_file(dir.join("main.s")).unwrap();
+        assert_eq!(assembly.origin, 0xC000);
+        assert_eq!(assembly.bytes.len(), 0x4000);
+        assert_eq!(assembly.symbols.len(), 8 * (2 * 100 + 1) + 3);
+        assert_eq!(assembly.bytes[..3], [0x20, 0x03, 0xC0]);
+        assert_eq!(assembly.symbols["routine0_0"], 0xC003);
+        let vectors = &assembly.bytes[0x3FFA..];
+        let nmi = assembly.symbols["nmi"].to_le_bytes();
+        assert_eq!(vectors, [nmi[0], nmi[1], 0x00, 0xC0, nmi[0], nmi[1]]);
+    }
+
+    #[test]
+    fn test_include_errors() {
+        let dir = test_dir("include-errors");
+        fs::write(dir.join("main.s"), "nop\n.include \"lib/util.s\"").unwrap();
+        fs::write(dir.join("lib/util.s"), "rts\n  .include \"chars.s\"").unwrap();
+        fs::write(dir.join("lib/chars.s"), ".byte nowhere").unwrap();
+
+        // The error is reported through the chain of files including it.
+        let err = Assembler::new(0).assemble_file(dir.join("main.s")).unwrap_err();
+        let AssemblyError::Included { path, span, error, .. } = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!((Path::new(&path), span), (dir.join("lib/util.s").as_path(), (4, 21).into()));
+        let AssemblyError::Included { path, span, error, .. } = *error else {
+            panic!("Unexpected error: {error:?}");
+        };
+        assert_eq!((Path::new(&path), span), (dir.join("lib/chars.s").as_path(), (6, 18).into()));
+        assert!(matches!(*error, AssemblyError::UndefinedLabel { span, .. } if span == (6, 7).into()));
+
+        fs::write(dir.join("lib/chars.s"), ".byte 'AB'").unwrap();
+        let err = Assembler::new(0).assemble_file(dir.join("main.s")).unwrap_err();
+        let AssemblyError::Included { error, .. } = err else { panic!("Unexpected error: {err:?}") };
+        let AssemblyError::Included { error, .. } = *error else { panic!("Unexpected error: {error:?}") };
+        assert!(matches!(*error, AssemblyError::Parse(ParseError::Lexing(_))));
+
+        fs::write(dir.join("lib/chars.s"), ".include \"../main.s\"").unwrap();
+        let err = Assembler::new(0).assemble_file(dir.join("main.s")).unwrap_err();
+        let AssemblyError::Included { error, .. } = err else { panic!("Unexpected error: {err:?}") };
+        let AssemblyError::Included { error, .. } = *error else { panic!("Unexpected error: {error:?}") };
+        assert!(matches!(*error, AssemblyError::IncludeCycle { span, .. } if span == (0, 20).into()));
+
+        let err = Assembler::new(0).assemble_file(dir.join("missing.s")).unwrap_err();
+        assert!(matches!(err, AssemblyError::Io { span: None, .. }));
+
+        fs::remove_file(dir.join("lib/chars.s")).unwrap();
+        let err = Assembler::new(0).assemble_file(dir.join("main.s")).unwrap_err();
+        let AssemblyError::Included { error, .. } = err else { panic!("Unexpected error: {err:?}") };
+        assert!(matches!(*error, AssemblyError::Io { span: Some(span), .. } if span == (6, 18).into()));
+    }
+
+    #[test]
+    fn test_branch_out_of_range() {
+        let src = format!("bne far\n{}far: rts", "nop\n".repeat(128));
+        let err = Assembler::new(0x0600).assemble(&src).expect_err("The branch is too far.");
+        let AssemblyError::BranchOutOfRange { offset, span, .. } = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(offset, 128);
+        assert_eq!(span, (0, 7).into());
+
+        // Exactly in range, in both directions.
+        let src = format!(
+            "back:\n{}bne back\nbne far\n{}far:",
+            "nop\n".repeat(126),
+            "nop\n".repeat(127)
+        );
+        let assembly = assemble(&src);
+        assert_eq!(assembly.bytes[126..130], [0xD0, 0x80, 0xD0, 0x7F]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = Assembler::new(0).assemble("jmp nowhere").unwrap_err();
+        assert!(matches!(err, AssemblyError::UndefinedLabel { span, .. } if span == (4, 7).into()));
+
+        let err = Assembler::new(0).assemble("a1:\nnop\na1: nop").unwrap_err();
+        assert!(matches!(err, AssemblyError::DuplicateLabel { span, .. } if span == (8, 2).into()));
+
+        let err = Assembler::new(0).assemble("stz $10").unwrap_err();
+        assert!(matches!(err, AssemblyError::UnsupportedMode { mode: Addr::ZeroPage, .. }));
+
+        let err = Assembler::new(0x1000).assemble("lda (far),y\nfar: nop").unwrap_err();
+        assert!(matches!(err, AssemblyError::ValueOutOfRange { value: 0x1002, .. }));
+
+        let err = Assembler::new(0x0100).assemble("lda #far\nfar:").unwrap_err();
+        assert!(matches!(err, AssemblyError::ValueOutOfRange { value: 0x0102, .. }));
+    }
+}
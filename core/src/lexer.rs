@@ -0,0 +1,539 @@
+use thiserror::Error;
+use miette::{Diagnostic, SourceSpan};
+use fixedstr::*;
+
+use crate::{Byte, Word};
+
+/// A lexer for tokenising raw assembly code. Our lexer should be able to: maintain a
+/// reference to the input source, keep track of the progress, look ahead `n` places when
+/// necessary, and raise errors when and where appropriate.
+pub struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Lexer {
+            src,
+            pos: 0
+        }
+    }
+
+    pub fn tokenise(&mut self) -> Result<Vec<Token>, LexingError> {
+        let tokens = self.tokenise_spanned()?;
+        Ok(tokens.into_iter().map(|(t, _)| t).collect())
+    }
+
+    /// Tokenise the source, keeping the span of each token so that later stages can point
+    /// at the offending bit of the source.
+    pub fn tokenise_spanned(&mut self) -> Result<Vec<(Token, SourceSpan)>, LexingError> {
+        let mut tokens = vec![];
+
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.next() {
+                Some(t) => {
+                    tokens.push((t, (start, self.pos - start).into()));
+
+                    if let Token::EOF = t {
+                        break;
+                    }
+                },
+                None => {
+                    // Highlight at least the whole character the lexer stopped at.
+                    let len = self.cur_char().map_or(1, char::len_utf8);
+                    return Err(LexingError {
+                        src: self.src.to_owned(),
+                        bad_bit: (start, (self.pos - start).max(len)).into(),
+                    });
+                }
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\r') = self.cur_char() {
+            self.pos += 1;
+        }
+    }
+
+    /// The character at `pos`, which is a byte offset into the source.
+    fn cur_char(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    /// Consume the rest of a literal quoted with `quote`, once the opening quote is
+    /// consumed, returning its contents. An escaped quote does not end the literal, and
+    /// neither does the end of the line.
+    fn quoted(&mut self, quote: char) -> Option<&'a str> {
+        let start = self.pos;
+        loop {
+            match self.cur_char()? {
+                '\n' => return None,
+                c if c == quote => break,
+                c => {
+                    self.pos += c.len_utf8();
+                    if c == '\\' {
+                        self.pos += self.cur_char()?.len_utf8();
+                    }
+                },
+            }
+        }
+        self.pos += 1;
+        Some(&self.src[start..self.pos - 1])
+    }
+
+}
+
+/// We want to implement our lexer as a stream of tokens, so we implement the iterator
+/// trait for our lexer.
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.src.len() {
+            return Some(Token::EOF);
+        }
+
+        match self.cur_char() {
+            // If we find a whitespace, skip
+            Some(' ' | '\t' | '\r') => {
+                self.skip_whitespace();
+                self.next()
+            },
+            // Identifiers are either instruction mnemonics (possibly ending in a digit, e.g.
+            // `RMB0`), labels or the X and Y registers
+            Some('a'..='z' | 'A'..='Z' | '_') => {
+                let start = self.pos;
+                while let Some(c) = self.cur_char() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let name = &self.src[start..self.pos];
+
+                if name.eq_ignore_ascii_case("x") {
+                    Some(Token::XReg)
+                } else if name.eq_ignore_ascii_case("y") {
+                    Some(Token::YReg)
+                } else if name.len() < 32 {
+                    Some(Token::Identifier(name.into()))
+                } else {
+                    None
+                }
+            },
+            // Directives are a name preceded by a dot, e.g. `.byte`
+            Some('.') => {
+                self.pos += 1;
+                let start = self.pos;
+                while let Some(c) = self.cur_char() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let name = &self.src[start..self.pos];
+
+                if name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.len() < 32 {
+                    Some(Token::Directive(name.into()))
+                } else {
+                    None
+                }
+            },
+            // Strings are kept in the source, see `unescape` for their contents
+            Some('"') => {
+                self.pos += 1;
+                unescape(self.quoted('"')?)?;
+                Some(Token::StringValue)
+            },
+            Some(':') => {
+                self.pos += 1;
+                Some(Token::Colon)
+            },
+            Some('=') => {
+                self.pos += 1;
+                Some(Token::Equals)
+            },
+            Some('#') => {
+                self.pos += 1;
+                Some(Token::ImmediateSpecifier)
+            },
+            // Tokenise hex values, a byte when written with up to 2 digits and a word
+            // with up to 4
+            Some('$') => {
+                self.pos += 1; // Skip the '$'
+                let start = self.pos;
+                while let Some(c) = self.cur_char() {
+                    if c.is_ascii_hexdigit() {
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let hex_str = &self.src[start..self.pos];
+
+                match hex_str.len() {
+                    1..=2 => Some(Token::ByteValue(u8::from_str_radix(hex_str, 16).ok()?)),
+                    3..=4 => Some(Token::WordValue(u16::from_str_radix(hex_str, 16).ok()?)),
+                    _ => None,
+                }
+            },
+            // Binary values are a byte with up to 8 digits and a word with up to 16
+            Some('%') => {
+                self.pos += 1;
+                let start = self.pos;
+                while let Some(c) = self.cur_char() {
+                    if matches!(c, '0' | '1') {
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let bin_str = &self.src[start..self.pos];
+
+                match bin_str.len() {
+                    1..=8 => Some(Token::ByteValue(u8::from_str_radix(bin_str, 2).ok()?)),
+                    9..=16 => Some(Token::WordValue(u16::from_str_radix(bin_str, 2).ok()?)),
+                    _ => None,
+                }
+            },
+            // Decimal values are a byte when they fit in one
+            Some('0'..='9') => {
+                let start = self.pos;
+                while let Some('0'..='9') = self.cur_char() {
+                    self.pos += 1;
+                }
+                let val = self.src[start..self.pos].parse::<u16>().ok()?;
+
+                match Byte::try_from(val) {
+                    Ok(val) => Some(Token::ByteValue(val)),
+                    Err(_) => Some(Token::WordValue(val)),
+                }
+            },
+            // Character literals such as 'A' or '\'' stand for their ASCII code, and are
+            // unescaped like strings
+            Some('\'') => {
+                self.pos += 1;
+                match unescape(self.quoted('\'')?)?[..] {
+                    [byte] => Some(Token::ByteValue(byte)),
+                    _ => None,
+                }
+            },
+            // `<` and `>` take the low and high byte of a value, unless doubled into a shift
+            Some(c @ ('<' | '>')) => {
+                self.pos += 1;
+                if self.cur_char() == Some(c) {
+                    self.pos += 1;
+                    Some(if c == '<' { Token::ShiftLeft } else { Token::ShiftRight })
+                } else {
+                    Some(if c == '<' { Token::LessThan } else { Token::GreaterThan })
+                }
+            },
+            Some(c @ ('+' | '-' | '*' | '/' | '&' | '|' | '^' | '~')) => {
+                self.pos += 1;
+                Some(match c {
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '/' => Token::Slash,
+                    '&' => Token::Ampersand,
+                    '|' => Token::Pipe,
+                    '^' => Token::Caret,
+                    _ => Token::Tilde,
+                })
+            },
+            Some('(') => {
+                self.pos += 1;
+                Some(Token::LeftBracket)
+            },
+            Some(')') => {
+                self.pos += 1;
+                Some(Token::RightBracket)
+            },
+            Some(',') => {
+                self.pos += 1;
+                Some(Token::Comma)
+            },
+            // Comments run until the end of the line
+            Some(';') => {
+                while let Some(c) = self.cur_char().filter(|&c| c != '\n') {
+                    self.pos += c.len_utf8();
+                }
+                Some(Token::CommentDelimiter)
+            },
+            Some('\n') => {
+                self.pos += 1;
+                Some(Token::NewLine)
+            },
+            _ => None
+        }
+    }
+}
+
+/// The bytes of a string or character literal, given without its quotes. Literals are
+/// made of printable characters, encoded as UTF-8, and the escape sequences `\n`, `\r`,
+/// `\t`, `\0`, `\\`, `\"` and `\'`.
+pub fn unescape(literal: &str) -> Option<Vec<Byte>> {
+    let mut bytes = vec![];
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        let byte = match c {
+            '\\' => match chars.next()? {
+                'n' => b'\n',
+                'r' => b'\r',
+                't' => b'\t',
+                '0' => 0,
+                c @ ('\\' | '"' | '\'') => c as Byte,
+                _ => return None,
+            },
+            c if c.is_control() => return None,
+            c => {
+                bytes.extend(c.encode_utf8(&mut [0; 4]).bytes());
+                continue;
+            },
+        };
+        bytes.push(byte);
+    }
+    Some(bytes)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Token {
+    /// A name of at most 31 characters, with its case preserved.
+    Identifier(str32),
+    /// The name of a directive, without the leading dot.
+    Directive(str32),
+    /// A string in double quotes, whose contents are read from the source with
+    /// [`unescape`].
+    StringValue,
+    Colon,
+    Equals,
+    Comma,
+    XReg,
+    YReg,
+    LeftBracket,
+    RightBracket,
+    ImmediateSpecifier,
+    Plus,
+    Minus,
+    /// Multiplication, or the address of the current instruction.
+    Star,
+    Slash,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
+    /// The low byte of a value.
+    LessThan,
+    /// The high byte of a value.
+    GreaterThan,
+    ShiftLeft,
+    ShiftRight,
+    /// A value written as a byte, e.g. `$04`, `%0101`, `42` or `'A'`.
+    ByteValue(Byte),
+    /// A value written as a word, e.g. `$0004` or `1024`.
+    WordValue(Word),
+    CommentDelimiter,
+    NewLine,
+    EOF
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("Error while attempting to tokenise the provided source.")]
+pub struct LexingError {
+    // The Source that we're gonna be printing snippets out of.
+    // This can be a String if you don't have or care about file names.
+    #[source_code]
+    src: String,
+    // Snippets and highlights can be included in the diagnostic!
+    #[label("This bit here")]
+    bad_bit: SourceSpan,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lexer() {
+        let src = "lda       #$3da5\nSTA  %00100110";
+        let mut lexer = Lexer::new(src);
+        let tokens = lexer.tokenise().expect("An array of tokens should be returned.");
+        assert_eq!(
+            tokens,
+            [
+                Token::Identifier("lda".into()),
+                Token::ImmediateSpecifier,
+                Token::WordValue(0x3DA5),
+                Token::NewLine,
+                Token::Identifier("STA".into()),
+                Token::ByteValue(0b00100110),
+                Token::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_spans() {
+        let src = "rmb0 $04 ; clear bit 0\nloop:\tlda ($04),y";
+        let mut lexer = Lexer::new(src);
+        let tokens = lexer.tokenise_spanned().expect("An array of tokens should be returned.");
+        let spans: Vec<(Token, usize, usize)> = tokens
+            .into_iter()
+            .map(|(t, span)| (t, span.offset(), span.len()))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (Token::Identifier("rmb0".into()), 0, 4),
+                (Token::ByteValue(0x04), 5, 3),
+                (Token::CommentDelimiter, 9, 13),
+                (Token::NewLine, 22, 1),
+                (Token::Identifier("loop".into()), 23, 4),
+                (Token::Colon, 27, 1),
+                (Token::Identifier("lda".into()), 29, 3),
+                (Token::LeftBracket, 33, 1),
+                (Token::ByteValue(0x04), 34, 3),
+                (Token::RightBracket, 37, 1),
+                (Token::Comma, 38, 1),
+                (Token::YReg, 39, 1),
+                (Token::EOF, 40, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_expressions() {
+        let src = "#<(table+2) *-$123 %101 'A' 42 1024 ~>x_1<<3>>1/4&$F|'z'^7";
+        let mut lexer = Lexer::new(src);
+        let tokens = lexer.tokenise().expect("An array of tokens should be returned.");
+        assert_eq!(
+            tokens,
+            [
+                Token::ImmediateSpecifier,
+                Token::LessThan,
+                Token::LeftBracket,
+                Token::Identifier("table".into()),
+                Token::Plus,
+                Token::ByteValue(2),
+                Token::RightBracket,
+                Token::Star,
+                Token::Minus,
+                Token::WordValue(0x0123),
+                Token::ByteValue(0b101),
+                Token::ByteValue(b'A'),
+                Token::ByteValue(42),
+                Token::WordValue(1024),
+                Token::Tilde,
+                Token::GreaterThan,
+                Token::Identifier("x_1".into()),
+                Token::ShiftLeft,
+                Token::ByteValue(3),
+                Token::ShiftRight,
+                Token::ByteValue(1),
+                Token::Slash,
+                Token::ByteValue(4),
+                Token::Ampersand,
+                Token::ByteValue(0x0F),
+                Token::Pipe,
+                Token::ByteValue(b'z'),
+                Token::Caret,
+                Token::ByteValue(7),
+                Token::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_directives() {
+        let src = r#"*= $0600
+msg: .BYTE "Hi \"you\"\n", 0"#;
+        let mut lexer = Lexer::new(src);
+        let tokens = lexer.tokenise_spanned().expect("An array of tokens should be returned.");
+        let tokens: Vec<Token> = tokens.into_iter().map(|(t, _)| t).collect();
+        assert_eq!(
+            tokens,
+            [
+                Token::Star,
+                Token::Equals,
+                Token::WordValue(0x0600),
+                Token::NewLine,
+                Token::Identifier("msg".into()),
+                Token::Colon,
+                Token::Directive("BYTE".into()),
+                Token::StringValue,
+                Token::Comma,
+                Token::ByteValue(0),
+                Token::EOF
+            ]
+        );
+        assert_eq!(unescape(r#"Hi \"you\"\n"#), Some(b"Hi \"you\"\n".to_vec()));
+        assert_eq!(unescape(r"\x"), None);
+    }
+
+    #[test]
+    fn test_lexer_non_ascii() {
+        let src = "lda #1 ; éééé\n; café\n.byte \"é\", '\\''";
+        let mut lexer = Lexer::new(src);
+        let tokens = lexer.tokenise_spanned().expect("An array of tokens should be returned.");
+        let spans: Vec<(Token, usize, usize)> = tokens
+            .into_iter()
+            .map(|(t, span)| (t, span.offset(), span.len()))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (Token::Identifier("lda".into()), 0, 3),
+                (Token::ImmediateSpecifier, 4, 1),
+                (Token::ByteValue(1), 5, 1),
+                (Token::CommentDelimiter, 7, 10),
+                (Token::NewLine, 17, 1),
+                (Token::CommentDelimiter, 18, 7),
+                (Token::NewLine, 25, 1),
+                (Token::Directive("byte".into()), 26, 5),
+                (Token::StringValue, 32, 4),
+                (Token::Comma, 36, 1),
+                (Token::ByteValue(b'\''), 38, 4),
+                (Token::EOF, 42, 0),
+            ]
+        );
+        assert_eq!(unescape("é"), Some(vec![0xC3, 0xA9]));
+    }
+
+    #[test]
+    fn test_lexer_large_input() {
+        // Lexing is linear in the size of the source.
+        let src = "loop: lda ($04),y ; copy a byte\n  sta $0200,x\n  bne loop\n".repeat(20_000);
+        let mut lexer = Lexer::new(&src);
+        let tokens = lexer.tokenise().expect("An array of tokens should be returned.");
+        assert_eq!(tokens.len(), 20_000 * 18 + 1);
+    }
+
+    #[test]
+    fn test_lexer_error() {
+        for (src, span) in [
+            ("LDA #$12345", (5, 6)),
+            ("LDA #%10000000000000000", (5, 18)),
+            ("LDA #65536", (5, 5)),
+            ("LDA #'AB'", (5, 4)),
+            ("LDA #'é'", (5, 4)),
+            ("LDA #'\\q'", (5, 4)),
+            ("LDA é", (4, 2)),
+            ("LDA @", (4, 1)),
+            (".byte \"open", (6, 5)),
+            (".byte \"\\q\"", (6, 4)),
+            (". byte", (0, 1)),
+        ] {
+            let mut lexer = Lexer::new(src);
+            let err = lexer.tokenise().expect_err(src);
+            assert_eq!(err.bad_bit, span.into(), "{src}");
+        }
+    }
+}
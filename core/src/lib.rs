@@ -0,0 +1,18 @@
+//! What the emulator and the `asm!` macro share: the addressing modes, the chips of the
+//! 6502 family and their opcodes, and the assembler itself. It lives in its own crate so
+//! that the macro can run the assembler at compile time.
+
+mod addr;
+mod variant;
+
+pub mod opcodes;
+pub mod lexer;
+pub mod parser;
+pub mod assembler;
+
+pub use addr::Addr;
+pub use variant::Variant;
+
+// These represent the types of the emulated 6502 CPU.
+type Byte = u8;
+type Word = u16;
@@ -0,0 +1,197 @@
+//! The mnemonic and addressing mode of every opcode, which is all the assembler needs to
+//! know about the instruction set. The emulator decodes the same opcodes to the
+//! instructions it executes, and checks that both agree.
+
+use crate::{Addr, Byte, Variant};
+use Addr::*;
+
+/// Encode the instruction with the given mnemonic and addressing mode for the CPU
+/// `variant`. The undocumented instructions of the NMOS 6502 can be encoded too, but not
+/// the reserved opcodes of the 65C02.
+pub fn encode(mnemonic: &str, mode: Addr, variant: Variant) -> Option<Byte> {
+    // Undocumented duplicates are encoded to the first of them, but the documented NOP
+    // comes after its undocumented ones.
+    if (mnemonic, mode) == ("NOP", Implicit) {
+        return Some(0xEA);
+    }
+    (0..=0xFF).find(|&code| opcode(code, variant) == Some((mnemonic, mode)))
+}
+
+/// The mnemonic and addressing mode of an opcode of the CPU `variant`, `None` for the
+/// reserved opcodes of the 65C02.
+pub fn opcode(code: Byte, variant: Variant) -> Option<(&'static str, Addr)> {
+    match variant {
+        Variant::Nmos6502 | Variant::Ricoh2A03 => Some(NMOS[code as usize]),
+        // The bit manipulation instructions are Rockwell additions.
+        Variant::Cmos65C02 if code & 0x07 == 0x07 => None,
+        // WAI and STP are WDC additions.
+        Variant::Cmos65C02 | Variant::Rockwell65C02 if matches!(code, 0xCB | 0xDB) => None,
+        _ => CMOS[code as usize],
+    }
+}
+
+/// Opcodes of the NMOS 6502, the undocumented ones included.
+const NMOS: [(&str, Addr); 256] = [
+    // $00
+    ("BRK", Implicit), ("ORA", XIndirect), ("JAM", Implicit), ("SLO", XIndirect),
+    ("NOP", ZeroPage), ("ORA", ZeroPage), ("ASL", ZeroPage), ("SLO", ZeroPage),
+    ("PHP", Implicit), ("ORA", Immediate), ("ASL", Accummulator), ("ANC", Immediate),
+    ("NOP", Absolute), ("ORA", Absolute), ("ASL", Absolute), ("SLO", Absolute),
+    // $10
+    ("BPL", Relative), ("ORA", IndirectY), ("JAM", Implicit), ("SLO", IndirectY),
+    ("NOP", ZeroPageX), ("ORA", ZeroPageX), ("ASL", ZeroPageX), ("SLO", ZeroPageX),
+    ("CLC", Implicit), ("ORA", AbsoluteY), ("NOP", Implicit), ("SLO", AbsoluteY),
+    ("NOP", AbsoluteX), ("ORA", AbsoluteX), ("ASL", AbsoluteX), ("SLO", AbsoluteX),
+    // $20
+    ("JSR", Absolute), ("AND", XIndirect), ("JAM", Implicit), ("RLA", XIndirect),
+    ("BIT", ZeroPage), ("AND", ZeroPage), ("ROL", ZeroPage), ("RLA", ZeroPage),
+    ("PLP", Implicit), ("AND", Immediate), ("ROL", Accummulator), ("ANC", Immediate),
+    ("BIT", Absolute), ("AND", Absolute), ("ROL", Absolute), ("RLA", Absolute),
+    // $30
+    ("BMI", Relative), ("AND", IndirectY), ("JAM", Implicit), ("RLA", IndirectY),
+    ("NOP", ZeroPageX), ("AND", ZeroPageX), ("ROL", ZeroPageX), ("RLA", ZeroPageX),
+    ("SEC", Implicit), ("AND", AbsoluteY), ("NOP", Implicit), ("RLA", AbsoluteY),
+    ("NOP", AbsoluteX), ("AND", AbsoluteX), ("ROL", AbsoluteX), ("RLA", AbsoluteX),
+    // $40
+    ("RTI", Implicit), ("EOR", XIndirect), ("JAM", Implicit), ("SRE", XIndirect),
+    ("NOP", ZeroPage), ("EOR", ZeroPage), ("LSR", ZeroPage), ("SRE", ZeroPage),
+    ("PHA", Implicit), ("EOR", Immediate), ("LSR", Accummulator), ("ALR", Immediate),
+    ("JMP", Absolute), ("EOR", Absolute), ("LSR", Absolute), ("SRE", Absolute),
+    // $50
+    ("BVC", Relative), ("EOR", IndirectY), ("JAM", Implicit), ("SRE", IndirectY),
+    ("NOP", ZeroPageX), ("EOR", ZeroPageX), ("LSR", ZeroPageX), ("SRE", ZeroPageX),
+    ("CLI", Implicit), ("EOR", AbsoluteY), ("NOP", Implicit), ("SRE", AbsoluteY),
+    ("NOP", AbsoluteX), ("EOR", AbsoluteX), ("LSR", AbsoluteX), ("SRE", AbsoluteX),
+    // $60
+    ("RTS", Implicit), ("ADC", XIndirect), ("JAM", Implicit), ("RRA", XIndirect),
+    ("NOP", ZeroPage), ("ADC", ZeroPage), ("ROR", ZeroPage), ("RRA", ZeroPage),
+    ("PLA", Implicit), ("ADC", Immediate), ("ROR", Accummulator), ("ARR", Immediate),
+    ("JMP", Indirect), ("ADC", Absolute), ("ROR", Absolute), ("RRA", Absolute),
+    // $70
+    ("BVS", Relative), ("ADC", IndirectY), ("JAM", Implicit), ("RRA", IndirectY),
+    ("NOP", ZeroPageX), ("ADC", ZeroPageX), ("ROR", ZeroPageX), ("RRA", ZeroPageX),
+    ("SEI", Implicit), ("ADC", AbsoluteY), ("NOP", Implicit), ("RRA", AbsoluteY),
+    ("NOP", AbsoluteX), ("ADC", AbsoluteX), ("ROR", AbsoluteX), ("RRA", AbsoluteX),
+    // $80
+    ("NOP", Immediate), ("STA", XIndirect), ("NOP", Immediate), ("SAX", XIndirect),
+    ("STY", ZeroPage), ("STA", ZeroPage), ("STX", ZeroPage), ("SAX", ZeroPage),
+    ("DEY", Implicit), ("NOP", Immediate), ("TXA", Implicit), ("ANE", Immediate),
+    ("STY", Absolute), ("STA", Absolute), ("STX", Absolute), ("SAX", Absolute),
+    // $90
+    ("BCC", Relative), ("STA", IndirectY), ("JAM", Implicit), ("SHA", IndirectY),
+    ("STY", ZeroPageX), ("STA", ZeroPageX), ("STX", ZeroPageY), ("SAX", ZeroPageY),
+    ("TYA", Implicit), ("STA", AbsoluteY), ("TXS", Implicit), ("TAS", AbsoluteY),
+    ("SHY", AbsoluteX), ("STA", AbsoluteX), ("SHX", AbsoluteY), ("SHA", AbsoluteY),
+    // $A0
+    ("LDY", Immediate), ("LDA", XIndirect), ("LDX", Immediate), ("LAX", XIndirect),
+    ("LDY", ZeroPage), ("LDA", ZeroPage), ("LDX", ZeroPage), ("LAX", ZeroPage),
+    ("TAY", Implicit), ("LDA", Immediate), ("TAX", Implicit), ("LXA", Immediate),
+    ("LDY", Absolute), ("LDA", Absolute), ("LDX", Absolute), ("LAX", Absolute),
+    // $B0
+    ("BCS", Relative), ("LDA", IndirectY), ("JAM", Implicit), ("LAX", IndirectY),
+    ("LDY", ZeroPageX), ("LDA", ZeroPageX), ("LDX", ZeroPageY), ("LAX", ZeroPageY),
+    ("CLV", Implicit), ("LDA", AbsoluteY), ("TSX", Implicit), ("LAS", AbsoluteY),
+    ("LDY", AbsoluteX), ("LDA", AbsoluteX), ("LDX", AbsoluteY), ("LAX", AbsoluteY),
+    // $C0
+    ("CPY", Immediate), ("CMP", XIndirect), ("NOP", Immediate), ("DCP", XIndirect),
+    ("CPY", ZeroPage), ("CMP", ZeroPage), ("DEC", ZeroPage), ("DCP", ZeroPage),
+    ("INY", Implicit), ("CMP", Immediate), ("DEX", Implicit), ("SBX", Immediate),
+    ("CPY", Absolute), ("CMP", Absolute), ("DEC", Absolute), ("DCP", Absolute),
+    // $D0
+    ("BNE", Relative), ("CMP", IndirectY), ("JAM", Implicit), ("DCP", IndirectY),
+    ("NOP", ZeroPageX), ("CMP", ZeroPageX), ("DEC", ZeroPageX), ("DCP", ZeroPageX),
+    ("CLD", Implicit), ("CMP", AbsoluteY), ("NOP", Implicit), ("DCP", AbsoluteY),
+    ("NOP", AbsoluteX), ("CMP", AbsoluteX), ("DEC", AbsoluteX), ("DCP", AbsoluteX),
+    // $E0
+    ("CPX", Immediate), ("SBC", XIndirect), ("NOP", Immediate), ("ISC", XIndirect),
+    ("CPX", ZeroPage), ("SBC", ZeroPage), ("INC", ZeroPage), ("ISC", ZeroPage),
+    ("INX", Implicit), ("SBC", Immediate), ("NOP", Implicit), ("SBC", Immediate),
+    ("CPX", Absolute), ("SBC", Absolute), ("INC", Absolute), ("ISC", Absolute),
+    // $F0
+    ("BEQ", Relative), ("SBC", IndirectY), ("JAM", Implicit), ("ISC", IndirectY),
+    ("NOP", ZeroPageX), ("SBC", ZeroPageX), ("INC", ZeroPageX), ("ISC", ZeroPageX),
+    ("SED", Implicit), ("SBC", AbsoluteY), ("NOP", Implicit), ("ISC", AbsoluteY),
+    ("NOP", AbsoluteX), ("SBC", AbsoluteX), ("INC", AbsoluteX), ("ISC", AbsoluteX),];
+
+/// Opcodes of the WDC 65C02, the most complete of the family, `None` for the reserved ones.
+const CMOS: [Option<(&str, Addr)>; 256] = [
+    // $00
+    Some(("BRK", Implicit)), Some(("ORA", XIndirect)), None, None,
+    Some(("TSB", ZeroPage)), Some(("ORA", ZeroPage)), Some(("ASL", ZeroPage)), Some(("RMB0", ZeroPage)),
+    Some(("PHP", Implicit)), Some(("ORA", Immediate)), Some(("ASL", Accummulator)), None,
+    Some(("TSB", Absolute)), Some(("ORA", Absolute)), Some(("ASL", Absolute)), Some(("BBR0", ZeroPageRelative)),
+    // $10
+    Some(("BPL", Relative)), Some(("ORA", IndirectY)), Some(("ORA", ZeroPageIndirect)), None,
+    Some(("TRB", ZeroPage)), Some(("ORA", ZeroPageX)), Some(("ASL", ZeroPageX)), Some(("RMB1", ZeroPage)),
+    Some(("CLC", Implicit)), Some(("ORA", AbsoluteY)), Some(("INC", Accummulator)), None,
+    Some(("TRB", Absolute)), Some(("ORA", AbsoluteX)), Some(("ASL", AbsoluteX)), Some(("BBR1", ZeroPageRelative)),
+    // $20
+    Some(("JSR", Absolute)), Some(("AND", XIndirect)), None, None,
+    Some(("BIT", ZeroPage)), Some(("AND", ZeroPage)), Some(("ROL", ZeroPage)), Some(("RMB2", ZeroPage)),
+    Some(("PLP", Implicit)), Some(("AND", Immediate)), Some(("ROL", Accummulator)), None,
+    Some(("BIT", Absolute)), Some(("AND", Absolute)), Some(("ROL", Absolute)), Some(("BBR2", ZeroPageRelative)),
+    // $30
+    Some(("BMI", Relative)), Some(("AND", IndirectY)), Some(("AND", ZeroPageIndirect)), None,
+    Some(("BIT", ZeroPageX)), Some(("AND", ZeroPageX)), Some(("ROL", ZeroPageX)), Some(("RMB3", ZeroPage)),
+    Some(("SEC", Implicit)), Some(("AND", AbsoluteY)), Some(("DEC", Accummulator)), None,
+    Some(("BIT", AbsoluteX)), Some(("AND", AbsoluteX)), Some(("ROL", AbsoluteX)), Some(("BBR3", ZeroPageRelative)),
+    // $40
+    Some(("RTI", Implicit)), Some(("EOR", XIndirect)), None, None,
+    None, Some(("EOR", ZeroPage)), Some(("LSR", ZeroPage)), Some(("RMB4", ZeroPage)),
+    Some(("PHA", Implicit)), Some(("EOR", Immediate)), Some(("LSR", Accummulator)), None,
+    Some(("JMP", Absolute)), Some(("EOR", Absolute)), Some(("LSR", Absolute)), Some(("BBR4", ZeroPageRelative)),
+    // $50
+    Some(("BVC", Relative)), Some(("EOR", IndirectY)), Some(("EOR", ZeroPageIndirect)), None,
+    None, Some(("EOR", ZeroPageX)), Some(("LSR", ZeroPageX)), Some(("RMB5", ZeroPage)),
+    Some(("CLI", Implicit)), Some(("EOR", AbsoluteY)), Some(("PHY", Implicit)), None,
+    None, Some(("EOR", AbsoluteX)), Some(("LSR", AbsoluteX)), Some(("BBR5", ZeroPageRelative)),
+    // $60
+    Some(("RTS", Implicit)), Some(("ADC", XIndirect)), None, None,
+    Some(("STZ", ZeroPage)), Some(("ADC", ZeroPage)), Some(("ROR", ZeroPage)), Some(("RMB6", ZeroPage)),
+    Some(("PLA", Implicit)), Some(("ADC", Immediate)), Some(("ROR", Accummulator)), None,
+    Some(("JMP", Indirect)), Some(("ADC", Absolute)), Some(("ROR", Absolute)), Some(("BBR6", ZeroPageRelative)),
+    // $70
+    Some(("BVS", Relative)), Some(("ADC", IndirectY)), Some(("ADC", ZeroPageIndirect)), None,
+    Some(("STZ", ZeroPageX)), Some(("ADC", ZeroPageX)), Some(("ROR", ZeroPageX)), Some(("RMB7", ZeroPage)),
+    Some(("SEI", Implicit)), Some(("ADC", AbsoluteY)), Some(("PLY", Implicit)), None,
+    Some(("JMP", AbsoluteXIndirect)), Some(("ADC", AbsoluteX)), Some(("ROR", AbsoluteX)), Some(("BBR7", ZeroPageRelative)),
+    // $80
+    Some(("BRA", Relative)), Some(("STA", XIndirect)), None, None,
+    Some(("STY", ZeroPage)), Some(("STA", ZeroPage)), Some(("STX", ZeroPage)), Some(("SMB0", ZeroPage)),
+    Some(("DEY", Implicit)), Some(("BIT", Immediate)), Some(("TXA", Implicit)), None,
+    Some(("STY", Absolute)), Some(("STA", Absolute)), Some(("STX", Absolute)), Some(("BBS0", ZeroPageRelative)),
+    // $90
+    Some(("BCC", Relative)), Some(("STA", IndirectY)), Some(("STA", ZeroPageIndirect)), None,
+    Some(("STY", ZeroPageX)), Some(("STA", ZeroPageX)), Some(("STX", ZeroPageY)), Some(("SMB1", ZeroPage)),
+    Some(("TYA", Implicit)), Some(("STA", AbsoluteY)), Some(("TXS", Implicit)), None,
+    Some(("STZ", Absolute)), Some(("STA", AbsoluteX)), Some(("STZ", AbsoluteX)), Some(("BBS1", ZeroPageRelative)),
+    // $A0
+    Some(("LDY", Immediate)), Some(("LDA", XIndirect)), Some(("LDX", Immediate)), None,
+    Some(("LDY", ZeroPage)), Some(("LDA", ZeroPage)), Some(("LDX", ZeroPage)), Some(("SMB2", ZeroPage)),
+    Some(("TAY", Implicit)), Some(("LDA", Immediate)), Some(("TAX", Implicit)), None,
+    Some(("LDY", Absolute)), Some(("LDA", Absolute)), Some(("LDX", Absolute)), Some(("BBS2", ZeroPageRelative)),
+    // $B0
+    Some(("BCS", Relative)), Some(("LDA", IndirectY)), Some(("LDA", ZeroPageIndirect)), None,
+    Some(("LDY", ZeroPageX)), Some(("LDA", ZeroPageX)), Some(("LDX", ZeroPageY)), Some(("SMB3", ZeroPage)),
+    Some(("CLV", Implicit)), Some(("LDA", AbsoluteY)), Some(("TSX", Implicit)), None,
+    Some(("LDY", AbsoluteX)), Some(("LDA", AbsoluteX)), Some(("LDX", AbsoluteY)), Some(("BBS3", ZeroPageRelative)),
+    // $C0
+    Some(("CPY", Immediate)), Some(("CMP", XIndirect)), None, None,
+    Some(("CPY", ZeroPage)), Some(("CMP", ZeroPage)), Some(("DEC", ZeroPage)), Some(("SMB4", ZeroPage)),
+    Some(("INY", Implicit)), Some(("CMP", Immediate)), Some(("DEX", Implicit)), Some(("WAI", Implicit)),
+    Some(("CPY", Absolute)), Some(("CMP", Absolute)), Some(("DEC", Absolute)), Some(("BBS4", ZeroPageRelative)),
+    // $D0
+    Some(("BNE", Relative)), Some(("CMP", IndirectY)), Some(("CMP", ZeroPageIndirect)), None,
+    None, Some(("CMP", ZeroPageX)), Some(("DEC", ZeroPageX)), Some(("SMB5", ZeroPage)),
+    Some(("CLD", Implicit)), Some(("CMP", AbsoluteY)), Some(("PHX", Implicit)), Some(("STP", Implicit)),
+    None, Some(("CMP", AbsoluteX)), Some(("DEC", AbsoluteX)), Some(("BBS5", ZeroPageRelative)),
+    // $E0
+    Some(("CPX", Immediate)), Some(("SBC", XIndirect)), None, None,
+    Some(("CPX", ZeroPage)), Some(("SBC", ZeroPage)), Some(("INC", ZeroPage)), Some(("SMB6", ZeroPage)),
+    Some(("INX", Implicit)), Some(("SBC", Immediate)), Some(("NOP", Implicit)), None,
+    Some(("CPX", Absolute)), Some(("SBC", Absolute)), Some(("INC", Absolute)), Some(("BBS6", ZeroPageRelative)),
+    // $F0
+    Some(("BEQ", Relative)), Some(("SBC", IndirectY)), Some(("SBC", ZeroPageIndirect)), None,
+    None, Some(("SBC", ZeroPageX)), Some(("INC", ZeroPageX)), Some(("SMB7", ZeroPage)),
+    Some(("SED", Implicit)), Some(("SBC", AbsoluteY)), Some(("PLX", Implicit)), None,
+    None, Some(("SBC", AbsoluteX)), Some(("INC", AbsoluteX)), Some(("BBS7", ZeroPageRelative)),];
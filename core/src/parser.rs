@@ -0,0 +1,876 @@
+use thiserror::Error;
+use miette::{Diagnostic, SourceSpan};
+use fixedstr::*;
+
+use crate::{Addr, Byte, Word};
+use crate::lexer::{unescape, Lexer, LexingError, Token};
+
+/// Mnemonics of every instruction known to the assembler, across all the CPU variants
+/// (undocumented NMOS instructions included). The bit manipulation instructions of the
+/// Rockwell and WDC 65C02 (e.g. `RMB0`) are recognised separately, see [`is_bit_op`].
+const MNEMONICS: [&str; 86] = [
+    // Documented
+    "ADC", "AND", "ASL", "BCC", "BCS", "BEQ", "BIT", "BMI", "BNE", "BPL", "BRK", "BVC",
+    "BVS", "CLC", "CLD", "CLI", "CLV", "CMP", "CPX", "CPY", "DEC", "DEX", "DEY", "EOR",
+    "INC", "INX", "INY", "JMP", "JSR", "LDA", "LDX", "LDY", "LSR", "NOP", "ORA", "PHA",
+    "PHP", "PLA", "PLP", "ROL", "ROR", "RTI", "RTS", "SBC", "SEC", "SED", "SEI", "STA",
+    "STX", "STY", "TAX", "TAY", "TSX", "TXA", "TXS", "TYA",
+    // 65C02
+    "BRA", "PHX", "PHY", "PLX", "PLY", "STZ", "TRB", "TSB", "WAI", "STP",
+    // Undocumented
+    "ALR", "ANC", "ANE", "ARR", "DCP", "ISC", "JAM", "LAS", "LAX", "LXA", "RLA", "RRA",
+    "SAX", "SBX", "SHA", "SHX", "SHY", "SLO", "SRE", "TAS",
+];
+
+/// Whether `mnemonic` is one of the bit manipulation instructions, i.e. `RMB`, `SMB`, `BBR`
+/// or `BBS` followed by the number of the bit (0 to 7).
+fn is_bit_op(mnemonic: &str) -> bool {
+    match mnemonic.as_bytes() {
+        [op @ .., b'0'..=b'7'] => matches!(op, b"RMB" | b"SMB" | b"BBR" | b"BBS"),
+        _ => false,
+    }
+}
+
+/// Whether `mnemonic` is a branch taking a single relative operand.
+fn is_branch(mnemonic: &str) -> bool {
+    matches!(mnemonic, "BCC" | "BCS" | "BEQ" | "BMI" | "BNE" | "BPL" | "BRA" | "BVC" | "BVS")
+}
+
+/// Whether `mnemonic` operates on the accumulator when it has no operand.
+fn has_accumulator(mnemonic: &str) -> bool {
+    matches!(mnemonic, "ASL" | "LSR" | "ROL" | "ROR" | "INC" | "DEC")
+}
+
+/// Binary operators by precedence, from the loosest binding to the tightest.
+const PRECEDENCE: [&[BinaryOp]; 6] = [
+    &[BinaryOp::Or],
+    &[BinaryOp::Xor],
+    &[BinaryOp::And],
+    &[BinaryOp::ShiftLeft, BinaryOp::ShiftRight],
+    &[BinaryOp::Add, BinaryOp::Sub],
+    &[BinaryOp::Mul, BinaryOp::Div],
+];
+
+/// A line of assembly, or the first part of it when a label is followed by an instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    /// A label definition such as `some_label:`, naming the address of whatever follows.
+    Label {
+        name: str32,
+        span: SourceSpan,
+    },
+    Instruction(Instruction),
+    Directive(Directive),
+}
+
+impl Statement {
+    /// Location of the statement in the source.
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Statement::Label { span, .. } => *span,
+            Statement::Instruction(ins) => ins.span,
+            Statement::Directive(directive) => directive.span,
+        }
+    }
+}
+
+/// An assembler directive such as `.byte $01, $02`, which lays out data rather than
+/// instructions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    /// Location of the directive in the source, from its name to the end of its arguments.
+    pub span: SourceSpan,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `.org` or `*=`, moving the location counter to the given address.
+    Org(Expr),
+    /// `.byte` or `.db`, a list of bytes and strings.
+    Byte(Vec<Data>),
+    /// `.word` or `.dw`, a list of little endian words.
+    Word(Vec<Expr>),
+    /// `.res` or `.ds`, reserving a number of bytes, filled with the optional value (or 0).
+    Res(Expr, Option<Expr>),
+    /// `.align`, filling with the optional value (or 0) up to the next multiple of the
+    /// alignment.
+    Align(Expr, Option<Expr>),
+    /// `.include`, assembling another source file in place.
+    Include(String),
+    /// `.incbin`, inserting the contents of a binary file.
+    Incbin(String),
+}
+
+/// An item of a `.byte` directive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Data {
+    Byte(Expr),
+    String(Vec<Byte>),
+}
+
+/// An instruction parsed from a line of assembly, whose addressing mode has been resolved
+/// from the syntax of its operand (e.g. `LDA ($04),Y` is [`Addr::IndirectY`]). Operands
+/// starting with a byte or a `<`/`>` operator get the zero page modes, and any other the
+/// absolute modes. Picking the actual encoding is left to the assembler.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    /// Upper case mnemonic, e.g. `LDA` or `BBS3`.
+    pub mnemonic: str8,
+    pub mode: Addr,
+    pub operand: Operand,
+    /// Location of the instruction in the source, from the mnemonic to the end of the
+    /// operand.
+    pub span: SourceSpan,
+}
+
+/// The operand of a parsed instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+    /// Implicit and accumulator instructions have no operand.
+    None,
+    /// A byte for the immediate and zero page modes, an address otherwise. The operand of
+    /// a branch is the address of its target rather than the relative offset.
+    Value(Expr),
+    /// The zero page address tested by `BBR` or `BBS`, and the address of the branch
+    /// target.
+    BitBranch(Expr, Expr),
+}
+
+/// An expression written in an operand, such as `#<(table+2)` or `*+3`. It is evaluated
+/// by the assembler, once the labels it refers to are known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    /// Location of the whole expression in the source.
+    pub span: SourceSpan,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprKind {
+    Number(Word),
+    /// A reference to a label, which may be defined after the instruction.
+    Label(str32),
+    /// `*`, the address of the current instruction.
+    Pc,
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// `-`
+    Neg,
+    /// `~`
+    Not,
+    /// `<`, the low byte.
+    Low,
+    /// `>`, the high byte.
+    High,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    And,
+    Or,
+    Xor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl BinaryOp {
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::Plus => Some(BinaryOp::Add),
+            Token::Minus => Some(BinaryOp::Sub),
+            Token::Star => Some(BinaryOp::Mul),
+            Token::Slash => Some(BinaryOp::Div),
+            Token::Ampersand => Some(BinaryOp::And),
+            Token::Pipe => Some(BinaryOp::Or),
+            Token::Caret => Some(BinaryOp::Xor),
+            Token::ShiftLeft => Some(BinaryOp::ShiftLeft),
+            Token::ShiftRight => Some(BinaryOp::ShiftRight),
+            _ => None,
+        }
+    }
+}
+
+/// A parser turning the tokens of the [`Lexer`] into [`Statement`]s. Each line holds an
+/// optional label and at most one instruction, optionally followed by a comment.
+pub struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<(Token, SourceSpan)>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Parser {
+            src,
+            tokens: vec![],
+            pos: 0
+        }
+    }
+
+    /// Parse the whole source, stopping at the first malformed line.
+    pub fn parse(&mut self) -> Result<Vec<Statement>, ParseError> {
+        self.tokens = Lexer::new(self.src).tokenise_spanned()?;
+        self.pos = 0;
+        let mut statements = vec![];
+
+        loop {
+            match self.peek() {
+                Token::EOF => break,
+                Token::NewLine | Token::CommentDelimiter => {
+                    self.advance();
+                },
+                // The identifier is never the last token, there is at least an EOF after it.
+                Token::Identifier(name) if self.tokens[self.pos + 1].0 == Token::Colon => {
+                    let (_, span) = self.advance();
+                    self.advance();
+                    statements.push(Statement::Label { name, span });
+                },
+                Token::XReg | Token::YReg if self.tokens[self.pos + 1].0 == Token::Colon => {
+                    let span = self.tokens[self.pos].1;
+                    return Err(ParseError::RegisterLabel {
+                        name: self.src[span.offset()..span.offset() + span.len()].to_owned(),
+                        src: self.src.to_owned(),
+                        span,
+                    });
+                },
+                Token::Identifier(_) => {
+                    statements.push(Statement::Instruction(self.instruction()?));
+                    self.end_of_line()?;
+                },
+                Token::Directive(_) | Token::Star => {
+                    statements.push(Statement::Directive(self.directive()?));
+                    self.end_of_line()?;
+                },
+                _ => return Err(self.unexpected("an instruction, a directive or a label")),
+            }
+        }
+
+        Ok(statements)
+    }
+
+    fn peek(&self) -> Token {
+        self.tokens[self.pos].0
+    }
+
+    /// Consume the current token. The final EOF token is never consumed.
+    fn advance(&mut self) -> (Token, SourceSpan) {
+        let token = self.tokens[self.pos];
+        if token.0 != Token::EOF {
+            self.pos += 1;
+        }
+        token
+    }
+
+    /// Consume the current token if it is `token`, or fail saying what was `expected`.
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), ParseError> {
+        if self.peek() == token {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        ParseError::UnexpectedToken {
+            expected,
+            src: self.src.to_owned(),
+            span: self.tokens[self.pos].1,
+        }
+    }
+
+    /// An instruction is followed by an optional comment, and then the end of the line.
+    fn end_of_line(&mut self) -> Result<(), ParseError> {
+        if self.peek() == Token::CommentDelimiter {
+            self.advance();
+        }
+        match self.peek() {
+            Token::NewLine | Token::EOF => Ok(()),
+            _ => Err(self.unexpected("the end of the line")),
+        }
+    }
+
+    fn instruction(&mut self) -> Result<Instruction, ParseError> {
+        let (Token::Identifier(name), start) = self.advance() else {
+            return Err(self.unexpected("an instruction"));
+        };
+        let name = name.to_uppercase();
+        if !MNEMONICS.contains(&name.as_str()) && !is_bit_op(&name) {
+            return Err(ParseError::UnknownMnemonic {
+                mnemonic: name,
+                src: self.src.to_owned(),
+                span: start,
+            });
+        }
+
+        let (mode, operand) = if is_bit_op(&name) && name.starts_with('B') {
+            let zero_page = self.expr()?;
+            self.expect(Token::Comma, "a comma")?;
+            (Addr::ZeroPageRelative, Operand::BitBranch(zero_page, self.expr()?))
+        } else if is_branch(&name) {
+            (Addr::Relative, Operand::Value(self.expr()?))
+        } else {
+            self.operand(&name)?
+        };
+
+        Ok(Instruction {
+            mnemonic: name.as_str().into(),
+            mode,
+            operand,
+            span: self.span_from(start),
+        })
+    }
+
+    /// The span from `start` up to the end of the last token consumed.
+    fn span_from(&self, start: SourceSpan) -> SourceSpan {
+        let end = self.tokens[self.pos - 1].1;
+        (start.offset(), end.offset() + end.len() - start.offset()).into()
+    }
+
+    fn directive(&mut self) -> Result<Directive, ParseError> {
+        let (token, start) = self.advance();
+        let name = match token {
+            Token::Directive(name) => name.to_lowercase(),
+            // `*=` is the same as `.org`
+            _ => {
+                self.expect(Token::Equals, "`=` after `*`")?;
+                "org".to_owned()
+            },
+        };
+
+        let kind = match name.as_str() {
+            "org" => DirectiveKind::Org(self.expr()?),
+            "byte" | "db" => {
+                let mut data = vec![self.data()?];
+                while self.peek() == Token::Comma {
+                    self.advance();
+                    data.push(self.data()?);
+                }
+                DirectiveKind::Byte(data)
+            },
+            "word" | "dw" => {
+                let mut words = vec![self.expr()?];
+                while self.peek() == Token::Comma {
+                    self.advance();
+                    words.push(self.expr()?);
+                }
+                DirectiveKind::Word(words)
+            },
+            "res" | "ds" => DirectiveKind::Res(self.expr()?, self.fill()?),
+            "align" => DirectiveKind::Align(self.expr()?, self.fill()?),
+            "include" => DirectiveKind::Include(self.path()?),
+            "incbin" => DirectiveKind::Incbin(self.path()?),
+            _ => {
+                return Err(ParseError::UnknownDirective {
+                    directive: name,
+                    src: self.src.to_owned(),
+                    span: start,
+                });
+            },
+        };
+
+        Ok(Directive { kind, span: self.span_from(start) })
+    }
+
+    /// An item of a `.byte` directive, i.e. an expression or a string.
+    fn data(&mut self) -> Result<Data, ParseError> {
+        if self.peek() == Token::StringValue {
+            Ok(Data::String(self.string()))
+        } else {
+            Ok(Data::Byte(self.expr()?))
+        }
+    }
+
+    /// The optional fill value of `.res` and `.align`, following a comma.
+    fn fill(&mut self) -> Result<Option<Expr>, ParseError> {
+        if self.peek() != Token::Comma {
+            return Ok(None);
+        }
+        self.advance();
+        Ok(Some(self.expr()?))
+    }
+
+    /// The path of the file of `.include` or `.incbin`, written as a string.
+    fn path(&mut self) -> Result<String, ParseError> {
+        if self.peek() != Token::StringValue {
+            return Err(self.unexpected("a file name in quotes"));
+        }
+        let span = self.tokens[self.pos].1;
+        String::from_utf8(self.string()).map_err(|_| ParseError::InvalidPath {
+            src: self.src.to_owned(),
+            span,
+        })
+    }
+
+    /// Consume a string, which the lexer has already validated.
+    fn string(&mut self) -> Vec<Byte> {
+        let (_, span) = self.advance();
+        let literal = &self.src[span.offset() + 1..span.offset() + span.len() - 1];
+        unescape(literal).unwrap_or_default()
+    }
+
+    /// Resolve the addressing mode from the syntax of the operand.
+    fn operand(&mut self, mnemonic: &str) -> Result<(Addr, Operand), ParseError> {
+        let operand = match self.peek() {
+            Token::NewLine | Token::CommentDelimiter | Token::EOF if has_accumulator(mnemonic) => {
+                (Addr::Accummulator, Operand::None)
+            },
+            Token::NewLine | Token::CommentDelimiter | Token::EOF => (Addr::Implicit, Operand::None),
+            // `A` is the accumulator only on its own, for the instructions operating on it.
+            // Otherwise it is a label, as in `LDA a` or `ASL a+1`.
+            Token::Identifier(name)
+                if name.eq_ignore_ascii_case("A")
+                    && has_accumulator(mnemonic)
+                    && matches!(self.tokens[self.pos + 1].0, Token::NewLine | Token::CommentDelimiter | Token::EOF) =>
+            {
+                self.advance();
+                (Addr::Accummulator, Operand::None)
+            },
+            Token::ImmediateSpecifier => {
+                self.advance();
+                (Addr::Immediate, Operand::Value(self.expr()?))
+            },
+            Token::LeftBracket => {
+                self.advance();
+                self.indirect()?
+            },
+            token if !starts_expr(token) => return Err(self.unexpected("an operand")),
+            token => {
+                let zero_page = is_zero_page(token);
+                let value = self.expr()?;
+                let mode = match (self.index()?, zero_page) {
+                    (Some(Token::XReg), true) => Addr::ZeroPageX,
+                    (Some(Token::XReg), false) => Addr::AbsoluteX,
+                    (Some(_), true) => Addr::ZeroPageY,
+                    (Some(_), false) => Addr::AbsoluteY,
+                    (None, true) => Addr::ZeroPage,
+                    (None, false) => Addr::Absolute,
+                };
+                (mode, Operand::Value(value))
+            },
+        };
+        Ok(operand)
+    }
+
+    /// Resolve the indirect addressing modes, once the opening bracket is consumed.
+    fn indirect(&mut self) -> Result<(Addr, Operand), ParseError> {
+        let zero_page = is_zero_page(self.peek());
+        let value = self.expr()?;
+
+        let mode = if self.peek() == Token::Comma {
+            // ($04,X) or ($1234,X)
+            self.advance();
+            self.expect(Token::XReg, "the X register")?;
+            self.expect(Token::RightBracket, "a closing bracket")?;
+            if zero_page { Addr::XIndirect } else { Addr::AbsoluteXIndirect }
+        } else {
+            self.expect(Token::RightBracket, "a closing bracket")?;
+            if self.peek() == Token::Comma {
+                // ($04),Y
+                self.advance();
+                self.expect(Token::YReg, "the Y register")?;
+                Addr::IndirectY
+            } else {
+                // ($04) or ($1234)
+                if zero_page { Addr::ZeroPageIndirect } else { Addr::Indirect }
+            }
+        };
+        Ok((mode, Operand::Value(value)))
+    }
+
+    /// Consume an optional index register, returning the [`Token::XReg`] or
+    /// [`Token::YReg`] found.
+    fn index(&mut self) -> Result<Option<Token>, ParseError> {
+        if self.peek() != Token::Comma {
+            return Ok(None);
+        }
+        self.advance();
+        match self.peek() {
+            token @ (Token::XReg | Token::YReg) => {
+                self.advance();
+                Ok(Some(token))
+            },
+            _ => Err(self.unexpected("the X or Y register")),
+        }
+    }
+
+    /// An expression, parsed by precedence climbing from the loosest binding operators.
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        self.binary(0)
+    }
+
+    /// A chain of binary operations of the given precedence `level` (an index into
+    /// [`PRECEDENCE`]), which associate to the left.
+    fn binary(&mut self, level: usize) -> Result<Expr, ParseError> {
+        let Some(ops) = PRECEDENCE.get(level) else {
+            return self.unary();
+        };
+
+        let start = self.tokens[self.pos].1;
+        let mut lhs = self.binary(level + 1)?;
+        while let Some(op) = BinaryOp::from_token(self.peek()).filter(|op| ops.contains(op)) {
+            self.advance();
+            let rhs = self.binary(level + 1)?;
+            lhs = Expr {
+                kind: ExprKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+                span: self.span_from(start),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        let op = match self.peek() {
+            Token::Minus => UnaryOp::Neg,
+            Token::Tilde => UnaryOp::Not,
+            Token::LessThan => UnaryOp::Low,
+            Token::GreaterThan => UnaryOp::High,
+            _ => return self.primary(),
+        };
+        let (_, start) = self.advance();
+        let operand = self.unary()?;
+        Ok(Expr {
+            kind: ExprKind::Unary(op, Box::new(operand)),
+            span: self.span_from(start),
+        })
+    }
+
+    /// A number, a label, the current address or a bracketed expression.
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let (token, span) = self.tokens[self.pos];
+        let kind = match token {
+            Token::ByteValue(value) => ExprKind::Number(value as Word),
+            Token::WordValue(value) => ExprKind::Number(value),
+            Token::Identifier(name) => ExprKind::Label(name),
+            Token::Star => ExprKind::Pc,
+            Token::LeftBracket => {
+                self.advance();
+                let expr = self.expr()?;
+                self.expect(Token::RightBracket, "a closing bracket")?;
+                return Ok(Expr { kind: expr.kind, span: self.span_from(span) });
+            },
+            _ => return Err(self.unexpected("a value")),
+        };
+        self.advance();
+        Ok(Expr { kind, span })
+    }
+}
+
+/// Whether `token` can be the first one of an expression.
+fn starts_expr(token: Token) -> bool {
+    matches!(
+        token,
+        Token::ByteValue(_) | Token::WordValue(_) | Token::Identifier(_) | Token::Star
+            | Token::LeftBracket | Token::Minus | Token::Tilde | Token::LessThan | Token::GreaterThan
+    )
+}
+
+/// Whether an operand starting with `token` is written as a byte, and so gets the zero page
+/// addressing modes.
+fn is_zero_page(token: Token) -> bool {
+    matches!(token, Token::ByteValue(_) | Token::LessThan | Token::GreaterThan)
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum ParseError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Lexing(#[from] LexingError),
+    #[error("Unknown instruction `{mnemonic}`.")]
+    UnknownMnemonic {
+        mnemonic: String,
+        #[source_code]
+        src: String,
+        #[label("Not a 6502 instruction")]
+        span: SourceSpan,
+    },
+    #[error("Unknown directive `.{directive}`.")]
+    UnknownDirective {
+        directive: String,
+        #[source_code]
+        src: String,
+        #[label("Not a supported directive")]
+        span: SourceSpan,
+    },
+    #[error("Malformed instruction, expected {expected}.")]
+    UnexpectedToken {
+        expected: &'static str,
+        #[source_code]
+        src: String,
+        #[label("Found this instead")]
+        span: SourceSpan,
+    },
+    #[error("`{name}` is an index register and cannot be used as a label.")]
+    RegisterLabel {
+        name: String,
+        #[source_code]
+        src: String,
+        #[label("Reserved for indexing")]
+        span: SourceSpan,
+    },
+    #[error("The file name is not valid UTF-8.")]
+    InvalidPath {
+        #[source_code]
+        src: String,
+        #[label("Not a valid path")]
+        span: SourceSpan,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_one(src: &str) -> (Addr, Operand) {
+        let statements = Parser::new(src).parse().expect("The instruction should be valid.");
+        match &statements[..] {
+            [Statement::Instruction(ins)] => (ins.mode, ins.operand.clone()),
+            _ => panic!("Expected a single instruction: {statements:?}"),
+        }
+    }
+
+    /// The addressing mode of an instruction, and its operands written in prefix notation.
+    fn parse_expr(src: &str) -> (Addr, Vec<String>) {
+        let (mode, operand) = parse_one(src);
+        let exprs = match &operand {
+            Operand::None => vec![],
+            Operand::Value(expr) => vec![expr],
+            Operand::BitBranch(zero_page, target) => vec![zero_page, target],
+        };
+        (mode, exprs.into_iter().map(prefix).collect())
+    }
+
+    fn prefix(expr: &Expr) -> String {
+        match &expr.kind {
+            ExprKind::Number(value) => format!("${value:X}"),
+            ExprKind::Label(name) => name.to_string(),
+            ExprKind::Pc => "*".to_owned(),
+            ExprKind::Unary(op, operand) => format!("({op:?} {})", prefix(operand)),
+            ExprKind::Binary(op, lhs, rhs) => format!("({op:?} {} {})", prefix(lhs), prefix(rhs)),
+        }
+    }
+
+    fn number(value: Word, span: (usize, usize)) -> Operand {
+        Operand::Value(Expr { kind: ExprKind::Number(value), span: span.into() })
+    }
+
+    fn label(name: &str, span: (usize, usize)) -> Operand {
+        Operand::Value(Expr { kind: ExprKind::Label(name.into()), span: span.into() })
+    }
+
+    #[test]
+    fn test_parser() {
+        let src = "; Clear the screen\nLDA #$00 ; black\n\n  sta $0200,x\nrts";
+        let statements = Parser::new(src).parse().expect("The program should be valid.");
+        assert_eq!(
+            statements,
+            [
+                Statement::Instruction(Instruction {
+                    mnemonic: "LDA".into(),
+                    mode: Addr::Immediate,
+                    operand: number(0x00, (24, 3)),
+                    span: (19, 8).into(),
+                }),
+                Statement::Instruction(Instruction {
+                    mnemonic: "STA".into(),
+                    mode: Addr::AbsoluteX,
+                    operand: number(0x0200, (43, 5)),
+                    span: (39, 11).into(),
+                }),
+                Statement::Instruction(Instruction {
+                    mnemonic: "RTS".into(),
+                    mode: Addr::Implicit,
+                    operand: Operand::None,
+                    span: (51, 3).into(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let src = "start:\nloop: dex\n  bne loop\n  jmp (vector,x)";
+        let statements = Parser::new(src).parse().expect("The program should be valid.");
+        assert_eq!(
+            statements,
+            [
+                Statement::Label { name: "start".into(), span: (0, 5).into() },
+                Statement::Label { name: "loop".into(), span: (7, 4).into() },
+                Statement::Instruction(Instruction {
+                    mnemonic: "DEX".into(),
+                    mode: Addr::Implicit,
+                    operand: Operand::None,
+                    span: (13, 3).into(),
+                }),
+                Statement::Instruction(Instruction {
+                    mnemonic: "BNE".into(),
+                    mode: Addr::Relative,
+                    operand: label("loop", (23, 4)),
+                    span: (19, 8).into(),
+                }),
+                Statement::Instruction(Instruction {
+                    mnemonic: "JMP".into(),
+                    mode: Addr::AbsoluteXIndirect,
+                    operand: label("vector", (35, 6)),
+                    span: (30, 14).into(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_addressing_modes() {
+        for (src, mode, operands) in [
+            ("CLC", Addr::Implicit, &[][..]),
+            ("ASL", Addr::Accummulator, &[]),
+            ("ROR A", Addr::Accummulator, &[]),
+            ("ADC #$04", Addr::Immediate, &["$4"]),
+            ("ADC $04", Addr::ZeroPage, &["$4"]),
+            ("ADC $04, X", Addr::ZeroPageX, &["$4"]),
+            ("LDX $04,Y", Addr::ZeroPageY, &["$4"]),
+            ("ADC $1234", Addr::Absolute, &["$1234"]),
+            ("ADC $1234,X", Addr::AbsoluteX, &["$1234"]),
+            ("ADC $1234,Y", Addr::AbsoluteY, &["$1234"]),
+            ("JMP ($1234)", Addr::Indirect, &["$1234"]),
+            ("JMP ($1234,X)", Addr::AbsoluteXIndirect, &["$1234"]),
+            ("ADC ($04,X)", Addr::XIndirect, &["$4"]),
+            ("LDA ($04),Y", Addr::IndirectY, &["$4"]),
+            ("LDA ($04)", Addr::ZeroPageIndirect, &["$4"]),
+            ("BNE $0600", Addr::Relative, &["$600"]),
+            ("rmb3 $12", Addr::ZeroPage, &["$12"]),
+            ("BBS7 $12, $0600", Addr::ZeroPageRelative, &["$12", "$600"]),
+        ] {
+            assert_eq!(parse_expr(src), (mode, operands.iter().map(|s| s.to_string()).collect()), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_label_named_a() {
+        assert_eq!(parse_expr("ROL a ; rotate"), (Addr::Accummulator, vec![]));
+        assert_eq!(parse_expr("LDA a"), (Addr::Absolute, vec!["a".to_owned()]));
+        assert_eq!(parse_expr("ASL A+1"), (Addr::Absolute, vec!["(Add A $1)".to_owned()]));
+        assert_eq!(parse_expr("ASL a,X"), (Addr::AbsoluteX, vec!["a".to_owned()]));
+    }
+
+    #[test]
+    fn test_expressions() {
+        for (src, mode, operand) in [
+            ("LDA #<(table+2)", Addr::Immediate, "(Low (Add table $2))"),
+            ("LDA #>table", Addr::Immediate, "(High table)"),
+            ("BNE *+3", Addr::Relative, "(Add * $3)"),
+            ("LDA label-1,X", Addr::AbsoluteX, "(Sub label $1)"),
+            ("AND #$FF & mask", Addr::Immediate, "(And $FF mask)"),
+            ("LDA <ptr", Addr::ZeroPage, "(Low ptr)"),
+            ("LDA ('A'+1),Y", Addr::IndirectY, "(Add $41 $1)"),
+            ("ADC #1+2*3-4", Addr::Immediate, "(Sub (Add $1 (Mul $2 $3)) $4)"),
+            ("ADC #(1+2)*3", Addr::Immediate, "(Mul (Add $1 $2) $3)"),
+            ("ORA #1|2^3&4<<5", Addr::Immediate, "(Or $1 (Xor $2 (And $3 (ShiftLeft $4 $5))))"),
+            ("LDA #-~1024/2>>1", Addr::Immediate, "(ShiftRight (Div (Neg (Not $400)) $2) $1)"),
+        ] {
+            assert_eq!(parse_expr(src), (mode, vec![operand.to_owned()]), "{src}");
+        }
+
+        // Expressions span from their first token to their last one.
+        let (_, operand) = parse_one("LDA #<(table + 2)");
+        let Operand::Value(expr) = operand else { panic!("Expected a value: {operand:?}") };
+        assert_eq!(expr.span, (5, 12).into());
+    }
+
+    #[test]
+    fn test_directives() {
+        let src = "*= $0600\n.org start+2\nmsg: .byte \"Hi\", 0, <msg\n.DW msg, $1234\n.res 4\n.ds 2, $EA\n.align 256\n.include \"lib.s\"\n.incbin \"font.bin\"";
+        let statements = Parser::new(src).parse().expect("The program should be valid.");
+        let kinds: Vec<&DirectiveKind> = statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Directive(directive) => Some(&directive.kind),
+                _ => None,
+            })
+            .collect();
+
+        let Statement::Directive(org) = &statements[0] else { panic!("Expected a directive") };
+        assert_eq!(org.span, (0, 8).into());
+        assert!(matches!(kinds[1], DirectiveKind::Org(expr) if prefix(expr) == "(Add start $2)"));
+        let DirectiveKind::Byte(data) = kinds[2] else { panic!("Expected .byte: {:?}", kinds[2]) };
+        assert_eq!(data[0], Data::String(b"Hi".to_vec()));
+        assert!(matches!(&data[1], Data::Byte(expr) if prefix(expr) == "$0"));
+        assert!(matches!(&data[2], Data::Byte(expr) if prefix(expr) == "(Low msg)"));
+        let DirectiveKind::Word(words) = kinds[3] else { panic!("Expected .dw: {:?}", kinds[3]) };
+        assert_eq!(words.iter().map(prefix).collect::<Vec<_>>(), ["msg", "$1234"]);
+        assert!(matches!(kinds[4], DirectiveKind::Res(len, None) if prefix(len) == "$4"));
+        assert!(matches!(kinds[5], DirectiveKind::Res(_, Some(fill)) if prefix(fill) == "$EA"));
+        assert!(matches!(kinds[6], DirectiveKind::Align(alignment, None) if prefix(alignment) == "$100"));
+        assert_eq!(kinds[7], &DirectiveKind::Include("lib.s".to_owned()));
+        assert_eq!(kinds[8], &DirectiveKind::Incbin("font.bin".to_owned()));
+    }
+
+    #[test]
+    fn test_unknown_directive() {
+        let err = Parser::new("nop\n.segment \"CODE\"").parse().expect_err("Segments are not supported.");
+        let ParseError::UnknownDirective { directive, span, .. } = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(directive, "segment");
+        assert_eq!(span, (4, 8).into());
+    }
+
+    #[test]
+    fn test_unknown_mnemonic() {
+        let err = Parser::new("NOP\nFOO $04").parse().expect_err("FOO is not an instruction.");
+        let ParseError::UnknownMnemonic { mnemonic, span, .. } = err else {
+            panic!("Unexpected error: {err:?}");
+        };
+        assert_eq!(mnemonic, "FOO");
+        assert_eq!(span, (4, 3).into());
+    }
+
+    #[test]
+    fn test_malformed_operand() {
+        for (src, expected, offset) in [
+            ("LDA #<", "a value", 6),
+            ("LDA #(1+2", "a closing bracket", 9),
+            ("LDA $04+", "a value", 8),
+            ("LDA $04,", "the X or Y register", 8),
+            ("LDA ($04,Y)", "the X register", 9),
+            ("LDA ($04", "a closing bracket", 8),
+            ("LDA ($04),X", "the Y register", 10),
+            ("BNE #$04", "a value", 4),
+            ("BBR0 $12 $0600", "a comma", 9),
+            ("LDA $04 $05", "the end of the line", 8),
+            ("* $0600", "`=` after `*`", 2),
+            (".include lib", "a file name in quotes", 9),
+            (".byte 1,", "a value", 8),
+            ("LDA ,", "an operand", 4),
+            (", LDA", "an instruction, a directive or a label", 0),
+        ] {
+            let err = Parser::new(src).parse().expect_err(src);
+            let ParseError::UnexpectedToken { expected: found, span, .. } = err else {
+                panic!("Unexpected error for {src}: {err:?}");
+            };
+            assert_eq!((found, span.offset()), (expected, offset), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_register_label() {
+        let err = Parser::new("nop\nx: nop").parse().expect_err("X is a register.");
+        assert!(matches!(err, ParseError::RegisterLabel { name, span, .. } if name == "x" && span == (4, 1).into()));
+        let err = Parser::new("Y:").parse().expect_err("Y is a register.");
+        assert!(matches!(err, ParseError::RegisterLabel { name, .. } if name == "Y"));
+    }
+
+    #[test]
+    fn test_lexing_error() {
+        let err = Parser::new("LDA #$12345").parse().expect_err("$12345 is not a value.");
+        assert!(matches!(err, ParseError::Lexing(_)));
+    }
+}
@@ -0,0 +1,32 @@
+/// The chips of the 6502 family which can be emulated. They share the same core, but
+/// differ in their instruction sets, decimal mode and timings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    /// The original MOS 6502, including its undocumented opcodes and quirks.
+    #[default]
+    Nmos6502,
+    /// The CMOS 65C02, which adds new instructions and the zero page indirect addressing
+    /// mode, fixes the `JMP ($xxFF)` bug and has valid flags in decimal mode. All of its
+    /// unused opcodes are NOPs.
+    Cmos65C02,
+    /// The Rockwell R65C02, a 65C02 with the RMB, SMB, BBR and BBS bit manipulation
+    /// instructions.
+    Rockwell65C02,
+    /// The WDC W65C02S, a Rockwell R65C02 which can also wait for an interrupt (WAI) and
+    /// stop its clock (STP).
+    Wdc65C02,
+    /// The Ricoh 2A03 used in the NES, an NMOS 6502 without decimal mode.
+    Ricoh2A03,
+}
+
+impl Variant {
+    /// Whether ADC and SBC honour the decimal flag.
+    pub fn has_decimal_mode(&self) -> bool {
+        *self != Variant::Ricoh2A03
+    }
+
+    /// Whether the chip belongs to the CMOS 65C02 family.
+    pub fn is_cmos(&self) -> bool {
+        matches!(self, Variant::Cmos65C02 | Variant::Rockwell65C02 | Variant::Wdc65C02)
+    }
+}
@@ -0,0 +1,194 @@
+use std::env;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use miette::Diagnostic;
+use mos_6502_core::assembler::Assembler;
+
+/// Assembles 6502 code at compile time, expanding to a `[u8; N]` with the machine code.
+///
+/// The code can be written either as a string literal or directly as tokens:
+///
+/// ```
+/// # use mos_6502_macros::asm;
+/// let code = asm! {
+///     ldx #$05
+///     loop: dex       ; count down to zero
+///     bne loop
+///     brk
+/// };
+/// assert_eq!(code, [0xA2, 0x05, 0xCA, 0xD0, 0xFD, 0x00]);
+/// ```
+///
+/// Operations are divided by new lines and comments start with `;`. Mnemonics are not case
+/// sensitive, `$` is used for hex and `%` for binary, and labels are followed by `:`. The
+/// code is assembled for the NMOS 6502 with the first instruction located at `$0000`,
+/// unless moved with `.org`.
+///
+/// Written as tokens, the code must also be valid Rust tokens, so hex values such as `$0E`
+/// (a malformed float) or comments with unbalanced quotes need the string form.
+///
+/// Files named by `.include` and `.incbin` are looked up relative to the directory holding
+/// the `Cargo.toml` of the crate being compiled (`CARGO_MANIFEST_DIR`), unlike
+/// `include_bytes!` which is relative to the current file.
+///
+/// Any assembly error is reported as a compile error pointing at the offending token.
+#[proc_macro]
+pub fn asm(input: TokenStream) -> TokenStream {
+    let mut tokens = input.clone().into_iter();
+    let (src, spans) = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) if is_string(&literal) => {
+            let src = unquote(&literal.to_string());
+            let spans = vec![(0, src.len(), literal.span())];
+            (src, spans)
+        },
+        _ => {
+            let mut source = Source::default();
+            source.push_stream(input);
+            (source.text, source.spans)
+        },
+    };
+
+    let dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    match Assembler::new(0).assemble_in(&src, dir) {
+        Ok(assembly) => {
+            // Including the files read lets the compiler know that the code depends on them.
+            let mut expansion = TokenStream::new();
+            for path in assembly.files.iter().filter_map(|path| path.to_str()) {
+                let include = format!("const _: &[u8] = include_bytes!({});", Literal::string(path));
+                expansion.extend(include.parse::<TokenStream>());
+            }
+            let bytes = assembly.bytes.into_iter().flat_map(|byte| [
+                TokenTree::Literal(Literal::u8_suffixed(byte)),
+                TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+            ]);
+            expansion.extend([TokenTree::Group(Group::new(Delimiter::Bracket, bytes.collect()))]);
+            TokenTree::Group(Group::new(Delimiter::Brace, expansion)).into()
+        },
+        Err(err) => {
+            let label = err.labels().and_then(|mut labels| labels.next());
+            let span = label
+                .map(|label| {
+                    let offset = label.offset();
+                    spans
+                        .iter()
+                        .find(|(start, end, _)| (*start..(*end).max(start + 1)).contains(&offset))
+                        .or(spans.last())
+                        .map_or(Span::call_site(), |(_, _, span)| *span)
+                })
+                .unwrap_or(Span::call_site());
+            compile_error(&err.to_string(), span)
+        },
+    }
+}
+
+/// Assembly source rebuilt from Rust tokens, along with the span of each of them.
+#[derive(Default)]
+struct Source {
+    text: String,
+    /// Byte range of each token in `text` and where it came from.
+    spans: Vec<(usize, usize, Span)>,
+    /// Line and column right after the last token.
+    end: Option<(usize, usize)>,
+}
+
+impl Source {
+    fn push_stream(&mut self, stream: TokenStream) {
+        for tree in stream {
+            match tree {
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::None => ("", ""),
+                    };
+                    self.push(open, group.span_open());
+                    self.push_stream(group.stream());
+                    self.push(close, group.span_close());
+                },
+                tree => {
+                    let span = tree.span();
+                    let text = span.source_text().unwrap_or_else(|| tree.to_string());
+                    self.push(&text, span);
+                },
+            }
+        }
+    }
+
+    /// Appends a token, keeping it on the line it was written in. Tokens are separated by
+    /// a space only when they were in the source, so that `#$04` is still a single value.
+    fn push(&mut self, text: &str, span: Span) {
+        if text.is_empty() {
+            return;
+        }
+
+        let (line, column) = (span.line(), span.column());
+        match self.end {
+            Some((end_line, _)) if line > end_line => self.text.push('\n'),
+            Some((_, end_column)) if column > end_column => self.text.push(' '),
+            _ => {},
+        }
+
+        let start = self.text.len();
+        self.text.push_str(text);
+        self.spans.push((start, self.text.len(), span));
+        let end = span.end();
+        self.end = Some((end.line(), end.column()));
+    }
+}
+
+fn is_string(literal: &Literal) -> bool {
+    let text = literal.to_string();
+    text.starts_with('"') || text.starts_with('r')
+}
+
+/// The contents of a string literal, with its escape sequences resolved.
+fn unquote(literal: &str) -> String {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return raw[hashes + 1..raw.len() - hashes - 1].to_owned();
+    }
+
+    let mut text = String::new();
+    let mut chars = literal[1..literal.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('t') => text.push('\t'),
+            Some('0') => text.push('\0'),
+            Some('\n') => {
+                // A line continuation skips the leading whitespace of the next line
+                while chars.clone().next().is_some_and(char::is_whitespace) {
+                    chars.next();
+                }
+            },
+            Some(c) => text.push(c),
+            None => {},
+        }
+    }
+    text
+}
+
+/// Expands to `compile_error!(message)`, reported at `span`.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    args.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(args),
+    ]
+    .into_iter()
+    .collect()
+}
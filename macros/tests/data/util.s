@@ -0,0 +1,3 @@
+helper:
+    rts
+    .incbin "table.bin"
@@ -0,0 +1,12 @@
+use mos_6502_macros::asm;
+
+#[test]
+fn include_from_manifest_dir() {
+    // The crate root is where paths start from, but an included file looks up the ones
+    // it names in its own directory.
+    let code = asm! {
+        jsr helper
+        .include "tests/data/util.s"
+    };
+    assert_eq!(code, [0x20, 0x03, 0x00, 0x60, 0x01, 0x02, 0x03]);
+}
@@ -0,0 +1,8 @@
+use mos_6502_macros::asm;
+
+fn main() {
+    let _ = asm! {
+        lda #$01
+        xyz $0200
+    };
+}
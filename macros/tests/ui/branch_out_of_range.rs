@@ -0,0 +1,9 @@
+use mos_6502_macros::asm;
+
+fn main() {
+    let _ = asm! {
+        start: nop
+        .res 200
+        beq start
+    };
+}
@@ -0,0 +1,9 @@
+use mos_6502_macros::asm;
+
+fn main() {
+    let _ = asm! {
+        ldx #$08
+        loop: dex
+        bne done
+    };
+}
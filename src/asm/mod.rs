@@ -1,23 +1,64 @@
-pub mod lexer;
-pub mod parser;
+//! A 6502 assembler. The [`asm!`] macro runs it at compile time, expanding to the
+//! machine code of the assembly written in it.
 
-#[macro_export]
-macro_rules! asm {
-    // Operations are divided by new lines
-    // Comments start with ';'
-    // '$' is used for hex and '%' for binary
-    // Instruction mnemonics are not case sensitive ('LDA' and 'lda' are both valid)
-    // Different syntax for each addressing method (all w/ operand $04):
+pub use mos_6502_core::{assembler, lexer, parser};
+pub use mos_6502_macros::asm;
 
-        // ADC #$04     ; The opcode is $69.
-        // ADC $04, X   ; The opcode is $61.
-        // ADC ($04), Y ; The opcode is $71.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assembler::Assembler;
+    use crate::Word;
+    use crate::cpu::{CPU, ResetMode, StopReason};
 
-    // Labels: associate a name with a particular location in the program code
-    // e.g.
-    // some_label: LDA #$00
+    #[test]
+    fn asm_tokens() {
+        let code = asm! {
+            LDA #$04    ; load the counter
+            sta $0200,X
+            loop: dex
+            bne loop
+            jmp ($3DA5)
+        };
+        assert_eq!(code, [0xA9, 0x04, 0x9D, 0x00, 0x02, 0xCA, 0xD0, 0xFD, 0x6C, 0xA5, 0x3D]);
+    }
 
-    () => {
+    #[test]
+    fn asm_directives() {
+        let code = asm! {
+            .org $0600
+            loop: jmp loop
+            .byte "ok", 0
+        };
+        assert_eq!(code, [0x4C, 0x00, 0x06, b'o', b'k', 0x00]);
+    }
 
-    };
+    #[test]
+    fn asm_string() {
+        let code = asm!("lda #$0E ; can't be written as tokens\n rol a\n asl $0E");
+        assert_eq!(code, [0xA9, 0x0E, 0x2A, 0x06, 0x0E]);
+    }
+
+    #[test]
+    fn asm_run_assembled() {
+        let src = "
+            lda #$00
+            ldx #$0A
+        loop:
+            clc
+            adc #$03
+            dex
+            bne loop
+            sta $0200
+            brk";
+        let assembly = Assembler::new(0x0600).assemble(src).unwrap();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+        for (offset, byte) in assembly.bytes.iter().enumerate() {
+            cpu.mem.write_byte(assembly.origin + offset as Word, *byte);
+        }
+        cpu.pc = assembly.origin;
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Break(0x060D)));
+        assert_eq!(cpu.mem.read_byte(0x0200), 30);
+    }
 }
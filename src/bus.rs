@@ -0,0 +1,25 @@
+use crate::{Byte, Word};
+
+/// The address and data buses connecting the CPU to the rest of the machine. Every memory
+/// access performed by the CPU goes through the bus, which decides what is mapped at each
+/// address (RAM, ROM, I/O registers, ...).
+pub trait Bus {
+    /// Read a byte from the specified address. Unlike [`Bus::peek`], this is a real bus
+    /// cycle and may have side effects (e.g. acknowledging an I/O register).
+    fn read(&mut self, address: Word) -> Byte;
+
+    /// Write a byte of data to the specified address.
+    fn write(&mut self, address: Word, data: Byte);
+
+    /// Read a byte from the specified address without any side effects, e.g. for
+    /// debuggers and disassemblers.
+    fn peek(&self, address: Word) -> Byte;
+
+    /// Advance the devices connected to the bus by `cycles` clock cycles.
+    fn tick(&mut self, _cycles: u32) {}
+
+    /// Whether a device connected to the bus is pulling the IRQ line low.
+    fn irq(&self) -> bool {
+        false
+    }
+}
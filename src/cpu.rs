@@ -1,10 +1,16 @@
+use std::collections::HashSet;
+
 use crate::{Byte, Word};
-use crate::mem::Memory;
-use crate::ins::DecodeIns;
+use crate::bus::Bus;
+use crate::mem::{Addr, Memory};
+use crate::ins::{DecodeError, DecodeIns, ExecError, InstructionDecoder};
+
+pub use mos_6502_core::Variant;
 
-/// All internal data structures of the 6502 CPU.
+/// All internal data structures of the 6502 CPU, connected to the rest of the machine
+/// through the bus `B` (a flat 64KiB [`Memory`] by default).
 #[derive(Clone)]
-pub struct CPU {
+pub struct CPU<B: Bus + ?Sized = Memory> {
     /// Program counter.
     pub pc: Word,
     /// Stack pointer (should only be `Byte`, not a `Word`).
@@ -15,34 +21,118 @@ pub struct CPU {
     pub sp: Byte,
     /// Cycle count.
     pub cycles: u32,
-    /// Memory.
-    pub mem: Memory,
     /// Registers.
     pub reg: Registers,
     /// Status flags.
     pub flags: StatusFlags,
+    /// Addresses at which the run loop stops before executing the instruction.
+    pub breakpoints: HashSet<Word>,
+    /// Which chip of the 6502 family is emulated.
+    pub variant: Variant,
+    /// How the CPU behaves when it is reset.
+    pub reset_mode: ResetMode,
+    /// How the CPU behaves when it fetches an illegal opcode.
+    pub illegal_opcodes: IllegalOpcodePolicy,
+    /// How the CPU behaves when it fetches an unstable undocumented opcode while emulating
+    /// illegal opcodes.
+    pub unstable_opcodes: UnstableOpcodePolicy,
+    /// Whether a JAM opcode halted the CPU until the next reset.
+    jammed: bool,
+    /// Whether a STP instruction stopped the clock until the next reset.
+    stopped: bool,
+    /// Whether a WAI instruction is waiting for an interrupt.
+    waiting: bool,
+    /// IRQ sources currently pulling the (shared, active low) IRQ line, one bit each.
+    irq_lines: [u64; 4],
+    /// Whether a falling edge on the NMI line has not been serviced yet.
+    nmi_pending: bool,
+    /// Bus through which all memory accesses go (kept last so that it can be unsized).
+    pub mem: B,
 }
 
+/// Location of the NMI vector, holding the address of the non-maskable interrupt handler.
+pub const NMI_VECTOR: Word = 0xFFFA;
+/// Location of the reset vector, holding the address at which execution starts.
+pub const RESET_VECTOR: Word = 0xFFFC;
+/// Location of the IRQ vector, holding the address of the handler for IRQs and BRK.
+pub const IRQ_VECTOR: Word = 0xFFFE;
+
+/// Identifies one of the devices sharing the IRQ line, from 0 to 255.
+pub type IrqSource = u8;
+
 impl CPU {
+    /// Creates a CPU with zeroed memory and performs a reset.
     pub fn new() -> Self {
-        Self {
-            pc: 0xFFFC,
-            // TODO: Check the initial sp address.
-            sp: 0xFF,
+        Self::with_reset_mode(ResetMode::default())
+    }
+
+    /// Creates a CPU with zeroed memory which resets according to `reset_mode`.
+    pub fn with_reset_mode(reset_mode: ResetMode) -> Self {
+        Self::with_bus(Memory::new(), reset_mode)
+    }
+
+    /// Returns whether two addresses are on different pages. Indexing across a page
+    /// boundary usually costs an extra cycle.
+    pub fn page_crossed(a: Word, b: Word) -> bool {
+        (a & 0xFF00) != (b & 0xFF00)
+    }
+
+    pub fn stack_address(addr: Byte) -> Word {
+        0x0100 + addr as Word
+    }
+}
+
+impl<B: Bus> CPU<B> {
+    /// Creates a CPU connected to `bus` which resets according to `reset_mode`.
+    pub fn with_bus(bus: B, reset_mode: ResetMode) -> Self {
+        let mut cpu = Self {
+            pc: 0,
+            sp: 0,
             cycles: 0,
-            mem: Memory::new(),
             reg: Registers::new(),
             flags: StatusFlags::new(),
-        }
+            breakpoints: HashSet::new(),
+            variant: Variant::default(),
+            reset_mode,
+            illegal_opcodes: IllegalOpcodePolicy::default(),
+            unstable_opcodes: UnstableOpcodePolicy::default(),
+            jammed: false,
+            stopped: false,
+            waiting: false,
+            irq_lines: [0; 4],
+            nmi_pending: false,
+            mem: bus,
+        };
+        cpu.reset();
+        cpu
     }
+}
 
+impl<B: Bus + ?Sized> CPU<B> {
+    /// Resets the CPU. Memory is left untouched, the registers are cleared, the stack
+    /// pointer ends up at $FD (the reset sequence performs three dummy pushes) and
+    /// interrupts are disabled. Then, unless in [`ResetMode::Legacy`], the program counter
+    /// is loaded from the reset vector at $FFFC/$FFFD, which takes 7 cycles.
     pub fn reset(&mut self) {
-        self.pc = 0xFFFC;
-        self.sp = 0xFF;
+        self.sp = 0xFD;
         self.cycles = 0;
         self.reg.clear();
         self.flags.clear();
-        self.mem.init();
+        self.flags.i = true;
+        self.nmi_pending = false;
+        self.jammed = false;
+        self.stopped = false;
+        self.waiting = false;
+
+        match self.reset_mode {
+            ResetMode::Vector => {
+                self.pc = self.read_word(RESET_VECTOR);
+                self.cycles += 7;
+            },
+            ResetMode::Legacy => {
+                self.pc = RESET_VECTOR;
+            },
+        }
     }
 
     /// Fetch the next instruction from memory.
@@ -51,44 +141,517 @@ impl CPU {
     }
 
     /// Read a byte from the specified address.
-    pub fn read_byte(&self, address: Word) -> Byte {
-        self.mem.read_byte(address)
+    pub fn read_byte(&mut self, address: Word) -> Byte {
+        self.mem.read(address)
     }
 
-    /// Read a word from the specified address.
-    pub fn read_word(&self, address: Word) -> Word {
-        self.mem.read_word(address)
+    /// Read a word (little-endian) from the specified address.
+    pub fn read_word(&mut self, address: Word) -> Word {
+        let lo = self.read_byte(address) as Word;
+        let hi = self.read_byte(address.wrapping_add(1)) as Word;
+        (hi << 8) | lo
+    }
+
+    /// Read a byte from the specified address without any side effects on the bus.
+    pub fn peek_byte(&self, address: Word) -> Byte {
+        self.mem.peek(address)
+    }
+
+    /// Read a word (little-endian) from the specified address without any side effects
+    /// on the bus.
+    pub fn peek_word(&self, address: Word) -> Word {
+        let lo = self.peek_byte(address) as Word;
+        let hi = self.peek_byte(address.wrapping_add(1)) as Word;
+        (hi << 8) | lo
     }
 
     /// Write a byte of data to the specified address.
     pub fn write_byte(&mut self, address: Word, data: Byte) {
-        self.mem.write_byte(address, data);
+        self.mem.write(address, data);
     }
 
-    /// Write a word of data to the specified address.
+    /// Write a word of data (little-endian) to the specified address.
     pub fn write_word(&mut self, address: Word, data: Word) {
-        self.mem.write_word(address, data);
+        self.write_byte(address, data as Byte);
+        self.write_byte(address.wrapping_add(1), (data >> 8) as Byte);
     }
 
-    pub fn stack_address(addr: Byte) -> Word {
-        0x0100 + addr as Word
+    /// Read a byte from the specified address, transform it with `op` and write the result
+    /// back. Like the NMOS 6502, the unmodified value is written back before the result,
+    /// whereas the 65C02 reads it a second time instead.
+    pub fn read_modify_write(&mut self, address: Word, op: impl FnOnce(&mut Self, Byte) -> Byte) {
+        let value = self.read_byte(address);
+        if self.variant.is_cmos() {
+            self.read_byte(address);
+        } else {
+            self.write_byte(address, value);
+        }
+        let result = op(self, value);
+        self.write_byte(address, result);
     }
 
-    /// Starts the fetch-decode-execute cycle.
+    /// Read a pointer from the zero page. Like on the real chip, a pointer at $FF takes its
+    /// high byte from $00 rather than $0100.
+    pub fn read_zero_page_word(&mut self, address: Byte) -> Word {
+        let lo = self.read_byte(address as Word) as Word;
+        let hi = self.read_byte(address.wrapping_add(1) as Word) as Word;
+        (hi << 8) | lo
+    }
+
+    /// Resolve the operand of the instruction at the program counter for the addressing
+    /// `mode`, reading its argument bytes (and pointers) but not the operand itself. All
+    /// address arithmetic wraps around: zero page indexing and pointers stay within page
+    /// zero, and everything else within the 64KiB address space.
+    pub fn resolve(&mut self, mode: &Addr) -> Operand {
+        let arg = self.pc.wrapping_add(1);
+        let (address, len, page_crossed) = match mode {
+            Addr::Implicit | Addr::Accummulator => (None, 1, false),
+            Addr::Immediate => (Some(arg), 2, false),
+            Addr::ZeroPage => (Some(self.read_byte(arg) as Word), 2, false),
+            Addr::ZeroPageX => (Some(self.read_byte(arg).wrapping_add(self.reg.x) as Word), 2, false),
+            Addr::ZeroPageY => (Some(self.read_byte(arg).wrapping_add(self.reg.y) as Word), 2, false),
+            Addr::Relative => {
+                let (target, page_crossed) = self.branch_target(arg, self.pc.wrapping_add(2));
+                (Some(target), 2, page_crossed)
+            },
+            // The branch target is left to `CPU::branch_target`, since the address of the
+            // operand is the zero page byte being tested.
+            Addr::ZeroPageRelative => (Some(self.read_byte(arg) as Word), 3, false),
+            Addr::Absolute => (Some(self.read_word(arg)), 3, false),
+            Addr::AbsoluteX => {
+                let base = self.read_word(arg);
+                let address = base.wrapping_add(self.reg.x as Word);
+                (Some(address), 3, CPU::page_crossed(base, address))
+            },
+            Addr::AbsoluteY => {
+                let base = self.read_word(arg);
+                let address = base.wrapping_add(self.reg.y as Word);
+                (Some(address), 3, CPU::page_crossed(base, address))
+            },
+            Addr::Indirect => {
+                // The NMOS 6502 does not carry into the high byte of the pointer when
+                // fetching the target, so `JMP ($10FF)` reads its high byte from $1000
+                // rather than $1100. The 65C02 fixes this.
+                let ptr = self.read_word(arg);
+                let target = if self.variant.is_cmos() {
+                    self.read_word(ptr)
+                } else {
+                    let lo = self.read_byte(ptr) as Word;
+                    let hi = self.read_byte((ptr & 0xFF00) | (ptr.wrapping_add(1) & 0x00FF)) as Word;
+                    (hi << 8) | lo
+                };
+                (Some(target), 3, false)
+            },
+            Addr::AbsoluteXIndirect => {
+                let ptr = self.read_word(arg).wrapping_add(self.reg.x as Word);
+                (Some(self.read_word(ptr)), 3, false)
+            },
+            Addr::ZeroPageIndirect => {
+                let ptr = self.read_byte(arg);
+                (Some(self.read_zero_page_word(ptr)), 2, false)
+            },
+            Addr::XIndirect => {
+                let ptr = self.read_byte(arg).wrapping_add(self.reg.x);
+                (Some(self.read_zero_page_word(ptr)), 2, false)
+            },
+            Addr::IndirectY => {
+                let ptr = self.read_byte(arg);
+                let base = self.read_zero_page_word(ptr);
+                let address = base.wrapping_add(self.reg.y as Word);
+                (Some(address), 2, CPU::page_crossed(base, address))
+            },
+        };
+        Operand { address, len, page_crossed }
+    }
+
+    /// Read the signed relative offset at `offset` and add it to `next`, the address of the
+    /// instruction following the branch. Returns the branch target, and whether it is on a
+    /// different page than `next`.
+    pub fn branch_target(&mut self, offset: Word, next: Word) -> (Word, bool) {
+        let offset = self.read_byte(offset) as i8;
+        let target = next.wrapping_add_signed(offset as i16);
+        (target, CPU::page_crossed(next, target))
+    }
+
+    /// Read the value of a resolved operand, i.e. the accumulator or the byte at its
+    /// effective address.
+    pub fn read_operand(&mut self, operand: &Operand) -> Byte {
+        match operand.address {
+            Some(address) => self.read_byte(address),
+            None => self.reg.acc,
+        }
+    }
+
+    /// Write the value of a resolved operand, i.e. the accumulator or the byte at its
+    /// effective address.
+    pub fn write_operand(&mut self, operand: &Operand, data: Byte) {
+        match operand.address {
+            Some(address) => self.write_byte(address, data),
+            None => self.reg.acc = data,
+        }
+    }
+
+    /// Transform the value of a resolved operand with `op`, writing the result back like
+    /// [`CPU::read_modify_write`] does for memory operands.
+    pub fn modify_operand(&mut self, operand: &Operand, op: impl FnOnce(&mut Self, Byte) -> Byte) {
+        match operand.address {
+            Some(address) => self.read_modify_write(address, op),
+            None => self.reg.acc = op(self, self.reg.acc),
+        }
+    }
+
+    /// Push a byte onto the stack. The byte is written at the address in S, and then S is
+    /// decremented (wrapping around within page 1).
+    pub fn push_byte(&mut self, data: Byte) {
+        self.write_byte(CPU::stack_address(self.sp), data);
+        self.sp = self.sp.wrapping_sub(1);
+    }
+
+    /// Pull a byte from the stack. S is incremented (wrapping around within page 1), and
+    /// then the byte at the address in S is read.
+    pub fn pull_byte(&mut self) -> Byte {
+        self.sp = self.sp.wrapping_add(1);
+        self.read_byte(CPU::stack_address(self.sp))
+    }
+
+    /// Push a word onto the stack, high byte first, so that it ends up in little-endian
+    /// order in memory.
+    pub fn push_word(&mut self, data: Word) {
+        self.push_byte((data >> 8) as Byte);
+        self.push_byte(data as Byte);
+    }
+
+    /// Pull a word from the stack, low byte first.
+    pub fn pull_word(&mut self) -> Word {
+        let lo = self.pull_byte() as Word;
+        let hi = self.pull_byte() as Word;
+        (hi << 8) | lo
+    }
+
+    /// Pulls the IRQ line low on behalf of `source`. The line is level-triggered, so the
+    /// interrupt keeps being serviced (whenever the interrupt disable flag is clear) until
+    /// every source sharing it has released it.
+    pub fn assert_irq(&mut self, source: IrqSource) {
+        self.irq_lines[source as usize / 64] |= 1 << (source % 64);
+    }
+
+    /// Stops pulling the IRQ line low on behalf of `source`.
+    pub fn release_irq(&mut self, source: IrqSource) {
+        self.irq_lines[source as usize / 64] &= !(1 << (source % 64));
+    }
+
+    /// Whether any source, including the devices on the bus, is pulling the IRQ line low.
+    pub fn irq_asserted(&self) -> bool {
+        self.irq_lines.iter().any(|&lines| lines != 0) || self.mem.irq()
+    }
+
+    /// Signals a falling edge on the NMI line. The interrupt is serviced once, before the
+    /// next instruction, regardless of the interrupt disable flag.
+    pub fn trigger_nmi(&mut self) {
+        self.nmi_pending = true;
+    }
+
+    /// Pushes the program counter and the processor status, and jumps to the handler whose
+    /// address is stored at `vector`. The pushed status has bit 5 set, and the break flag
+    /// set only if the interrupt was caused by a BRK instruction. The 65C02 also clears the
+    /// decimal flag. Takes 7 cycles.
+    pub fn interrupt(&mut self, return_address: Word, vector: Word, brk: bool) {
+        self.push_word(return_address);
+        let status: Byte = self.flags.to_owned().into();
+        let status = (status & !0b00010000) | 0b00100000 | ((brk as Byte) << 4);
+        self.push_byte(status);
+        self.flags.i = true;
+        if self.variant.is_cmos() {
+            self.flags.d = false;
+        }
+        self.pc = self.read_word(vector);
+        self.cycles += 7;
+    }
+
+    /// Halts the CPU, like the JAM opcodes do. Only a reset gets it running again.
+    pub fn jam(&mut self) {
+        self.jammed = true;
+    }
+
+    /// Whether the CPU is halted by a JAM opcode.
+    pub fn jammed(&self) -> bool {
+        self.jammed
+    }
+
+    /// Stops the clock, like the STP instruction does. Only a reset gets it running again.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    /// Whether the clock is stopped by a STP instruction.
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Waits for an interrupt, like the WAI instruction does. An NMI, or an IRQ while the
+    /// interrupt disable flag is clear, is serviced as usual. A masked IRQ just resumes
+    /// execution with the next instruction.
+    pub fn wait(&mut self) {
+        self.waiting = true;
+    }
+
+    /// Whether a WAI instruction is waiting for an interrupt.
+    pub fn waiting(&self) -> bool {
+        self.waiting
+    }
+
+    /// Services a pending NMI or, if not masked, IRQ. Returns whether an interrupt was
+    /// serviced.
+    fn poll_interrupts(&mut self) -> bool {
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            self.interrupt(self.pc, NMI_VECTOR, false);
+            true
+        } else if self.irq_asserted() && !self.flags.i {
+            self.interrupt(self.pc, IRQ_VECTOR, false);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<B: Bus + 'static> CPU<B> {
+    /// Starts the fetch-decode-execute cycle. Note that only a single instruction is
+    /// executed, see [`CPU::step`] and the `run_*` methods to keep executing until a stop
+    /// condition is met. Panics if the instruction cannot be executed.
     pub fn start(&mut self) {
-        // TODO handle stack calls
-        // while !self.flags.b {
-            self
-                // Fetch the next instruction code from memory.
-                .fetch()
-                // Identify the instruction from the code retrieved.
-                .decode()
-                // Execute the instruction in our CPU.
-                .execute(self)
-        // }
+        self
+            // Fetch the next instruction code from memory.
+            .fetch()
+            // Identify the instruction from the code retrieved.
+            .decode_for(self.variant)
+            // Execute the instruction in our CPU.
+            .execute(self)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Executes a single instruction, returning the reason to stop if it should not be
+    /// followed by any other, or an error if it cannot be executed (in which case the
+    /// registers, flags, program counter and cycle count are unchanged, although the
+    /// opcode and possibly its operand have already been read from the bus). Interrupts
+    /// are serviced between instructions: if one is pending, this step only performs the
+    /// interrupt sequence. The devices on the bus are then ticked for the cycles elapsed.
+    /// A jammed or stopped CPU does nothing at all, while one waiting for an interrupt
+    /// only lets a cycle go by (so that the devices get a chance to raise one).
+    pub fn step(&mut self) -> Result<Option<StopReason>, ExecError> {
+        if self.jammed {
+            return Ok(Some(StopReason::Jam(self.pc)));
+        }
+        if self.stopped {
+            return Ok(Some(StopReason::Stop(self.pc)));
+        }
+        let cycles = self.cycles;
+        let reason = if self.waiting && !self.nmi_pending && !self.irq_asserted() {
+            self.cycles += 1;
+            Ok(None)
+        } else if self.poll_interrupts() {
+            self.waiting = false;
+            Ok(None)
+        } else {
+            self.waiting = false;
+            self.execute_next()
+        };
+        self.mem.tick(self.cycles.wrapping_sub(cycles));
+        reason
+    }
+
+    /// Fetches, decodes and executes the instruction at the program counter.
+    fn execute_next(&mut self) -> Result<Option<StopReason>, ExecError> {
+        let pc = self.pc;
+        let opcode = self.fetch();
+        match InstructionDecoder::try_from_byte_for(opcode, self.variant) {
+            Ok(ins) => ins.execute(self)?,
+            Err(err) => self.illegal_opcode(opcode, err)?,
+        }
+
+        if self.jammed {
+            Ok(Some(StopReason::Jam(pc)))
+        } else if self.stopped {
+            Ok(Some(StopReason::Stop(pc)))
+        } else if opcode == 0x00 {
+            Ok(Some(StopReason::Break(pc)))
+        } else if self.pc == pc && !self.nmi_pending && self.flags.i {
+            // The instruction jumped or branched to itself (e.g. `JMP *`), so nothing
+            // but an interrupt could ever get us out of here. IRQs are masked, and while
+            // they are not a device on the bus might still raise one.
+            Ok(Some(StopReason::InfiniteLoop(pc)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Handles an opcode which could not be decoded according to the
+    /// [`IllegalOpcodePolicy`] and, for unstable opcodes, the [`UnstableOpcodePolicy`].
+    fn illegal_opcode(&mut self, opcode: Byte, err: DecodeError) -> Result<(), ExecError> {
+        let error = ExecError::Decode { pc: self.pc, source: err };
+        let emulate = match (self.illegal_opcodes, self.unstable_opcodes) {
+            (IllegalOpcodePolicy::Error, _) => return Err(error),
+            (IllegalOpcodePolicy::Nop, _) => false,
+            (IllegalOpcodePolicy::Emulate, _) if !InstructionDecoder::is_unstable(opcode) => true,
+            (IllegalOpcodePolicy::Emulate, UnstableOpcodePolicy::Error) => return Err(error),
+            (IllegalOpcodePolicy::Emulate, UnstableOpcodePolicy::Nop) => false,
+            (IllegalOpcodePolicy::Emulate, UnstableOpcodePolicy::Emulate { .. }) => true,
+        };
+
+        if emulate {
+            InstructionDecoder::try_from_illegal_byte(opcode).ok_or(error)?.execute(self)
+        } else {
+            let operand = self.resolve(&InstructionDecoder::addressing_mode(opcode));
+            self.cycles += 2;
+            self.pc = self.pc.wrapping_add(operand.len);
+            Ok(())
+        }
+    }
+
+    /// Executes instructions until `predicate` returns true (checked before every
+    /// instruction), another stop condition is met or an instruction cannot be executed.
+    /// Breakpoints are ignored for the very first instruction so that execution can be
+    /// resumed from one.
+    pub fn run_until(&mut self, mut predicate: impl FnMut(&Self) -> bool) -> Result<StopReason, ExecError> {
+        let mut first = true;
+        loop {
+            if predicate(self) {
+                return Ok(StopReason::Predicate);
+            }
+            if !first && self.breakpoints.contains(&self.pc) {
+                return Ok(StopReason::Breakpoint(self.pc));
+            }
+            if let Some(reason) = self.step()? {
+                return Ok(reason);
+            }
+            first = false;
+        }
+    }
+
+    /// Executes instructions until at least `cycles` cycles have elapsed or another stop
+    /// condition is met.
+    pub fn run_for_cycles(&mut self, cycles: u32) -> Result<StopReason, ExecError> {
+        let target = self.cycles.saturating_add(cycles);
+        match self.run_until(|cpu| cpu.cycles >= target)? {
+            StopReason::Predicate => Ok(StopReason::CycleBudget),
+            reason => Ok(reason),
+        }
+    }
+
+    /// Executes `count` instructions unless another stop condition is met first.
+    pub fn run_for_instructions(&mut self, count: usize) -> Result<StopReason, ExecError> {
+        let mut executed = 0;
+        let reason = self.run_until(|_| {
+            executed += 1;
+            executed > count
+        })?;
+        match reason {
+            StopReason::Predicate => Ok(StopReason::InstructionBudget),
+            reason => Ok(reason),
+        }
+    }
+}
+
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Behaviour of [`CPU::reset`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResetMode {
+    /// Start executing at the address stored in the reset vector, like real hardware.
+    #[default]
+    Vector,
+    /// Start executing the bytes at the reset vector itself, without consuming any
+    /// cycles. This is convenient for tests which write a single instruction at $FFFC.
+    Legacy,
+}
+
+/// Behaviour of the CPU when it fetches an opcode which does not correspond to any
+/// documented instruction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IllegalOpcodePolicy {
+    /// Stop executing with [`ExecError::Decode`], leaving the program counter on the
+    /// offending opcode.
+    #[default]
+    Error,
+    /// Skip the opcode and its operand, as if it were a NOP of the same length.
+    Nop,
+    /// Execute the undocumented behaviour of the NMOS 6502. JAM opcodes halt the CPU, and
+    /// unstable opcodes follow the [`UnstableOpcodePolicy`].
+    Emulate,
+}
+
+/// Behaviour of the CPU when it fetches one of the unstable undocumented opcodes (ANE,
+/// LXA, SHA, SHX, SHY and TAS), whose results vary between chips and even with their
+/// temperature. Only used with [`IllegalOpcodePolicy::Emulate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnstableOpcodePolicy {
+    /// Stop executing with [`ExecError::Decode`].
+    Error,
+    /// Skip the opcode and its operand, as if it were a NOP of the same length.
+    Nop,
+    /// Execute the most common behaviour. ANE and LXA OR the accumulator with `magic`
+    /// before ANDing it, which is usually $EE or $FF.
+    Emulate { magic: Byte },
+}
+
+impl UnstableOpcodePolicy {
+    /// The magic constant of ANE and LXA, falling back to the default one when they are
+    /// not emulated.
+    pub fn magic(&self) -> Byte {
+        match self {
+            UnstableOpcodePolicy::Emulate { magic } => *magic,
+            _ => 0xEE,
+        }
     }
 }
 
+impl Default for UnstableOpcodePolicy {
+    fn default() -> Self {
+        UnstableOpcodePolicy::Emulate { magic: 0xEE }
+    }
+}
+
+/// The operand of an instruction, as resolved by [`CPU::resolve`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Operand {
+    /// Effective address, or `None` when operating on the accumulator (or on nothing at
+    /// all for implicit instructions). Immediate operands live right after the opcode, and
+    /// the address of relative operands is the branch target (but not of zero page
+    /// relative ones, which address the byte tested).
+    pub address: Option<Word>,
+    /// Length of the instruction in bytes, opcode included.
+    pub len: Word,
+    /// Whether indexing crossed a page boundary, or the branch target is on a different
+    /// page than the next instruction. Some instructions take an extra cycle when it does.
+    pub page_crossed: bool,
+}
+
+/// The reason why the CPU stopped executing instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// A BRK instruction at the given address was executed.
+    Break(Word),
+    /// The cycle budget given to [`CPU::run_for_cycles`] was exhausted.
+    CycleBudget,
+    /// The instruction budget given to [`CPU::run_for_instructions`] was exhausted.
+    InstructionBudget,
+    /// The program counter reached a breakpoint. The instruction has not been executed.
+    Breakpoint(Word),
+    /// A JAM opcode at the given address halted the CPU until the next reset.
+    Jam(Word),
+    /// A STP instruction at the given address stopped the clock until the next reset.
+    Stop(Word),
+    /// The instruction at the given address jumps to itself (e.g. `JMP *`).
+    InfiniteLoop(Word),
+    /// The predicate given to [`CPU::run_until`] returned true.
+    Predicate,
+}
+
 /// Storage location that holds inputs and outputs for the ALU.
 #[derive(Clone)]
 pub struct Registers {
@@ -197,6 +760,7 @@ impl From<Byte> for StatusFlags {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::asm;
 
     #[test]
     fn test_status_flags_into_byte() {
@@ -230,4 +794,553 @@ mod tests {
         assert_eq!(StatusFlags::from(0b01110100), flags);
 
     }
+
+    #[test]
+    fn reset_vector() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x8000);
+        cpu.reg.acc = 0x42;
+        cpu.sp = 0x12;
+        cpu.reset();
+
+        assert_eq!(cpu.pc, 0x8000);
+        assert_eq!(cpu.sp, 0xFD);
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.i);
+        assert_eq!(cpu.cycles, 7);
+        // Memory survives a reset.
+        assert_eq!(cpu.mem.read_word(RESET_VECTOR), 0x8000);
+    }
+
+    #[test]
+    fn reset_legacy() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.mem.write_word(RESET_VECTOR, 0x8000);
+        cpu.reset();
+
+        assert_eq!(cpu.pc, RESET_VECTOR);
+        assert_eq!(cpu.sp, 0xFD);
+        assert!(cpu.flags.i);
+        assert_eq!(cpu.cycles, 0);
+    }
+
+    #[test]
+    fn resolve_wraps_around() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+        cpu.pc = 0x0200;
+        cpu.reg.x = 0x10;
+        cpu.reg.y = 0x20;
+
+        // Indexing past $FFFF wraps to the zero page, crossing a page.
+        cpu.mem.write_word(0x0201, 0xFFF8);
+        let operand = cpu.resolve(&Addr::AbsoluteX);
+        assert_eq!(operand, Operand { address: Some(0x0008), len: 3, page_crossed: true });
+
+        // Zero page indexing stays within the zero page.
+        cpu.mem.write_byte(0x0201, 0xF8);
+        assert_eq!(cpu.resolve(&Addr::ZeroPageX).address, Some(0x0008));
+        assert_eq!(cpu.resolve(&Addr::ZeroPageY).address, Some(0x0018));
+
+        // Zero page pointers at $FF take their high byte from $00.
+        cpu.mem.write_byte(0x0201, 0xEF);
+        cpu.mem.write_byte(0x00FF, 0x34);
+        cpu.mem.write_byte(0x0000, 0x12);
+        cpu.mem.write_byte(0x0100, 0x56);
+        assert_eq!(cpu.resolve(&Addr::XIndirect).address, Some(0x1234));
+        cpu.mem.write_byte(0x0201, 0xFF);
+        let operand = cpu.resolve(&Addr::IndirectY);
+        assert_eq!(operand, Operand { address: Some(0x1254), len: 2, page_crossed: false });
+    }
+
+    #[test]
+    fn implied_wraps_around() {
+        // TAX, TAY, TXA, TYA, INX, INY, DEX, DEY, PHA, PHP, PLA, PLP, TSX, TXS, CLC, CLD,
+        // CLI, CLV, SEC, SED and SEI.
+        let opcodes = [
+            0xAA, 0xA8, 0x8A, 0x98, 0xE8, 0xC8, 0xCA, 0x88, 0x48, 0x08, 0x68, 0x28, 0xBA, 0x9A,
+            0x18, 0xD8, 0x58, 0xB8, 0x38, 0xF8, 0x78,
+        ];
+        for opcode in opcodes {
+            let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+            cpu.pc = 0xFFFF;
+            cpu.mem.write_byte(0xFFFF, opcode);
+            assert_eq!(cpu.step(), Ok(None), "opcode {opcode:#04X}");
+            assert_eq!(cpu.pc, 0x0000, "opcode {opcode:#04X}");
+        }
+
+        // PHX, PHY, PLX and PLY on the 65C02.
+        for opcode in [0xDA, 0x5A, 0xFA, 0x7A] {
+            let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+            cpu.variant = Variant::Cmos65C02;
+            cpu.pc = 0xFFFF;
+            cpu.mem.write_byte(0xFFFF, opcode);
+            assert_eq!(cpu.step(), Ok(None), "opcode {opcode:#04X}");
+            assert_eq!(cpu.pc, 0x0000, "opcode {opcode:#04X}");
+        }
+    }
+
+    #[test]
+    fn resolve_relative() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+        cpu.pc = 0x0200;
+
+        cpu.mem.write_byte(0x0201, 0xFC); // -4
+        let operand = cpu.resolve(&Addr::Relative);
+        assert_eq!(operand, Operand { address: Some(0x01FE), len: 2, page_crossed: true });
+
+        cpu.mem.write_byte(0x0201, 0x10);
+        let operand = cpu.resolve(&Addr::Relative);
+        assert_eq!(operand, Operand { address: Some(0x0212), len: 2, page_crossed: false });
+    }
+
+    /// Sets up a program at $0200 made of `INX` instructions, and an interrupt handler
+    /// at $0300 which increments Y and returns.
+    fn interrupt_cpu(vector: Word) -> CPU {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_word(vector, 0x0300);
+        for addr in 0x0200..0x0210 {
+            cpu.mem.write_byte(addr, 0xE8); // INX
+        }
+        cpu.mem.write_byte(0x0300, 0xC8); // INY
+        cpu.mem.write_byte(0x0301, 0x40); // RTI
+        cpu.reset();
+        cpu
+    }
+
+    #[test]
+    fn irq() {
+        let mut cpu = interrupt_cpu(IRQ_VECTOR);
+        cpu.flags.i = false;
+        cpu.flags.c = true;
+        let sp = cpu.sp;
+
+        cpu.step().unwrap();
+        cpu.assert_irq(0);
+        let cycles = cpu.cycles;
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x0300);
+        assert_eq!(cpu.cycles, cycles + 7);
+        assert!(cpu.flags.i);
+        // Return address, then the status with B clear and bit 5 set.
+        assert_eq!(cpu.mem.read_word(CPU::stack_address(sp - 1)), 0x0201);
+        assert_eq!(cpu.mem.read_byte(CPU::stack_address(sp - 2)), 0b00100001);
+
+        // The handler runs with interrupts disabled, so the asserted line is ignored
+        // until RTI restores the flags.
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0201);
+        assert!(!cpu.flags.i);
+        assert_eq!(cpu.reg.y, 1);
+        assert_eq!(cpu.sp, sp);
+
+        // The line is level-triggered, so the interrupt is taken again.
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0300);
+    }
+
+    #[test]
+    fn irq_masked() {
+        let mut cpu = interrupt_cpu(IRQ_VECTOR);
+        cpu.flags.i = true;
+
+        cpu.assert_irq(0);
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x0202);
+        assert_eq!(cpu.reg.x, 2);
+    }
+
+    #[test]
+    fn irq_shared_line() {
+        let mut cpu = interrupt_cpu(IRQ_VECTOR);
+
+        cpu.assert_irq(3);
+        cpu.assert_irq(7);
+        cpu.release_irq(3);
+        assert!(cpu.irq_asserted());
+
+        cpu.release_irq(7);
+        assert!(!cpu.irq_asserted());
+
+        // Every source has a line of its own.
+        for source in [31, 32, 63, 64, 255] {
+            cpu.assert_irq(source);
+        }
+        for source in [31, 32, 63, 64, 255] {
+            assert!(cpu.irq_asserted());
+            cpu.release_irq(source);
+        }
+        assert!(!cpu.irq_asserted());
+
+        cpu.flags.i = false;
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0201);
+    }
+
+    #[test]
+    fn nmi() {
+        let mut cpu = interrupt_cpu(NMI_VECTOR);
+        cpu.flags.i = true;
+
+        cpu.trigger_nmi();
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0300);
+
+        // The NMI is edge-triggered, so it is only serviced once.
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0200);
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0201);
+        assert_eq!(cpu.reg.y, 1);
+    }
+
+    #[test]
+    fn nmi_has_priority_over_irq() {
+        let mut cpu = interrupt_cpu(NMI_VECTOR);
+        cpu.flags.i = false;
+        cpu.mem.write_word(IRQ_VECTOR, 0x0400);
+
+        cpu.assert_irq(0);
+        cpu.trigger_nmi();
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0300);
+
+        // The IRQ is still pending once the NMI handler returns.
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+        assert_eq!(cpu.pc, 0x0400);
+    }
+
+    /// RAM with a latch at $D000 which is cleared whenever it is read.
+    struct LatchBus {
+        ram: Memory,
+        latch: Byte,
+    }
+
+    impl Bus for LatchBus {
+        fn read(&mut self, address: Word) -> Byte {
+            match address {
+                0xD000 => std::mem::take(&mut self.latch),
+                _ => self.ram.read_byte(address),
+            }
+        }
+
+        fn write(&mut self, address: Word, data: Byte) {
+            match address {
+                0xD000 => self.latch = data,
+                _ => self.ram.write_byte(address, data),
+            }
+        }
+
+        fn peek(&self, address: Word) -> Byte {
+            match address {
+                0xD000 => self.latch,
+                _ => self.ram.read_byte(address),
+            }
+        }
+    }
+
+    #[test]
+    fn custom_bus() {
+        let mut ram = Memory::new();
+        ram.write_word(RESET_VECTOR, 0x0200);
+        ram.write_bytes(0x0200, &asm! {
+            lda $D000
+            ldx $D000
+        });
+        let mut cpu = CPU::with_bus(LatchBus { ram, latch: 0x42 }, ResetMode::Vector);
+
+        assert_eq!(cpu.pc, 0x0200);
+        assert_eq!(cpu.peek_byte(0xD000), 0x42);
+        assert_eq!(cpu.peek_byte(0xD000), 0x42);
+
+        cpu.step().unwrap();
+        assert_eq!(cpu.reg.acc, 0x42);
+        assert_eq!(cpu.peek_byte(0xD000), 0x00);
+
+        cpu.step().unwrap();
+        assert_eq!(cpu.reg.x, 0x00);
+    }
+
+    #[test]
+    fn run_until_brk() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! {
+            inx
+            inx
+            brk
+        });
+        cpu.reset();
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Break(0x0202)));
+        assert_eq!(cpu.reg.x, 2);
+    }
+
+    #[test]
+    fn run_until_predicate() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! {
+            inx
+            jmp $0200
+        });
+        cpu.reset();
+
+        assert_eq!(cpu.run_until(|cpu| cpu.reg.x == 5), Ok(StopReason::Predicate));
+        assert_eq!(cpu.reg.x, 5);
+    }
+
+    #[test]
+    fn run_for_instructions() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! {
+            inx
+            jmp $0200
+        });
+        cpu.reset();
+
+        assert_eq!(cpu.run_for_instructions(7), Ok(StopReason::InstructionBudget));
+        assert_eq!(cpu.reg.x, 4);
+        assert_eq!(cpu.pc, 0x0201);
+    }
+
+    #[test]
+    fn run_for_cycles() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        // A chain of taken branches to the next instruction, 3 cycles each.
+        for addr in (0x0200..0x0220).step_by(2) {
+            cpu.mem.write_byte(addr, 0x90); // BCC +0
+            cpu.mem.write_byte(addr + 1, 0x00);
+        }
+        cpu.reset();
+
+        assert_eq!(cpu.run_for_cycles(10), Ok(StopReason::CycleBudget));
+        assert_eq!(cpu.cycles, 7 + 12);
+        assert_eq!(cpu.pc, 0x0208);
+    }
+
+    #[test]
+    fn run_until_breakpoint() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! {
+            inx
+            iny
+            brk
+        });
+        cpu.reset();
+        cpu.breakpoints.insert(0x0201);
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Breakpoint(0x0201)));
+        assert_eq!((cpu.reg.x, cpu.reg.y), (1, 0));
+
+        // Resuming from a breakpoint executes the instruction at it.
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Break(0x0202)));
+        assert_eq!((cpu.reg.x, cpu.reg.y), (1, 1));
+    }
+
+    #[test]
+    fn run_until_illegal_opcode() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_byte(0x0200, 0xE8); // INX
+        cpu.mem.write_byte(0x0201, 0x02); // Illegal
+        cpu.reset();
+
+        assert_eq!(
+            cpu.run_until(|_| false),
+            Err(ExecError::Decode { pc: 0x0201, source: DecodeError::IllegalOpcode(0x02) })
+        );
+        assert_eq!(cpu.pc, 0x0201);
+        assert_eq!(cpu.reg.x, 1);
+    }
+
+    #[test]
+    fn illegal_opcode_as_nop() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_byte(0x0200, 0x0C); // Illegal, absolute
+        cpu.mem.write_word(0x0201, 0x1234);
+        cpu.mem.write_byte(0x0203, 0x00); // BRK
+        cpu.reset();
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Nop;
+
+        assert_eq!(cpu.step(), Ok(None));
+        assert_eq!(cpu.pc, 0x0203);
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Break(0x0203)));
+    }
+
+    #[test]
+    fn illegal_opcode_emulated() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! {
+            lax $42
+            brk
+        });
+        cpu.mem.write_byte(0x0042, 0x37);
+        cpu.reset();
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Emulate;
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Break(0x0202)));
+        assert_eq!((cpu.reg.acc, cpu.reg.x), (0x37, 0x37));
+    }
+
+    #[test]
+    fn unstable_opcode_policy() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! { ane #$FF });
+        cpu.reset();
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Emulate;
+
+        cpu.unstable_opcodes = UnstableOpcodePolicy::Error;
+        assert_eq!(
+            cpu.step(),
+            Err(ExecError::Decode { pc: 0x0200, source: DecodeError::IllegalOpcode(0x8B) })
+        );
+
+        cpu.unstable_opcodes = UnstableOpcodePolicy::Nop;
+        cpu.reg.x = 0xFF;
+        assert_eq!(cpu.step(), Ok(None));
+        assert_eq!((cpu.reg.acc, cpu.pc), (0x00, 0x0202));
+
+        cpu.pc = 0x0200;
+        cpu.unstable_opcodes = UnstableOpcodePolicy::Emulate { magic: 0xFF };
+        assert_eq!(cpu.step(), Ok(None));
+        assert_eq!((cpu.reg.acc, cpu.pc), (0xFF, 0x0202));
+    }
+
+    #[test]
+    fn jam() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_byte(0x0200, 0xE8); // INX
+        cpu.mem.write_byte(0x0201, 0x02); // JAM
+        cpu.reset();
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Emulate;
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Jam(0x0201)));
+        assert!(cpu.jammed());
+
+        // Not even an NMI gets the CPU out of it.
+        let cycles = cpu.cycles;
+        cpu.trigger_nmi();
+        assert_eq!(cpu.step(), Ok(Some(StopReason::Jam(0x0201))));
+        assert_eq!((cpu.pc, cpu.cycles), (0x0201, cycles));
+
+        cpu.reset();
+        assert!(!cpu.jammed());
+        assert_eq!(cpu.step(), Ok(None));
+    }
+
+    #[test]
+    fn stp() {
+        let mut cpu = CPU::new();
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_byte(0x0200, 0xE8); // INX
+        cpu.mem.write_byte(0x0201, 0xDB); // STP
+        cpu.reset();
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Stop(0x0201)));
+        assert!(cpu.stopped());
+
+        // Interrupts are ignored until the next reset.
+        let cycles = cpu.cycles;
+        cpu.trigger_nmi();
+        cpu.assert_irq(0);
+        assert_eq!(cpu.step(), Ok(Some(StopReason::Stop(0x0201))));
+        assert_eq!((cpu.pc, cpu.cycles), (0x0201, cycles));
+
+        cpu.reset();
+        assert!(!cpu.stopped());
+        assert_eq!(cpu.step(), Ok(None));
+    }
+
+    fn waiting_cpu(vector: Word) -> CPU {
+        let mut cpu = interrupt_cpu(vector);
+        cpu.variant = Variant::Wdc65C02;
+        cpu.mem.write_byte(0x0200, 0xCB); // WAI
+        cpu.step().unwrap();
+        cpu
+    }
+
+    #[test]
+    fn wai_irq() {
+        let mut cpu = waiting_cpu(IRQ_VECTOR);
+        cpu.flags.i = false;
+
+        // Waiting lets the clock (and the devices) run, one cycle per step.
+        let cycles = cpu.cycles;
+        assert_eq!(cpu.run_for_cycles(10), Ok(StopReason::CycleBudget));
+        assert_eq!((cpu.pc, cpu.cycles), (0x0201, cycles + 10));
+        assert!(cpu.waiting());
+
+        cpu.assert_irq(0);
+        cpu.step().unwrap();
+        assert!(!cpu.waiting());
+        assert_eq!(cpu.pc, 0x0300);
+        assert_eq!(cpu.mem.read_word(CPU::stack_address(cpu.sp + 2)), 0x0201);
+    }
+
+    #[test]
+    fn wai_masked_irq() {
+        let mut cpu = waiting_cpu(IRQ_VECTOR);
+
+        // A masked IRQ is not serviced, but still ends the wait.
+        cpu.assert_irq(0);
+        cpu.step().unwrap();
+        assert!(!cpu.waiting());
+        assert_eq!(cpu.pc, 0x0202);
+        assert_eq!(cpu.reg.x, 1);
+    }
+
+    #[test]
+    fn wai_nmi() {
+        let mut cpu = waiting_cpu(NMI_VECTOR);
+
+        cpu.trigger_nmi();
+        cpu.step().unwrap();
+        assert!(!cpu.waiting());
+        assert_eq!(cpu.pc, 0x0300);
+    }
+
+    #[test]
+    fn run_until_infinite_loop() {
+        let mut cpu = CPU::new();
+
+        cpu.mem.write_word(RESET_VECTOR, 0x0200);
+        cpu.mem.write_bytes(0x0200, &asm! {
+            inx
+            jmp $0201
+        });
+        cpu.reset();
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::InfiniteLoop(0x0201)));
+        assert_eq!(cpu.reg.x, 1);
+    }
 }
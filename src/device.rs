@@ -0,0 +1,161 @@
+use crate::{Byte, Word};
+
+/// A memory-mapped peripheral registered at a range of addresses in a
+/// [`MemoryMap`](crate::map::MemoryMap), e.g. a timer or a video chip. Registers are
+/// addressed as offsets from the start of that range.
+pub trait Device: DeviceClone {
+    /// Read the register at `offset` on behalf of the CPU. This may have side effects,
+    /// like acknowledging an interrupt or popping a byte from a FIFO.
+    fn read(&mut self, offset: Word) -> Byte;
+
+    /// Read the register at `offset` without any side effects, e.g. for debuggers.
+    fn peek(&self, offset: Word) -> Byte;
+
+    /// Write a byte of data to the register at `offset`.
+    fn write(&mut self, offset: Word, data: Byte);
+
+    /// Advance the device by one clock cycle.
+    fn tick(&mut self) {}
+
+    /// Whether the device is pulling the IRQ line low.
+    fn irq(&self) -> bool {
+        false
+    }
+}
+
+/// Allows cloning boxed devices, implemented for every [`Device`] which is [`Clone`].
+pub trait DeviceClone {
+    fn clone_box(&self) -> Box<dyn Device>;
+}
+
+impl<T: Device + Clone + 'static> DeviceClone for T {
+    fn clone_box(&self) -> Box<dyn Device> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Device> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::cpu::{CPU, ResetMode, StopReason, IRQ_VECTOR, RESET_VECTOR};
+    use crate::map::MemoryMap;
+
+    /// Counts down once per cycle from the value written to register 0, then raises an
+    /// IRQ. Reading register 1 returns whether the timer expired and acknowledges it.
+    #[derive(Clone, Default)]
+    struct Timer {
+        counter: Byte,
+        expired: bool,
+    }
+
+    impl Device for Timer {
+        fn read(&mut self, offset: Word) -> Byte {
+            let value = self.peek(offset);
+            if offset == 1 {
+                self.expired = false;
+            }
+            value
+        }
+
+        fn peek(&self, offset: Word) -> Byte {
+            match offset {
+                0 => self.counter,
+                _ => self.expired as Byte,
+            }
+        }
+
+        fn write(&mut self, offset: Word, data: Byte) {
+            if offset == 0 {
+                self.counter = data;
+            }
+        }
+
+        fn tick(&mut self) {
+            if self.counter > 0 {
+                self.counter -= 1;
+                self.expired = self.counter == 0;
+            }
+        }
+
+        fn irq(&self) -> bool {
+            self.expired
+        }
+    }
+
+    #[test]
+    fn read_write() {
+        let mut map = MemoryMap::builder()
+            .device(0xD000..=0xD001, Timer::default())
+            .build()
+            .unwrap();
+
+        map.write(0xD000, 2);
+        assert_eq!(map.read(0xD000), 2);
+
+        map.tick(2);
+        assert_eq!(map.read(0xD000), 0);
+        assert!(map.irq());
+
+        // Peeking leaves the status untouched, reading acknowledges it.
+        assert_eq!(map.peek(0xD001), 1);
+        assert!(map.irq());
+        assert_eq!(map.read(0xD001), 1);
+        assert!(!map.irq());
+        assert_eq!(map.read(0xD001), 0);
+    }
+
+    #[test]
+    fn mirrored() {
+        let mut map = MemoryMap::builder()
+            .device(0xD000..=0xD001, Timer::default())
+            .mirror(0xD002..=0xD0FF, 0xD000..=0xD001)
+            .build()
+            .unwrap();
+
+        map.write(0xD010, 5);
+        assert_eq!(map.read(0xD000), 5);
+    }
+
+    #[test]
+    fn irq_from_device() {
+        let map = MemoryMap::builder()
+            .ram(0x0000..=0x07FF)
+            .device(0xD000..=0xD001, Timer::default())
+            .ram(0xFF00..=0xFFFF)
+            .build()
+            .unwrap();
+        let mut cpu = CPU::with_bus(map, ResetMode::Vector);
+
+        let program = [
+            0x58,             // CLI
+            0xA9, 0x10,       // LDA #$10
+            0x8D, 0x00, 0xD0, // STA $D000
+            0x4C, 0x06, 0x02, // JMP $0206
+        ];
+        let handler = [
+            0xAD, 0x01, 0xD0, // LDA $D001
+            0x00,             // BRK
+        ];
+        for (addr, byte) in (0x0200..).zip(program) {
+            cpu.write_byte(addr, byte);
+        }
+        for (addr, byte) in (0x0300..).zip(handler) {
+            cpu.write_byte(addr, byte);
+        }
+        cpu.write_word(RESET_VECTOR, 0x0200);
+        cpu.write_word(IRQ_VECTOR, 0x0300);
+        cpu.reset();
+
+        assert_eq!(cpu.run_until(|_| false), Ok(StopReason::Break(0x0303)));
+        // The handler acknowledged the interrupt.
+        assert_eq!(cpu.reg.acc, 1);
+        assert!(!cpu.irq_asserted());
+    }
+}
@@ -0,0 +1,401 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::{Byte, Word};
+use crate::bus::Bus;
+
+/// Add with Carry - Adds the contents of a memory location to the accumulator together
+/// with the carry bit. If overflow occurs the carry bit is set, this enables multiple
+/// byte addition to be performed. When the decimal flag is set the operands are treated
+/// as packed BCD values, except on the Ricoh 2A03.
+pub struct ADC(pub Addr);
+
+impl ADC {
+    /// Adds `value` and the carry flag to the accumulator, updating the C, Z, V and N
+    /// flags the way an NMOS 6502 does in both binary and decimal mode. The 65C02 sets N
+    /// and Z from the decimal result instead.
+    pub fn add<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) {
+        let acc = cpu.reg.acc;
+        let carry = cpu.flags.c as Word;
+        let binary = acc as Word + value as Word + carry;
+
+        // The zero flag is always computed from the binary sum, even in decimal mode.
+        cpu.flags.z = (binary & 0xFF) == 0;
+
+        if cpu.flags.d && cpu.variant.has_decimal_mode() {
+            // Add the low nibbles and adjust them if they are not a valid BCD digit.
+            let mut lo = (acc & 0x0F) as Word + (value & 0x0F) as Word + carry;
+            if lo >= 0x0A {
+                lo = ((lo + 0x06) & 0x0F) + 0x10;
+            }
+            // N and V are taken from the intermediate result, before the high nibble is
+            // adjusted, which is why they look "wrong" on the NMOS 6502.
+            let signed = (acc & 0xF0) as i8 as i16 + (value & 0xF0) as i8 as i16 + lo as i16;
+            cpu.flags.n = (signed & 0x80) > 0;
+            cpu.flags.v = !(-128..=127).contains(&signed);
+
+            let mut result = (acc & 0xF0) as Word + (value & 0xF0) as Word + lo;
+            if result >= 0xA0 {
+                result += 0x60;
+            }
+            cpu.flags.c = result >= 0x100;
+            cpu.reg.acc = result as Byte;
+
+            if cpu.variant.is_cmos() {
+                cpu.flags.z = cpu.reg.acc == 0;
+                cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+            }
+        } else {
+            let result = binary as Byte;
+            cpu.flags.c = binary > 0xFF;
+            // Overflow if both operands share a sign which differs from the result's.
+            cpu.flags.v = ((acc ^ result) & (value ^ result) & 0b10000000) > 0;
+            cpu.flags.n = (result & 0b10000000) > 0;
+            cpu.reg.acc = result;
+        }
+    }
+}
+
+impl Instruction for ADC {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            ADC(Addr::Immediate) => cpu.cycles += 2,
+            // 2B, 3C
+            ADC(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            ADC(Addr::ZeroPageX) => cpu.cycles += 4,
+            // 3B, 4C
+            ADC(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 4C (+1 if page crossed)
+            ADC(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 3B, 4C (+1 if page crossed)
+            ADC(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 2B, 6C
+            ADC(Addr::XIndirect) => cpu.cycles += 6,
+            // 2B, 5C (+1 if page crossed)
+            ADC(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            ADC(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        // The 65C02 takes an extra cycle to fix up the flags in decimal mode.
+        if cpu.flags.d && cpu.variant.is_cmos() {
+            cpu.cycles += 1;
+        }
+        let value = cpu.read_operand(&operand);
+        Self::add(cpu, value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ADC(Addr::Immediate) => Ok(0x69),
+            ADC(Addr::ZeroPage) => Ok(0x65),
+            ADC(Addr::ZeroPageX) => Ok(0x75),
+            ADC(Addr::Absolute) => Ok(0x6D),
+            ADC(Addr::AbsoluteX) => Ok(0x7D),
+            ADC(Addr::AbsoluteY) => Ok(0x79),
+            ADC(Addr::XIndirect) => Ok(0x61),
+            ADC(Addr::IndirectY) => Ok(0x71),
+            ADC(Addr::ZeroPageIndirect) => Ok(0x72),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ADC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    /// Reference model of an NMOS ADC, written independently of `ADC::add` (it follows
+    /// the nibble-based formulation used by VICE). Returns `(acc, c, z, v, n)`.
+    fn reference(acc: Byte, value: Byte, carry: bool, decimal: bool) -> (Byte, bool, bool, bool, bool) {
+        let (a, m, c) = (acc as u32, value as u32, carry as u32);
+        if !decimal {
+            let sum = a + m + c;
+            let signed = acc as i8 as i32 + value as i8 as i32 + c as i32;
+            let result = sum as Byte;
+            return (result, sum > 0xFF, result == 0, !(-128..=127).contains(&signed), result >= 0x80);
+        }
+
+        let z = ((a + m + c) & 0xFF) == 0;
+        let mut tmp = (a & 0x0F) + (m & 0x0F) + c;
+        if tmp > 0x09 {
+            tmp += 0x06;
+        }
+        tmp = if tmp <= 0x0F {
+            (tmp & 0x0F) + (a & 0xF0) + (m & 0xF0)
+        } else {
+            (tmp & 0x0F) + (a & 0xF0) + (m & 0xF0) + 0x10
+        };
+        let n = (tmp & 0x80) > 0;
+        let v = ((a ^ tmp) & 0x80) > 0 && ((a ^ m) & 0x80) == 0;
+        if (tmp & 0x1F0) > 0x90 {
+            tmp += 0x60;
+        }
+        let c = (tmp & 0xFF0) > 0xF0;
+        (tmp as Byte, c, z, v, n)
+    }
+
+    #[test]
+    fn adc_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x50;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x50);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0xA0);
+
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert!(cpu.flags.v);
+        assert!(cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn adc_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x01);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x01);
+
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert!(!cpu.flags.v);
+        assert!(!cpu.flags.n);
+
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn adc_zero_page_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x80;
+        cpu.reg.x = 0x02;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::ZeroPageX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0x0001, 0x80); // 0xFF + 0x02 % 0xFF = 0x01
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(cpu.flags.v);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn adc_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x12;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x46);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn adc_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x12;
+        cpu.reg.x = 0x12;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x46);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn adc_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x12;
+        cpu.reg.y = 0x12;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x46);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn adc_x_indirect() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x12;
+        cpu.reg.x = 0x04;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::XIndirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02); // 0x02 + 0x04 = 0x06
+        cpu.mem.write_byte(0x0006, 0x00);
+        cpu.mem.write_byte(0x0007, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8000, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x46);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn adc_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x12;
+        cpu.reg.y = 0x04;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0x00);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8004, 0x34); // 0x8000 + 0x0004
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x46);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn adc_decimal() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.d = true;
+        cpu.reg.acc = 0x58;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x46);
+
+        cpu.start();
+
+        // 58 + 46 = 104 in BCD.
+        assert_eq!(cpu.reg.acc, 0x04);
+        assert!(cpu.flags.c);
+    }
+
+    #[test]
+    fn adc_exhaustive() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::Immediate).code().unwrap());
+
+        for decimal in [false, true] {
+            for carry in [false, true] {
+                for acc in 0..=0xFF {
+                    for value in 0..=0xFF {
+                        cpu.pc = 0xFFFC;
+                        cpu.reg.acc = acc;
+                        cpu.flags.c = carry;
+                        cpu.flags.d = decimal;
+                        cpu.mem.write_byte(0xFFFD, value);
+                        cpu.start();
+
+                        let (r_acc, c, z, v, n) = reference(acc, value, carry, decimal);
+                        let ctx = format!("{acc:02X} + {value:02X} + {carry} (decimal: {decimal})");
+                        assert_eq!(cpu.reg.acc, r_acc, "A: {ctx}");
+                        assert_eq!(cpu.flags.c, c, "C: {ctx}");
+                        assert_eq!(cpu.flags.z, z, "Z: {ctx}");
+                        assert_eq!(cpu.flags.v, v, "V: {ctx}");
+                        assert_eq!(cpu.flags.n, n, "N: {ctx}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adc_decimal_cmos() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Cmos65C02;
+        cpu.flags.d = true;
+        cpu.reg.acc = 0x99;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x01);
+
+        cpu.step().unwrap();
+
+        // 99 + 01 = 100 in BCD, with Z and N taken from the decimal result.
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        // The 65C02 spends an extra cycle in decimal mode.
+        assert_eq!(cpu.cycles, 3);
+    }
+
+    #[test]
+    fn adc_ricoh_ignores_decimal() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Ricoh2A03;
+        cpu.flags.d = true;
+        cpu.reg.acc = 0x58;
+        cpu.mem.write_byte(0xFFFC, ADC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x46);
+
+        cpu.step().unwrap();
+
+        // The D flag is set but the 2A03 adds in binary regardless.
+        assert_eq!(cpu.reg.acc, 0x9E);
+        assert!(!cpu.flags.c);
+        assert_eq!(cpu.cycles, 2);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Compare - Compares the contents of the accumulator with another memory held value
+/// and sets the zero and carry flags as appropriate.
+pub struct CMP(pub Addr);
+
+impl CMP {
+    /// Sets the flags resulting from comparing `reg` against `value`. Shared by CMP, CPX
+    /// and CPY, which only differ in the register being compared.
+    pub fn set_flags<B: Bus + ?Sized>(cpu: &mut CPU<B>, reg: Byte, value: Byte) {
+        let result = reg.wrapping_sub(value);
+        // Set carry flag if reg >= value
+        cpu.flags.c = reg >= value;
+        // Set zero flag if reg = value
+        cpu.flags.z = reg == value;
+        // Set negative flag if bit 7 of the result is set
+        cpu.flags.n = (result & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for CMP {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            CMP(Addr::Immediate) => cpu.cycles += 2,
+            // 2B, 3C
+            CMP(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            CMP(Addr::ZeroPageX) => cpu.cycles += 4,
+            // 3B, 4C
+            CMP(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 4C (+1 if page crossed)
+            CMP(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 3B, 4C (+1 if page crossed)
+            CMP(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 2B, 6C
+            CMP(Addr::XIndirect) => cpu.cycles += 6,
+            // 2B, 5C (+1 if page crossed)
+            CMP(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            CMP(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand);
+        Self::set_flags(cpu, cpu.reg.acc, value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            CMP(Addr::Immediate) => Ok(0xC9),
+            CMP(Addr::ZeroPage) => Ok(0xC5),
+            CMP(Addr::ZeroPageX) => Ok(0xD5),
+            CMP(Addr::Absolute) => Ok(0xCD),
+            CMP(Addr::AbsoluteX) => Ok(0xDD),
+            CMP(Addr::AbsoluteY) => Ok(0xD9),
+            CMP(Addr::XIndirect) => Ok(0xC1),
+            CMP(Addr::IndirectY) => Ok(0xD1),
+            CMP(Addr::ZeroPageIndirect) => Ok(0xD2),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "CMP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn cmp_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x42;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x42);
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert_eq!(cpu.flags.v, cpu_start.flags.v);
+        assert!(!cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn cmp_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x10;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x20);
+
+        cpu.start();
+
+        // 0x10 - 0x20 = 0xF0
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn cmp_zero_page_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x80;
+        cpu.reg.x = 0x02;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::ZeroPageX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0x0001, 0x01); // 0xFF + 0x02 % 0xFF = 0x01
+
+        cpu.start();
+
+        // 0x80 - 0x01 = 0x7F
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn cmp_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x37;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0x37);
+
+        cpu.start();
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn cmp_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x37;
+        cpu.reg.x = 0x12;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x37);
+
+        cpu.start();
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn cmp_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x37;
+        cpu.reg.y = 0x12;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x37);
+
+        cpu.start();
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn cmp_x_indirect() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x37;
+        cpu.reg.x = 0x04;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::XIndirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02); // 0x02 + 0x04 = 0x06
+        cpu.mem.write_byte(0x0006, 0x00);
+        cpu.mem.write_byte(0x0007, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8000, 0x37);
+
+        cpu.start();
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn cmp_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x37;
+        cpu.reg.y = 0x04;
+        cpu.mem.write_byte(0xFFFC, CMP(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0x00);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8004, 0x37); // 0x8000 + 0x0004
+
+        cpu.start();
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::cmp::CMP;
+use crate::bus::Bus;
+
+/// Compare X Register - Compares the contents of the X register with another memory
+/// held value and sets the zero and carry flags as appropriate.
+pub struct CPX(pub Addr);
+
+impl Instruction for CPX {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            CPX(Addr::Immediate) => cpu.cycles += 2,
+            // 2B, 3C
+            CPX(Addr::ZeroPage) => cpu.cycles += 3,
+            // 3B, 4C
+            CPX(Addr::Absolute) => cpu.cycles += 4,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand);
+        CMP::set_flags(cpu, cpu.reg.x, value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            CPX(Addr::Immediate) => Ok(0xE0),
+            CPX(Addr::ZeroPage) => Ok(0xE4),
+            CPX(Addr::Absolute) => Ok(0xEC),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "CPX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn cpx_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x42;
+        cpu.mem.write_byte(0xFFFC, CPX(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.x, 0x42);
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert_eq!(cpu.flags.v, cpu_start.flags.v);
+        assert!(!cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn cpx_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x10;
+        cpu.mem.write_byte(0xFFFC, CPX(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x20);
+
+        cpu.start();
+
+        // 0x10 - 0x20 = 0xF0
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn cpx_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x80;
+        cpu.mem.write_byte(0xFFFC, CPX(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0x01);
+
+        cpu.start();
+
+        // 0x80 - 0x01 = 0x7F
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod adc;
+pub mod sbc;
+pub mod cmp;
+pub mod cpx;
+pub mod cpy;
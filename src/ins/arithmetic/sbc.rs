@@ -0,0 +1,418 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Subtract with Carry - Subtracts the contents of a memory location from the
+/// accumulator together with the not of the carry bit. If overflow occurs the carry bit
+/// is cleared, this enables multiple byte subtraction to be performed. When the decimal
+/// flag is set the operands are treated as packed BCD values, except on the Ricoh 2A03.
+pub struct SBC(pub Addr);
+
+impl SBC {
+    /// Subtracts `value` and the borrow (the inverted carry flag) from the accumulator,
+    /// updating the C, Z, V and N flags the way an NMOS 6502 does in both binary and
+    /// decimal mode. The 65C02 adjusts the result differently, and sets N and Z from it.
+    pub fn subtract<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) {
+        let acc = cpu.reg.acc;
+        let borrow = !cpu.flags.c as i16;
+        let binary = acc as i16 - value as i16 - borrow;
+        let result = binary as Byte;
+
+        // On the NMOS 6502 every flag is computed from the binary difference, even in
+        // decimal mode.
+        cpu.flags.c = binary >= 0;
+        cpu.flags.z = result == 0;
+        // Overflow if the operands have different signs and the result's sign differs
+        // from the accumulator's.
+        cpu.flags.v = ((acc ^ value) & (acc ^ result) & 0b10000000) > 0;
+        cpu.flags.n = (result & 0b10000000) > 0;
+
+        if cpu.flags.d && cpu.variant.is_cmos() {
+            // Adjust the binary difference for each nibble that borrowed.
+            let lo = (acc & 0x0F) as i16 - (value & 0x0F) as i16 - borrow;
+            let mut decimal = binary;
+            if decimal < 0 {
+                decimal -= 0x60;
+            }
+            if lo < 0 {
+                decimal -= 0x06;
+            }
+            cpu.reg.acc = decimal as Byte;
+            cpu.flags.z = cpu.reg.acc == 0;
+            cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+        } else if cpu.flags.d && cpu.variant.has_decimal_mode() {
+            // Subtract the low nibbles and adjust them if a borrow occurred.
+            let mut lo = (acc & 0x0F) as i16 - (value & 0x0F) as i16 - borrow;
+            if lo < 0 {
+                lo = ((lo - 0x06) & 0x0F) - 0x10;
+            }
+            let mut decimal = (acc & 0xF0) as i16 - (value & 0xF0) as i16 + lo;
+            if decimal < 0 {
+                decimal -= 0x60;
+            }
+            cpu.reg.acc = decimal as Byte;
+        } else {
+            cpu.reg.acc = result;
+        }
+    }
+}
+
+impl Instruction for SBC {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            SBC(Addr::Immediate) => cpu.cycles += 2,
+            // 2B, 3C
+            SBC(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            SBC(Addr::ZeroPageX) => cpu.cycles += 4,
+            // 3B, 4C
+            SBC(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 4C (+1 if page crossed)
+            SBC(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 3B, 4C (+1 if page crossed)
+            SBC(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 2B, 6C
+            SBC(Addr::XIndirect) => cpu.cycles += 6,
+            // 2B, 5C (+1 if page crossed)
+            SBC(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            SBC(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        // The 65C02 takes an extra cycle to fix up the flags in decimal mode.
+        if cpu.flags.d && cpu.variant.is_cmos() {
+            cpu.cycles += 1;
+        }
+        let value = cpu.read_operand(&operand);
+        Self::subtract(cpu, value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SBC(Addr::Immediate) => Ok(0xE9),
+            SBC(Addr::ZeroPage) => Ok(0xE5),
+            SBC(Addr::ZeroPageX) => Ok(0xF5),
+            SBC(Addr::Absolute) => Ok(0xED),
+            SBC(Addr::AbsoluteX) => Ok(0xFD),
+            SBC(Addr::AbsoluteY) => Ok(0xF9),
+            SBC(Addr::XIndirect) => Ok(0xE1),
+            SBC(Addr::IndirectY) => Ok(0xF1),
+            SBC(Addr::ZeroPageIndirect) => Ok(0xF2),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SBC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    /// Reference model of an NMOS SBC, written independently of `SBC::subtract` (it
+    /// follows the formulation used by VICE). Returns `(acc, c, z, v, n)`.
+    fn reference(acc: Byte, value: Byte, carry: bool, decimal: bool) -> (Byte, bool, bool, bool, bool) {
+        let (a, m, borrow) = (acc as u32, value as u32, !carry as u32);
+        let tmp = a.wrapping_sub(m).wrapping_sub(borrow);
+        let c = tmp < 0x100;
+        let z = (tmp & 0xFF) == 0;
+        let v = ((a ^ tmp) & 0x80) > 0 && ((a ^ m) & 0x80) > 0;
+        let n = (tmp & 0x80) > 0;
+        if !decimal {
+            return (tmp as Byte, c, z, v, n);
+        }
+
+        let mut tmp_a = (a & 0x0F).wrapping_sub(m & 0x0F).wrapping_sub(borrow);
+        tmp_a = if (tmp_a & 0x10) > 0 {
+            (tmp_a.wrapping_sub(6) & 0x0F) | (a & 0xF0).wrapping_sub(m & 0xF0).wrapping_sub(0x10)
+        } else {
+            (tmp_a & 0x0F) | (a & 0xF0).wrapping_sub(m & 0xF0)
+        };
+        if (tmp_a & 0x100) > 0 {
+            tmp_a = tmp_a.wrapping_sub(0x60);
+        }
+        (tmp_a as Byte, c, z, v, n)
+    }
+
+    #[test]
+    fn sbc_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x50;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xB0);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0xA0);
+
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert!(cpu.flags.v);
+        assert!(cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sbc_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x05;
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x04);
+
+        cpu.start();
+
+        // 0x05 - 0x04 - 1 (borrow)
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sbc_zero_page_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x46;
+        cpu.reg.x = 0x02;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::ZeroPageX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0x0001, 0x34); // 0xFF + 0x02 % 0xFF = 0x01
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x12);
+        assert!(cpu.flags.c);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sbc_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x46;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x12);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn sbc_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x46;
+        cpu.reg.x = 0x12;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x12);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn sbc_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x46;
+        cpu.reg.y = 0x12;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x12);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn sbc_x_indirect() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x46;
+        cpu.reg.x = 0x04;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::XIndirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02); // 0x02 + 0x04 = 0x06
+        cpu.mem.write_byte(0x0006, 0x00);
+        cpu.mem.write_byte(0x0007, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8000, 0x34);
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x12);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sbc_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x46;
+        cpu.reg.y = 0x04;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0x00);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8004, 0x34); // 0x8000 + 0x0004
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0x12);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sbc_decimal() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.d = true;
+        cpu.flags.c = true;
+        cpu.reg.acc = 0x21;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x34);
+
+        cpu.start();
+
+        // 21 - 34 = -13, i.e. 87 with a borrow in BCD.
+        assert_eq!(cpu.reg.acc, 0x87);
+        assert!(!cpu.flags.c);
+    }
+
+    #[test]
+    fn sbc_decimal_cmos() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Cmos65C02;
+        cpu.flags.d = true;
+        cpu.flags.c = true;
+        cpu.reg.acc = 0x21;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x34);
+
+        cpu.step().unwrap();
+
+        // 21 - 34 = -13, i.e. 87 with a borrow in BCD.
+        assert_eq!(cpu.reg.acc, 0x87);
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(cpu.flags.n);
+        // The 65C02 spends an extra cycle in decimal mode.
+        assert_eq!(cpu.cycles, 3);
+
+        // Z and N are taken from the decimal result, not from the binary one ($06).
+        cpu.pc = 0xFFFC;
+        cpu.flags.c = true;
+        cpu.reg.acc = 0x10;
+        cpu.mem.write_byte(0xFFFD, 0x0A);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+    }
+
+    #[test]
+    fn sbc_ricoh_ignores_decimal() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Ricoh2A03;
+        cpu.flags.d = true;
+        cpu.flags.c = true;
+        cpu.reg.acc = 0x21;
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x34);
+
+        cpu.step().unwrap();
+
+        // The D flag is set but the 2A03 subtracts in binary regardless.
+        assert_eq!(cpu.reg.acc, 0xED);
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.cycles, 2);
+    }
+
+    #[test]
+    fn sbc_exhaustive() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+        cpu.mem.write_byte(0xFFFC, SBC(Addr::Immediate).code().unwrap());
+
+        for decimal in [false, true] {
+            for carry in [false, true] {
+                for acc in 0..=0xFF {
+                    for value in 0..=0xFF {
+                        cpu.pc = 0xFFFC;
+                        cpu.reg.acc = acc;
+                        cpu.flags.c = carry;
+                        cpu.flags.d = decimal;
+                        cpu.mem.write_byte(0xFFFD, value);
+                        cpu.start();
+
+                        let (r_acc, c, z, v, n) = reference(acc, value, carry, decimal);
+                        let ctx = format!("{acc:02X} - {value:02X} - {} (decimal: {decimal})", !carry as u8);
+                        assert_eq!(cpu.reg.acc, r_acc, "A: {ctx}");
+                        assert_eq!(cpu.flags.c, c, "C: {ctx}");
+                        assert_eq!(cpu.flags.z, z, "Z: {ctx}");
+                        assert_eq!(cpu.flags.v, v, "V: {ctx}");
+                        assert_eq!(cpu.flags.n, n, "N: {ctx}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::branch_on_bit;
+use crate::bus::Bus;
+
+/// Branch on Bit Reset - If the given bit (0 to 7) of a byte in the zero page is clear,
+/// adds the relative displacement to the program counter. Only available on the Rockwell
+/// and WDC 65C02.
+pub struct BBR(pub Addr, pub Byte);
+
+impl Instruction for BBR {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 3B, 5C (+1 if branch succeeds, +2 if to a new page)
+            BBR(Addr::ZeroPageRelative, bit @ 0..=7) => branch_on_bit(cpu, *bit, false),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            BBR(Addr::ZeroPageRelative, bit @ 0..=7) => Ok(0x0F | (bit << 4)),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        const MNEMONICS: [&str; 8] = ["BBR0", "BBR1", "BBR2", "BBR3", "BBR4", "BBR5", "BBR6", "BBR7"];
+        MNEMONICS[self.1 as usize]
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn bbr_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, BBR(Addr::ZeroPageRelative, 3).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0xFFFE, 0xF0); // -16
+        cpu.mem.write_byte(0x0042, 0b11110111);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFEF); // 0xFFFF - 0x10
+        assert_eq!(cpu.cycles, 6);
+    }
+
+    #[test]
+    fn bbr_not_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, BBR(Addr::ZeroPageRelative, 3).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0xFFFE, 0xF0); // -16
+        cpu.mem.write_byte(0x0042, 0b00001000);
+
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0xFFFF);
+        assert_eq!(cpu.cycles, 5);
+    }
+
+    #[test]
+    fn bbr_page_crossed() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Rockwell65C02;
+        cpu.reset();
+        cpu.pc = 0x10F0;
+        cpu.mem.write_byte(0x10F0, BBR(Addr::ZeroPageRelative, 0).code().unwrap());
+        cpu.mem.write_byte(0x10F1, 0x42);
+        cpu.mem.write_byte(0x10F2, 0x10);
+
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0x1103); // 0x10F3 + 0x10
+        assert_eq!(cpu.cycles, 7);
+    }
+}
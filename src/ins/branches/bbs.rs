@@ -0,0 +1,78 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::branch_on_bit;
+use crate::bus::Bus;
+
+/// Branch on Bit Set - If the given bit (0 to 7) of a byte in the zero page is set, adds
+/// the relative displacement to the program counter. Only available on the Rockwell and
+/// WDC 65C02.
+pub struct BBS(pub Addr, pub Byte);
+
+impl Instruction for BBS {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 3B, 5C (+1 if branch succeeds, +2 if to a new page)
+            BBS(Addr::ZeroPageRelative, bit @ 0..=7) => branch_on_bit(cpu, *bit, true),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            BBS(Addr::ZeroPageRelative, bit @ 0..=7) => Ok(0x8F | (bit << 4)),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        const MNEMONICS: [&str; 8] = ["BBS0", "BBS1", "BBS2", "BBS3", "BBS4", "BBS5", "BBS6", "BBS7"];
+        MNEMONICS[self.1 as usize]
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn bbs_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, BBS(Addr::ZeroPageRelative, 7).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0xFFFE, 0xF0); // -16
+        cpu.mem.write_byte(0x0042, 0b10000000);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFEF); // 0xFFFF - 0x10
+        assert_eq!(cpu.cycles, 6);
+    }
+
+    #[test]
+    fn bbs_not_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, BBS(Addr::ZeroPageRelative, 7).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0xFFFE, 0xF0); // -16
+        cpu.mem.write_byte(0x0042, 0b01111111);
+
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0xFFFF);
+        assert_eq!(cpu.cycles, 5);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::branch;
+use crate::bus::Bus;
+
+/// Branch if Carry Set - If the carry flag is set then add the relative displacement to the program
+/// counter to cause a branch to a new location.
+pub struct BCS(pub Addr);
+
+impl Instruction for BCS {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 2B, 2C (+1 if branch succeeds, +2 if to a new page)
+            BCS(Addr::Relative) => branch(cpu, cpu.flags.c),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            BCS(Addr::Relative) => Ok(0xB0),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "BCS"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn bcs_relative_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, BCS(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFEE); // 0xFFFE - 0x10
+        assert_eq!(cpu.cycles, 3);
+    }
+
+    #[test]
+    fn bcs_relative_not_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, BCS(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFFE);
+        assert_eq!(cpu.cycles, 2);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::branch;
+use crate::bus::Bus;
+
+/// Branch if Equal - If the zero flag is set then add the relative displacement to the program
+/// counter to cause a branch to a new location.
+pub struct BEQ(pub Addr);
+
+impl Instruction for BEQ {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 2B, 2C (+1 if branch succeeds, +2 if to a new page)
+            BEQ(Addr::Relative) => branch(cpu, cpu.flags.z),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            BEQ(Addr::Relative) => Ok(0xF0),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "BEQ"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn beq_relative_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.z = true;
+        cpu.mem.write_byte(0xFFFC, BEQ(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFEE); // 0xFFFE - 0x10
+        assert_eq!(cpu.cycles, 3);
+    }
+
+    #[test]
+    fn beq_relative_not_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.z = false;
+        cpu.mem.write_byte(0xFFFC, BEQ(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFFE);
+        assert_eq!(cpu.cycles, 2);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::branch;
+use crate::bus::Bus;
+
+/// Branch if Minus - If the negative flag is set then add the relative displacement to the program
+/// counter to cause a branch to a new location.
+pub struct BMI(pub Addr);
+
+impl Instruction for BMI {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 2B, 2C (+1 if branch succeeds, +2 if to a new page)
+            BMI(Addr::Relative) => branch(cpu, cpu.flags.n),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            BMI(Addr::Relative) => Ok(0x30),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "BMI"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn bmi_relative_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.n = true;
+        cpu.mem.write_byte(0xFFFC, BMI(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFEE); // 0xFFFE - 0x10
+        assert_eq!(cpu.cycles, 3);
+    }
+
+    #[test]
+    fn bmi_relative_not_taken() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.n = false;
+        cpu.mem.write_byte(0xFFFC, BMI(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFFE);
+        assert_eq!(cpu.cycles, 2);
+    }
+}
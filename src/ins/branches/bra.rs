@@ -0,0 +1,57 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use super::branch;
+use crate::bus::Bus;
+
+/// Branch Always - Adds the relative displacement to the program counter unconditionally.
+/// Only available on the 65C02.
+pub struct BRA(pub Addr);
+
+impl Instruction for BRA {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 2B, 3C (+1 if to a new page)
+            BRA(Addr::Relative) => branch(cpu, true),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            BRA(Addr::Relative) => Ok(0x80),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "BRA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn bra_relative() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, BRA(Addr::Relative).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0); // -16
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFEE); // 0xFFFE - 0x10
+        assert_eq!(cpu.cycles, 3);
+    }
+}
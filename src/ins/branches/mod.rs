@@ -0,0 +1,50 @@
+use crate::cpu::CPU;
+use crate::bus::Bus;
+use crate::mem::Addr;
+use crate::Byte;
+
+pub mod bbr;
+pub mod bbs;
+pub mod bcc;
+pub mod bcs;
+pub mod beq;
+pub mod bmi;
+pub mod bne;
+pub mod bpl;
+pub mod bra;
+pub mod bvc;
+pub mod bvs;
+
+/// Shared behaviour of all conditional branches. The operand is a signed 8-bit offset
+/// relative to the address of the next instruction (i.e. the one after the operand). A
+/// taken branch costs an extra cycle, and another one if the target is on a new page.
+pub fn branch<B: Bus + ?Sized>(cpu: &mut CPU<B>, condition: bool) {
+    let operand = cpu.resolve(&Addr::Relative);
+    cpu.cycles += 2;
+
+    match operand.address {
+        Some(target) if condition => {
+            cpu.cycles += 1 + operand.page_crossed as u32;
+            cpu.pc = target;
+        },
+        _ => cpu.pc = cpu.pc.wrapping_add(operand.len),
+    }
+}
+
+/// Shared behaviour of BBR and BBS. The `bit` of the zero page byte addressed by the first
+/// operand is tested, and the branch is taken if it is `set` (or clear when `set` is
+/// false). The second operand is the relative offset, and the penalties of a taken branch
+/// are the same as for conditional branches.
+pub fn branch_on_bit<B: Bus + ?Sized>(cpu: &mut CPU<B>, bit: Byte, set: bool) {
+    let operand = cpu.resolve(&Addr::ZeroPageRelative);
+    let value = cpu.read_operand(&operand);
+    let (target, page_crossed) = cpu.branch_target(cpu.pc.wrapping_add(2), cpu.pc.wrapping_add(3));
+    cpu.cycles += 5;
+
+    if (value >> bit) & 1 == set as Byte {
+        cpu.cycles += 1 + page_crossed as u32;
+        cpu.pc = target;
+    } else {
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::shifts::lsr::LSR;
+
+/// AND then Logical Shift Right (undocumented) - ANDs the immediate value into the
+/// accumulator like AND, then shifts the accumulator one bit right like LSR.
+pub struct ALR(pub Addr);
+
+impl Instruction for ALR {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            ALR(Addr::Immediate) => cpu.cycles += 2,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.reg.acc & cpu.read_operand(&operand);
+        cpu.reg.acc = LSR::shift(cpu, value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ALR(Addr::Immediate) => Ok(0x4B),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ALR"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn alr_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0b11000011;
+        cpu.mem.write_byte(0xFFFC, ALR(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0b10000001);
+
+        ALR(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0b01000000);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
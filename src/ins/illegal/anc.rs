@@ -0,0 +1,72 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// AND with Carry (undocumented) - ANDs the immediate value into the accumulator like AND,
+/// then copies the resulting negative flag into the carry flag. Available as both $0B and
+/// $2B.
+pub struct ANC(pub Addr);
+
+impl ANC {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set zero flag if acc = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set negative and carry flags if bit 7 of acc is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+        cpu.flags.c = cpu.flags.n;
+    }
+}
+
+impl Instruction for ANC {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            ANC(Addr::Immediate) => cpu.cycles += 2,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.reg.acc &= cpu.read_operand(&operand);
+        Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ANC(Addr::Immediate) => Ok(0x0B),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ANC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn anc_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0b11000011;
+        cpu.mem.write_byte(0xFFFC, ANC(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0b10000001);
+
+        ANC(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0b10000001);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// AND X then AND (undocumented, unstable) - ORs the accumulator with a chip dependent
+/// "magic" constant, then ANDs it with the X register and the immediate value. Also known
+/// as XAA. See [`crate::cpu::UnstableOpcodePolicy`].
+pub struct ANE(pub Addr);
+
+impl ANE {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set zero flag if acc = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set negative flag if bit 7 of acc is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for ANE {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            ANE(Addr::Immediate) => cpu.cycles += 2,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand);
+        cpu.reg.acc = (cpu.reg.acc | cpu.unstable_opcodes.magic()) & cpu.reg.x & value;
+        Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ANE(Addr::Immediate) => Ok(0x8B),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ANE"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, UnstableOpcodePolicy};
+
+    #[test]
+    fn ane_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.unstable_opcodes = UnstableOpcodePolicy::Emulate { magic: 0xEE };
+        cpu.reg.acc = 0x01;
+        cpu.reg.x = 0xF3;
+        cpu.mem.write_byte(0xFFFC, ANE(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x7F);
+
+        ANE(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        // (0x01 | 0xEE) & 0xF3 & 0x7F
+        assert_eq!(cpu.reg.acc, 0x63);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// AND then Rotate Right (undocumented) - ANDs the immediate value into the accumulator,
+/// then rotates the accumulator one bit right through the carry. The carry and overflow
+/// flags come from bits 6 and 5 of the result rather than the rotation, and in decimal
+/// mode (which the Ricoh 2A03 lacks) the result is adjusted like ADC's BCD correction.
+pub struct ARR(pub Addr);
+
+impl ARR {
+    /// ANDs `value` into the accumulator and rotates it right, updating the C, Z, V and N
+    /// flags the way an NMOS 6502 does in both binary and decimal mode.
+    pub fn and_rotate<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) {
+        let and = cpu.reg.acc & value;
+        let mut result = (and >> 1) | ((cpu.flags.c as Byte) << 7);
+
+        // N and Z reflect the rotated value, even in decimal mode.
+        cpu.flags.n = cpu.flags.c;
+        cpu.flags.z = result == 0;
+
+        if cpu.flags.d && cpu.variant.has_decimal_mode() {
+            // Overflow if bit 6 changed during the rotation.
+            cpu.flags.v = ((and ^ result) & 0b01000000) > 0;
+            // Adjust each nibble of the AND result which is not a valid BCD digit.
+            let (lo, hi) = (and & 0x0F, and >> 4);
+            if lo + (lo & 0x01) > 0x05 {
+                result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+            }
+            cpu.flags.c = hi + (hi & 0x01) > 0x05;
+            if cpu.flags.c {
+                result = result.wrapping_add(0x60);
+            }
+        } else {
+            // Set carry flag to bit 6 of the result, and overflow to bit 6 XOR bit 5.
+            cpu.flags.c = (result & 0b01000000) > 0;
+            cpu.flags.v = (((result >> 6) ^ (result >> 5)) & 0x01) > 0;
+        }
+        cpu.reg.acc = result;
+    }
+}
+
+impl Instruction for ARR {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            ARR(Addr::Immediate) => cpu.cycles += 2,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand);
+        Self::and_rotate(cpu, value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ARR(Addr::Immediate) => Ok(0x6B),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ARR"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn arr_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, ARR(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xC1);
+
+        ARR(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        // 0xC1 rotated right with the carry set.
+        assert_eq!(cpu.reg.acc, 0xE0);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn arr_overflow() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x80;
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, ARR(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+
+        ARR(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x40);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.v);
+        assert!(!cpu.flags.n);
+    }
+
+    #[test]
+    fn arr_decimal() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.flags.c = false;
+        cpu.flags.d = true;
+        cpu.mem.write_byte(0xFFFC, ARR(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x9B);
+
+        ARR(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        // 0x9B >> 1 = 0x4D, then both nibbles of 0x9B are adjusted.
+        assert_eq!(cpu.reg.acc, 0xA3);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.v);
+        assert!(!cpu.flags.n);
+    }
+}
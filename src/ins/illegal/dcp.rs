@@ -0,0 +1,111 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::arithmetic::cmp::CMP;
+
+/// Decrement then Compare (undocumented) - Decrements the memory contents by one like DEC,
+/// then compares the accumulator with the result like CMP.
+pub struct DCP(pub Addr);
+
+impl Instruction for DCP {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            DCP(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            DCP(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            DCP(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C
+            DCP(Addr::AbsoluteX) => cpu.cycles += 7,
+            // 3B, 7C
+            DCP(Addr::AbsoluteY) => cpu.cycles += 7,
+            // 2B, 8C
+            DCP(Addr::XIndirect) => cpu.cycles += 8,
+            // 2B, 8C
+            DCP(Addr::IndirectY) => cpu.cycles += 8,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = value.wrapping_sub(1);
+            CMP::set_flags(cpu, cpu.reg.acc, result);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            DCP(Addr::ZeroPage) => Ok(0xC7),
+            DCP(Addr::ZeroPageX) => Ok(0xD7),
+            DCP(Addr::Absolute) => Ok(0xCF),
+            DCP(Addr::AbsoluteX) => Ok(0xDF),
+            DCP(Addr::AbsoluteY) => Ok(0xDB),
+            DCP(Addr::XIndirect) => Ok(0xC3),
+            DCP(Addr::IndirectY) => Ok(0xD3),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "DCP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn dcp_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x41;
+        cpu.mem.write_byte(0xFFFC, DCP(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x42);
+
+        DCP(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0x41);
+        assert_eq!(cpu.reg.acc, 0x41);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn dcp_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x41;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, DCP(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0xF8);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x80F8 (LE)
+        cpu.mem.write_byte(0x8108, 0x42); // 0x80F8 + 0x0010
+
+        DCP(Addr::IndirectY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x8108), 0x41);
+        assert_eq!(cpu.reg.acc, 0x41);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        // No extra cycle for the page crossing, the write always takes it.
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
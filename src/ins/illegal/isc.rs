@@ -0,0 +1,115 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::arithmetic::sbc::SBC;
+
+/// Increment then Subtract with Carry (undocumented) - Increments the memory contents by
+/// one like INC, then subtracts the result from the accumulator like SBC.
+pub struct ISC(pub Addr);
+
+impl Instruction for ISC {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            ISC(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            ISC(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            ISC(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C
+            ISC(Addr::AbsoluteX) => cpu.cycles += 7,
+            // 3B, 7C
+            ISC(Addr::AbsoluteY) => cpu.cycles += 7,
+            // 2B, 8C
+            ISC(Addr::XIndirect) => cpu.cycles += 8,
+            // 2B, 8C
+            ISC(Addr::IndirectY) => cpu.cycles += 8,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = value.wrapping_add(1);
+            SBC::subtract(cpu, result);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ISC(Addr::ZeroPage) => Ok(0xE7),
+            ISC(Addr::ZeroPageX) => Ok(0xF7),
+            ISC(Addr::Absolute) => Ok(0xEF),
+            ISC(Addr::AbsoluteX) => Ok(0xFF),
+            ISC(Addr::AbsoluteY) => Ok(0xFB),
+            ISC(Addr::XIndirect) => Ok(0xE3),
+            ISC(Addr::IndirectY) => Ok(0xF3),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ISC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn isc_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x50;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, ISC(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x0F);
+
+        ISC(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0x10);
+        assert_eq!(cpu.reg.acc, 0x40);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn isc_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x50;
+        cpu.flags.c = true;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, ISC(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0xF8);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x80F8 (LE)
+        cpu.mem.write_byte(0x8108, 0x0F); // 0x80F8 + 0x0010
+
+        ISC(Addr::IndirectY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x8108), 0x10);
+        assert_eq!(cpu.reg.acc, 0x40);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(!cpu.flags.n);
+        // No extra cycle for the page crossing, the write always takes it.
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
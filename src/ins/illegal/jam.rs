@@ -0,0 +1,56 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Jam (undocumented) - Halts the CPU, which stops fetching instructions and no longer
+/// responds to interrupts until it is reset. Also known as KIL. Available as $02, $12,
+/// $22, $32, $42, $52, $62, $72, $92, $B2, $D2 and $F2.
+pub struct JAM(pub Addr);
+
+impl Instruction for JAM {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, the program counter is left on the opcode
+            JAM(Addr::Implicit) => cpu.jam(),
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            JAM(Addr::Implicit) => Ok(0x02),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "JAM"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn jam_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, JAM(Addr::Implicit).code().unwrap());
+
+        JAM(Addr::Implicit).execute(&mut cpu).unwrap();
+
+        assert!(cpu.jammed());
+        assert_eq!(cpu.pc, 0xFFFC);
+
+        cpu.reset();
+        assert!(!cpu.jammed());
+    }
+}
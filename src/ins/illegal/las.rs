@@ -0,0 +1,79 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Load A, X and S (undocumented) - ANDs a byte of memory with the stack pointer and
+/// loads the result into the accumulator, the X register and the stack pointer, setting
+/// the zero and negative flags as appropriate.
+pub struct LAS(pub Addr);
+
+impl LAS {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set if A = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set if bit 7 of A is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for LAS {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 3B, 4C (+1 if page crossed)
+            LAS(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand) & cpu.sp;
+        cpu.reg.acc = value;
+        cpu.reg.x = value;
+        cpu.sp = value;
+        Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            LAS(Addr::AbsoluteY) => Ok(0xBB),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "LAS"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn las_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.sp = 0xF3;
+        cpu.reg.y = 0x12;
+        cpu.mem.write_byte(0xFFFC, LAS(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x9E);
+
+        LAS(Addr::AbsoluteY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x92);
+        assert_eq!(cpu.reg.x, 0x92);
+        assert_eq!(cpu.sp, 0x92);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.cycles, 4);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
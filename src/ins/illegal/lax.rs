@@ -0,0 +1,108 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Load Accumulator and X (undocumented) - Loads a byte of memory into both the
+/// accumulator and the X register, setting the zero and negative flags as appropriate.
+pub struct LAX(pub Addr);
+
+impl LAX {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set if A = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set if bit 7 of A is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for LAX {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 3C
+            LAX(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            LAX(Addr::ZeroPageY) => cpu.cycles += 4,
+            // 3B, 4C
+            LAX(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 4C (+1 if page crossed)
+            LAX(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
+            // 2B, 6C
+            LAX(Addr::XIndirect) => cpu.cycles += 6,
+            // 2B, 5C (+1 if page crossed)
+            LAX(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.reg.acc = cpu.read_operand(&operand);
+        cpu.reg.x = cpu.reg.acc;
+        Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            LAX(Addr::ZeroPage) => Ok(0xA7),
+            LAX(Addr::ZeroPageY) => Ok(0xB7),
+            LAX(Addr::Absolute) => Ok(0xAF),
+            LAX(Addr::AbsoluteY) => Ok(0xBF),
+            LAX(Addr::XIndirect) => Ok(0xA3),
+            LAX(Addr::IndirectY) => Ok(0xB3),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "LAX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn lax_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, LAX(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x84);
+
+        LAX(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x84);
+        assert_eq!(cpu.reg.x, 0x84);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.cycles, 3);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn lax_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.y = 0x20;
+        cpu.mem.write_byte(0xFFFC, LAX(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x44F0 (LE)
+        cpu.mem.write_byte(0x4510, 0x00); // 0x44F0 + 0x0020
+
+        LAX(Addr::AbsoluteY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert_eq!(cpu.reg.x, 0x00);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// AND then Load Accumulator and X (undocumented, unstable) - ORs the accumulator with a
+/// chip dependent "magic" constant, ANDs it with the immediate value and loads the result
+/// into both the accumulator and the X register. See
+/// [`crate::cpu::UnstableOpcodePolicy`].
+pub struct LXA(pub Addr);
+
+impl LXA {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set if A = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set if bit 7 of A is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for LXA {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            LXA(Addr::Immediate) => cpu.cycles += 2,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand);
+        cpu.reg.acc = (cpu.reg.acc | cpu.unstable_opcodes.magic()) & value;
+        cpu.reg.x = cpu.reg.acc;
+        Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            LXA(Addr::Immediate) => Ok(0xAB),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "LXA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, UnstableOpcodePolicy};
+
+    #[test]
+    fn lxa_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.unstable_opcodes = UnstableOpcodePolicy::Emulate { magic: 0xFF };
+        cpu.reg.acc = 0x00;
+        cpu.mem.write_byte(0xFFFC, LXA(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x85);
+
+        LXA(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x85);
+        assert_eq!(cpu.reg.x, 0x85);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::cpu::{CPU, Operand};
+use crate::bus::Bus;
+use crate::{Byte, Word};
+
+pub mod alr;
+pub mod anc;
+pub mod ane;
+pub mod arr;
+pub mod dcp;
+pub mod isc;
+pub mod jam;
+pub mod las;
+pub mod lax;
+pub mod lxa;
+pub mod rla;
+pub mod rra;
+pub mod sax;
+pub mod sbx;
+pub mod sha;
+pub mod shx;
+pub mod shy;
+pub mod slo;
+pub mod sre;
+pub mod tas;
+
+/// Shared behaviour of SHA, SHX, SHY and TAS, which store `value` ANDed with the high byte
+/// of the base address plus one. When indexing crosses a page boundary the high byte of
+/// the effective address is replaced by the stored value, like on most NMOS chips.
+pub fn store_high<B: Bus + ?Sized>(cpu: &mut CPU<B>, operand: &Operand, value: Byte) {
+    let Some(address) = operand.address else {
+        return;
+    };
+    let base_hi = ((address >> 8) as Byte).wrapping_sub(operand.page_crossed as Byte);
+    let result = value & base_hi.wrapping_add(1);
+    let address = if operand.page_crossed {
+        ((result as Word) << 8) | (address & 0x00FF)
+    } else {
+        address
+    };
+    cpu.write_byte(address, result);
+}
@@ -0,0 +1,123 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::shifts::rol::ROL;
+
+/// Rotate Left then AND (undocumented) - Rotates the memory contents one bit left through
+/// the carry like ROL, then ANDs the result into the accumulator like AND.
+pub struct RLA(pub Addr);
+
+impl RLA {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set zero flag if acc = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set negative flag if bit 7 of acc is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for RLA {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            RLA(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            RLA(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            RLA(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C
+            RLA(Addr::AbsoluteX) => cpu.cycles += 7,
+            // 3B, 7C
+            RLA(Addr::AbsoluteY) => cpu.cycles += 7,
+            // 2B, 8C
+            RLA(Addr::XIndirect) => cpu.cycles += 8,
+            // 2B, 8C
+            RLA(Addr::IndirectY) => cpu.cycles += 8,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = ROL::rotate(cpu, value);
+            cpu.reg.acc &= result;
+            Self::set_flags(cpu);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            RLA(Addr::ZeroPage) => Ok(0x27),
+            RLA(Addr::ZeroPageX) => Ok(0x37),
+            RLA(Addr::Absolute) => Ok(0x2F),
+            RLA(Addr::AbsoluteX) => Ok(0x3F),
+            RLA(Addr::AbsoluteY) => Ok(0x3B),
+            RLA(Addr::XIndirect) => Ok(0x23),
+            RLA(Addr::IndirectY) => Ok(0x33),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "RLA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn rla_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xF0;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, RLA(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0b11000000);
+
+        RLA(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0b10000001);
+        assert_eq!(cpu.reg.acc, 0b10000000);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn rla_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xF0;
+        cpu.flags.c = true;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, RLA(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0xF8);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x80F8 (LE)
+        cpu.mem.write_byte(0x8108, 0b11000000); // 0x80F8 + 0x0010
+
+        RLA(Addr::IndirectY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x8108), 0b10000001);
+        assert_eq!(cpu.reg.acc, 0b10000000);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        // No extra cycle for the page crossing, the write always takes it.
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
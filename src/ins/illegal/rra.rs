@@ -0,0 +1,118 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::{arithmetic::adc::ADC, shifts::ror::ROR};
+
+/// Rotate Right then Add with Carry (undocumented) - Rotates the memory contents one bit
+/// right through the carry like ROR, then adds the result to the accumulator like ADC,
+/// using the carry shifted out of the rotation.
+pub struct RRA(pub Addr);
+
+impl Instruction for RRA {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            RRA(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            RRA(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            RRA(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C
+            RRA(Addr::AbsoluteX) => cpu.cycles += 7,
+            // 3B, 7C
+            RRA(Addr::AbsoluteY) => cpu.cycles += 7,
+            // 2B, 8C
+            RRA(Addr::XIndirect) => cpu.cycles += 8,
+            // 2B, 8C
+            RRA(Addr::IndirectY) => cpu.cycles += 8,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = ROR::rotate(cpu, value);
+            ADC::add(cpu, result);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            RRA(Addr::ZeroPage) => Ok(0x67),
+            RRA(Addr::ZeroPageX) => Ok(0x77),
+            RRA(Addr::Absolute) => Ok(0x6F),
+            RRA(Addr::AbsoluteX) => Ok(0x7F),
+            RRA(Addr::AbsoluteY) => Ok(0x7B),
+            RRA(Addr::XIndirect) => Ok(0x63),
+            RRA(Addr::IndirectY) => Ok(0x73),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "RRA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn rra_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x10;
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, RRA(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x05);
+
+        RRA(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0x02);
+        // 0x10 + 0x02 + 1 (carry out of the rotation)
+        assert_eq!(cpu.reg.acc, 0x13);
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn rra_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x10;
+        cpu.flags.c = false;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, RRA(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0xF8);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x80F8 (LE)
+        cpu.mem.write_byte(0x8108, 0x05); // 0x80F8 + 0x0010
+
+        RRA(Addr::IndirectY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x8108), 0x02);
+        // 0x10 + 0x02 + 1 (carry out of the rotation)
+        assert_eq!(cpu.reg.acc, 0x13);
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.v);
+        assert!(!cpu.flags.n);
+        // No extra cycle for the page crossing, the write always takes it.
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Store A AND X (undocumented) - Stores the bitwise AND of the accumulator and the X
+/// register into memory. No flags are affected.
+pub struct SAX(pub Addr);
+
+impl Instruction for SAX {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 3C
+            SAX(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            SAX(Addr::ZeroPageY) => cpu.cycles += 4,
+            // 3B, 4C
+            SAX(Addr::Absolute) => cpu.cycles += 4,
+            // 2B, 6C
+            SAX(Addr::XIndirect) => cpu.cycles += 6,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.write_operand(&operand, cpu.reg.acc & cpu.reg.x);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SAX(Addr::ZeroPage) => Ok(0x87),
+            SAX(Addr::ZeroPageY) => Ok(0x97),
+            SAX(Addr::Absolute) => Ok(0x8F),
+            SAX(Addr::XIndirect) => Ok(0x83),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SAX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn sax_zero_page_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0b11110000;
+        cpu.reg.x = 0b10101010;
+        cpu.reg.y = 0x02;
+        cpu.mem.write_byte(0xFFFC, SAX(Addr::ZeroPageY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+
+        let cpu_start = cpu.clone();
+        SAX(Addr::ZeroPageY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0001), 0b10100000); // 0xFF + 0x02 % 0xFF = 0x01
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.cycles, 4);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
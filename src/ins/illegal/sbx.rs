@@ -0,0 +1,87 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::arithmetic::cmp::CMP;
+
+/// Subtract from A AND X (undocumented) - Stores the bitwise AND of the accumulator and
+/// the X register, minus the immediate value, into the X register. The flags are set like
+/// CMP, so the carry flag is not used as a borrow and decimal mode has no effect.
+pub struct SBX(pub Addr);
+
+impl Instruction for SBX {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 2C
+            SBX(Addr::Immediate) => cpu.cycles += 2,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        let value = cpu.read_operand(&operand);
+        let reg = cpu.reg.acc & cpu.reg.x;
+        CMP::set_flags(cpu, reg, value);
+        cpu.reg.x = reg.wrapping_sub(value);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SBX(Addr::Immediate) => Ok(0xCB),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SBX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn sbx_immediate() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x3F;
+        cpu.reg.x = 0xF5;
+        cpu.flags.c = false;
+        cpu.flags.d = true;
+        cpu.mem.write_byte(0xFFFC, SBX(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x06);
+
+        SBX(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        // (0x3F & 0xF5) - 0x06, ignoring both the carry and decimal flags.
+        assert_eq!(cpu.reg.x, 0x2F);
+        assert_eq!(cpu.reg.acc, 0x3F);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sbx_borrow() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x0F;
+        cpu.reg.x = 0x03;
+        cpu.mem.write_byte(0xFFFC, SBX(Addr::Immediate).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x04);
+
+        SBX(Addr::Immediate).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.reg.x, 0xFF);
+        assert!(!cpu.flags.c);
+        assert!(cpu.flags.n);
+    }
+}
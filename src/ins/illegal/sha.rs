@@ -0,0 +1,87 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use super::store_high;
+
+/// AND X then Store High (undocumented, unstable) - Stores the bitwise AND of the
+/// accumulator, the X register and the high byte of the base address plus one. Also known
+/// as AHX. See [`store_high`] for what happens when indexing crosses a page.
+pub struct SHA(pub Addr);
+
+impl Instruction for SHA {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 3B, 5C
+            SHA(Addr::AbsoluteY) => cpu.cycles += 5,
+            // 2B, 6C
+            SHA(Addr::IndirectY) => cpu.cycles += 6,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        store_high(cpu, &operand, cpu.reg.acc & cpu.reg.x);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SHA(Addr::AbsoluteY) => Ok(0x9F),
+            SHA(Addr::IndirectY) => Ok(0x93),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SHA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn sha_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.reg.x = 0x7E;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, SHA(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+
+        SHA(Addr::AbsoluteY).execute(&mut cpu).unwrap();
+
+        // 0x7E & (0x44 + 1)
+        assert_eq!(cpu.mem.read_byte(0x4410), 0x44);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn sha_page_crossed() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.reg.x = 0x7E;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, SHA(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF8);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x44F8 (LE)
+
+        SHA(Addr::AbsoluteY).execute(&mut cpu).unwrap();
+
+        // The stored value also replaces the high byte of 0x44F8 + 0x0010.
+        assert_eq!(cpu.mem.read_byte(0x4408), 0x44);
+        assert_eq!(cpu.mem.read_byte(0x4508), 0x00);
+        assert_eq!(cpu.cycles, 5);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use super::store_high;
+
+/// Store Y High (undocumented, unstable) - Stores the bitwise AND of the Y register and
+/// the high byte of the base address plus one. See [`store_high`] for what happens when indexing crosses a page.
+pub struct SHY(pub Addr);
+
+impl Instruction for SHY {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 3B, 5C
+            SHY(Addr::AbsoluteX) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        store_high(cpu, &operand, cpu.reg.y);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SHY(Addr::AbsoluteX) => Ok(0x9C),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SHY"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn shy_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.y = 0x7E;
+        cpu.reg.x = 0x10;
+        cpu.mem.write_byte(0xFFFC, SHY(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+
+        SHY(Addr::AbsoluteX).execute(&mut cpu).unwrap();
+
+        // 0x7E & (0x44 + 1)
+        assert_eq!(cpu.mem.read_byte(0x4410), 0x44);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn shy_page_crossed() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.y = 0x7E;
+        cpu.reg.x = 0x10;
+        cpu.mem.write_byte(0xFFFC, SHY(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF8);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x44F8 (LE)
+
+        SHY(Addr::AbsoluteX).execute(&mut cpu).unwrap();
+
+        // The stored value also replaces the high byte of 0x44F8 + 0x0010.
+        assert_eq!(cpu.mem.read_byte(0x4408), 0x44);
+        assert_eq!(cpu.mem.read_byte(0x4508), 0x00);
+        assert_eq!(cpu.cycles, 5);
+    }
+}
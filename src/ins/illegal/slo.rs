@@ -0,0 +1,121 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::shifts::asl::ASL;
+
+/// Shift Left then OR (undocumented) - Shifts the memory contents one bit left like ASL,
+/// then ORs the result into the accumulator like ORA.
+pub struct SLO(pub Addr);
+
+impl SLO {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set zero flag if acc = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set negative flag if bit 7 of acc is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for SLO {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            SLO(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            SLO(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            SLO(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C
+            SLO(Addr::AbsoluteX) => cpu.cycles += 7,
+            // 3B, 7C
+            SLO(Addr::AbsoluteY) => cpu.cycles += 7,
+            // 2B, 8C
+            SLO(Addr::XIndirect) => cpu.cycles += 8,
+            // 2B, 8C
+            SLO(Addr::IndirectY) => cpu.cycles += 8,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = ASL::shift(cpu, value);
+            cpu.reg.acc |= result;
+            Self::set_flags(cpu);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SLO(Addr::ZeroPage) => Ok(0x07),
+            SLO(Addr::ZeroPageX) => Ok(0x17),
+            SLO(Addr::Absolute) => Ok(0x0F),
+            SLO(Addr::AbsoluteX) => Ok(0x1F),
+            SLO(Addr::AbsoluteY) => Ok(0x1B),
+            SLO(Addr::XIndirect) => Ok(0x03),
+            SLO(Addr::IndirectY) => Ok(0x13),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SLO"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn slo_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x01;
+        cpu.mem.write_byte(0xFFFC, SLO(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0b10000010);
+
+        SLO(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0b00000100);
+        assert_eq!(cpu.reg.acc, 0b00000101);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn slo_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x01;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, SLO(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0xF8);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x80F8 (LE)
+        cpu.mem.write_byte(0x8108, 0b10000010); // 0x80F8 + 0x0010
+
+        SLO(Addr::IndirectY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x8108), 0b00000100);
+        assert_eq!(cpu.reg.acc, 0b00000101);
+        assert!(cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        // No extra cycle for the page crossing, the write always takes it.
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use crate::ins::shifts::lsr::LSR;
+
+/// Shift Right then Exclusive OR (undocumented) - Shifts the memory contents one bit right
+/// like LSR, then exclusive ORs the result into the accumulator like EOR.
+pub struct SRE(pub Addr);
+
+impl SRE {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set zero flag if acc = 0
+        cpu.flags.z = cpu.reg.acc == 0;
+        // Set negative flag if bit 7 of acc is set
+        cpu.flags.n = (cpu.reg.acc & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for SRE {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            SRE(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            SRE(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            SRE(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C
+            SRE(Addr::AbsoluteX) => cpu.cycles += 7,
+            // 3B, 7C
+            SRE(Addr::AbsoluteY) => cpu.cycles += 7,
+            // 2B, 8C
+            SRE(Addr::XIndirect) => cpu.cycles += 8,
+            // 2B, 8C
+            SRE(Addr::IndirectY) => cpu.cycles += 8,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = LSR::shift(cpu, value);
+            cpu.reg.acc ^= result;
+            Self::set_flags(cpu);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SRE(Addr::ZeroPage) => Ok(0x47),
+            SRE(Addr::ZeroPageX) => Ok(0x57),
+            SRE(Addr::Absolute) => Ok(0x4F),
+            SRE(Addr::AbsoluteX) => Ok(0x5F),
+            SRE(Addr::AbsoluteY) => Ok(0x5B),
+            SRE(Addr::XIndirect) => Ok(0x43),
+            SRE(Addr::IndirectY) => Ok(0x53),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "SRE"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn sre_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x21;
+        cpu.mem.write_byte(0xFFFC, SRE(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0b01000011);
+
+        SRE(Addr::ZeroPage).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0b00100001);
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn sre_indirect_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0x21;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, SRE(Addr::IndirectY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0xF8);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x80F8 (LE)
+        cpu.mem.write_byte(0x8108, 0b01000011); // 0x80F8 + 0x0010
+
+        SRE(Addr::IndirectY).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.mem.read_byte(0x8108), 0b00100001);
+        assert_eq!(cpu.reg.acc, 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        // No extra cycle for the page crossing, the write always takes it.
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+}
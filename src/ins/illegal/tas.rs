@@ -0,0 +1,86 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+use super::store_high;
+
+/// Transfer A AND X to S then Store High (undocumented, unstable) - Loads the bitwise AND
+/// of the accumulator and the X register into the stack pointer, then stores it ANDed with
+/// the high byte of the base address plus one. Also known as SHS. See [`store_high`] for what happens when indexing crosses a page.
+pub struct TAS(pub Addr);
+
+impl Instruction for TAS {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 3B, 5C
+            TAS(Addr::AbsoluteY) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.sp = cpu.reg.acc & cpu.reg.x;
+        store_high(cpu, &operand, cpu.sp);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            TAS(Addr::AbsoluteY) => Ok(0x9B),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "TAS"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn tas_absolute_y() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.reg.x = 0x7E;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, TAS(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+
+        TAS(Addr::AbsoluteY).execute(&mut cpu).unwrap();
+
+        // 0x7E & (0x44 + 1)
+        assert_eq!(cpu.mem.read_byte(0x4410), 0x44);
+        assert_eq!(cpu.sp, 0x7E);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn tas_page_crossed() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0xFF;
+        cpu.reg.x = 0x7E;
+        cpu.reg.y = 0x10;
+        cpu.mem.write_byte(0xFFFC, TAS(Addr::AbsoluteY).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF8);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x44F8 (LE)
+
+        TAS(Addr::AbsoluteY).execute(&mut cpu).unwrap();
+
+        // The stored value also replaces the high byte of 0x44F8 + 0x0010.
+        assert_eq!(cpu.mem.read_byte(0x4408), 0x44);
+        assert_eq!(cpu.mem.read_byte(0x4508), 0x00);
+        assert_eq!(cpu.cycles, 5);
+    }
+}
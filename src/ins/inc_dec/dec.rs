@@ -1,13 +1,14 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Decrement Memory - Decrements the value in the specified byte in memory by one,
 /// wrapping around so that the result of decrementing $00 is $FF. The Carry flag is not
-/// affected.
+/// affected. The 65C02 can also decrement the accumulator.
 pub struct DEC(pub Addr);
 
 impl DEC {
-    pub fn set_flags(cpu: &mut CPU, value: Byte) {
+    pub fn set_flags<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) {
         // Set zero flag if the result is 0
         cpu.flags.z = value == 0;
         // Updated negative flag to the value of bit #7 of the result.
@@ -16,71 +17,61 @@ impl DEC {
 }
 
 impl Instruction for DEC {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
+            // 1B, 2C (65C02 only)
+            DEC(Addr::Accummulator) => cpu.cycles += 2,
             // 2B, 5C
-            DEC(Addr::ZeroPage) => {
-                let addr = cpu.read_byte(cpu.pc + 1) as Word;
-                let value = cpu.read_byte(addr);
-                let result = value.wrapping_sub(1);
-                cpu.write_byte(addr, result);
-                Self::set_flags(cpu, result);
-                cpu.pc += 2;
-            },
+            DEC(Addr::ZeroPage) => cpu.cycles += 5,
             // 2B, 6C
-            DEC(Addr::ZeroPageX) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                let addr = zp_addr.wrapping_add(cpu.reg.x) as Word;
-                let value = cpu.read_byte(addr);
-                let result = value.wrapping_sub(1);
-                cpu.write_byte(addr, result);
-                Self::set_flags(cpu, result);
-                cpu.pc += 2;
-            },
+            DEC(Addr::ZeroPageX) => cpu.cycles += 6,
             // 3B, 6C
-            DEC(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                let value = cpu.read_byte(addr);
-                let result = value.wrapping_sub(1);
-                cpu.write_byte(addr, result);
-                Self::set_flags(cpu, result);
-                cpu.pc += 3;
-            },
+            DEC(Addr::Absolute) => cpu.cycles += 6,
             // 3B, 7C
-            DEC(Addr::AbsoluteX) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.x as Word;
-                let value = cpu.read_byte(addr);
-                let result = value.wrapping_sub(1);
-                cpu.write_byte(addr, result);
-                Self::set_flags(cpu, result);
-                cpu.pc += 3;
-            },
-            _ => panic!("Operation not supported!")
+            DEC(Addr::AbsoluteX) => cpu.cycles += 7,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = value.wrapping_sub(1);
+            Self::set_flags(cpu, result);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            DEC(Addr::ZeroPage) => 0xC6,
-            DEC(Addr::ZeroPageX) => 0xD6,
-            DEC(Addr::Absolute) => 0xCE,
-            DEC(Addr::AbsoluteX) => 0xDE,
-            _ => panic!("Operation not supported!")
+            DEC(Addr::Accummulator) => Ok(0x3A),
+            DEC(Addr::ZeroPage) => Ok(0xC6),
+            DEC(Addr::ZeroPageX) => Ok(0xD6),
+            DEC(Addr::Absolute) => Ok(0xCE),
+            DEC(Addr::AbsoluteX) => Ok(0xDE),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "DEC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cpu::ResetMode;
 
     #[test]
     fn dec_zero_page() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, DEC(Addr::ZeroPage).code());
+        cpu.mem.write_byte(0xFFFC, DEC(Addr::ZeroPage).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x48);
         cpu.mem.write_byte(0x0048, 0x00);
 
@@ -103,11 +94,11 @@ mod tests {
 
     #[test]
     fn dec_zero_page_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 3;
-        cpu.mem.write_byte(0xFFFC, DEC(Addr::ZeroPageX).code());
+        cpu.mem.write_byte(0xFFFC, DEC(Addr::ZeroPageX).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0xFF);
         cpu.mem.write_byte(0x0002, 0x00); // 0xFF + 0x03 % 0xFF
 
@@ -130,10 +121,10 @@ mod tests {
 
     #[test]
     fn dec_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, DEC(Addr::Absolute).code());
+        cpu.mem.write_byte(0xFFFC, DEC(Addr::Absolute).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x34);
         cpu.mem.write_byte(0xFFFE, 0x12); // 0x1234 (LE)
         cpu.mem.write_byte(0x1234, 0x05);
@@ -157,11 +148,11 @@ mod tests {
 
     #[test]
     fn dec_absolute_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 0x12;
-        cpu.mem.write_byte(0xFFFC, DEC(Addr::AbsoluteX).code());
+        cpu.mem.write_byte(0xFFFC, DEC(Addr::AbsoluteX).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x00);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
         cpu.mem.write_byte(0x4412, 0x05);
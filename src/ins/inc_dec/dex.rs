@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Decrement X Register - Decrements the value in the X register by one, wrapping around
 /// so that the result of decrementing $00 is $FF. The Carry flag is not affected.
 pub struct DEX(pub Addr);
 
 impl DEX {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag is X is 0
         cpu.flags.z = cpu.reg.x == 0;
         // Set negative flag if bit 7 of X is set
@@ -15,22 +16,32 @@ impl DEX {
 }
 
 impl Instruction for DEX {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             DEX(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.x = cpu.reg.x.wrapping_sub(1);
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            DEX(Addr::Implicit) => 0xCA,
-            _ => panic!("Operation not supported!")
+            DEX(Addr::Implicit) => Ok(0xCA),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "DEX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,13 +1,14 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Increment Memory by One - Increments the value in the specified byte in memory by one,
 /// wrapping around so that the result of incrementing $FF is $00. The Carry flag is not
-/// affected.
+/// affected. The 65C02 can also increment the accumulator.
 pub struct INC(pub Addr);
 
 impl INC {
-    pub fn set_flags(cpu: &mut CPU, value: Byte) {
+    pub fn set_flags<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) {
         // Set zero flag if the result is 0
         cpu.flags.z = value == 0;
         // Updated negative flag to the value of bit #7 of the result.
@@ -16,53 +17,117 @@ impl INC {
 }
 
 impl Instruction for INC {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
+            // 1B, 2C (65C02 only)
+            INC(Addr::Accummulator) => cpu.cycles += 2,
             // 2B, 5C
-            INC(Addr::ZeroPage) => {
-                let addr = cpu.read_byte(cpu.pc + 1) as Word;
-                let value = cpu.read_byte(addr);
-                cpu.write_byte(addr, value.wrapping_add(1));
-                Self::set_flags(cpu, value);
-                cpu.pc += 2;
-            },
+            INC(Addr::ZeroPage) => cpu.cycles += 5,
             // 2B, 6C
-            INC(Addr::ZeroPageX) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                let addr = zp_addr.wrapping_add(cpu.reg.x) as Word;
-                let value = cpu.read_byte(addr);
-                cpu.write_byte(addr, value.wrapping_add(1));
-                Self::set_flags(cpu, value);
-                cpu.pc += 2;
-            }
+            INC(Addr::ZeroPageX) => cpu.cycles += 6,
             // 3B, 6C
-            INC(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                let value = cpu.read_byte(addr);
-                cpu.write_byte(addr, value.wrapping_add(1));
-                Self::set_flags(cpu, value);
-                cpu.pc += 3;
-            },
+            INC(Addr::Absolute) => cpu.cycles += 6,
             // 3B, 7C
-            INC(Addr::AbsoluteX) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.x as Word;
-                let value = cpu.read_byte(addr);
-                cpu.write_byte(addr, value.wrapping_add(1));
-                Self::set_flags(cpu, value);
-                cpu.pc += 3;
-            },
-            _ => panic!("Operation not supported!")
+            INC(Addr::AbsoluteX) => cpu.cycles += 7,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.modify_operand(&operand, |cpu, value| {
+            let result = value.wrapping_add(1);
+            Self::set_flags(cpu, result);
+            result
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            INC(Addr::ZeroPage) => 0xE6,
-            INC(Addr::ZeroPageX) => 0xF6,
-            INC(Addr::Absolute) => 0xEE,
-            INC(Addr::AbsoluteX) => 0xFE,
-            _ => panic!("Operation not supported!")
+            INC(Addr::Accummulator) => Ok(0x1A),
+            INC(Addr::ZeroPage) => Ok(0xE6),
+            INC(Addr::ZeroPageX) => Ok(0xF6),
+            INC(Addr::Absolute) => Ok(0xEE),
+            INC(Addr::AbsoluteX) => Ok(0xFE),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "INC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+    use crate::cpu::Variant;
+
+    #[test]
+    fn inc_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, INC(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x48);
+        cpu.mem.write_byte(0x0048, 0xFF);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0048), 0x00);
+
+        // The flags reflect the incremented value.
+        assert_eq!(cpu.flags.c, cpu_start.flags.c);
+        assert!(cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert_eq!(cpu.flags.v, cpu_start.flags.v);
+        assert!(!cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn inc_absolute_x_wraps() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x10;
+        cpu.mem.write_byte(0xFFFC, INC(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF8);
+        cpu.mem.write_byte(0xFFFE, 0xFF); // 0xFFF8 (LE)
+        cpu.mem.write_byte(0x0008, 0x41); // 0xFFF8 + 0x10 wraps to 0x0008
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0008), 0x42);
+        assert!(!cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn inc_accumulator() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reg.acc = 0x7F;
+        cpu.mem.write_byte(0xFFFC, INC(Addr::Accummulator).code().unwrap());
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x80);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFD);
+        assert_eq!(cpu.cycles, 2);
+    }
 }
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Increment X Register - Increments the value in the X register by one, wrapping around
 /// so that the result of incrementing $FF is $00. The Carry flag is not affected.
 pub struct INX(pub Addr);
 
 impl INX {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag is X is 0
         cpu.flags.z = cpu.reg.x == 0;
         // Set negative flag if bit 7 of X is set
@@ -15,22 +16,32 @@ impl INX {
 }
 
 impl Instruction for INX {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             INX(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.x = cpu.reg.x.wrapping_add(1);
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            INX(Addr::Implicit) => 0xE8,
-            _ => panic!("Operation not supported!")
+            INX(Addr::Implicit) => Ok(0xE8),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "INX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
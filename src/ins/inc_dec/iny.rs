@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Increment Y Register - Increments the value in the X register by one, wrapping around
 /// so that the result of incrementing $FF is $00. The Carry flag is not affected.
 pub struct INY(pub Addr);
 
 impl INY {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag is Y is 0
         cpu.flags.z = cpu.reg.y == 0;
         // Set negative flag if bit 7 of Y is set
@@ -15,22 +16,32 @@ impl INY {
 }
 
 impl Instruction for INY {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             INY(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.y = cpu.reg.y.wrapping_add(1);
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            INY(Addr::Implicit) => 0xC8,
-            _ => panic!("Operation not supported!")
+            INY(Addr::Implicit) => Ok(0xC8),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "INY"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -0,0 +1,135 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Jump - Sets the program counter to the address specified by the operand.
+pub struct JMP(pub Addr);
+
+impl Instruction for JMP {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 3B, 3C
+            JMP(Addr::Absolute) => cpu.cycles += 3,
+            // 3B, 5C (65C02: 6C)
+            JMP(Addr::Indirect) => cpu.cycles += 5 + cpu.variant.is_cmos() as u32,
+            // 3B, 6C (65C02 only)
+            JMP(Addr::AbsoluteXIndirect) => cpu.cycles += 6,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.pc = operand.address.expect("JMP operands have an address");
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            JMP(Addr::Absolute) => Ok(0x4C),
+            JMP(Addr::Indirect) => Ok(0x6C),
+            JMP(Addr::AbsoluteXIndirect) => Ok(0x7C),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "JMP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn jmp_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, JMP(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x32);
+        cpu.mem.write_byte(0xFFFE, 0x42); // 0x4232 (LE)
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0x4232);
+        assert_eq!(cpu.sp, cpu_start.sp);
+        assert_eq!(cpu.flags, cpu_start.flags);
+    }
+
+    #[test]
+    fn jmp_indirect() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, JMP(Addr::Indirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x20);
+        cpu.mem.write_byte(0xFFFE, 0x01); // 0x0120 (LE)
+        cpu.mem.write_byte(0x0120, 0x32);
+        cpu.mem.write_byte(0x0121, 0x42); // 0x4232 (LE)
+
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0x4232);
+    }
+
+    #[test]
+    fn jmp_indirect_page_boundary_bug() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, JMP(Addr::Indirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0xFFFE, 0x10); // 0x10FF (LE)
+        cpu.mem.write_byte(0x10FF, 0x32);
+        cpu.mem.write_byte(0x1000, 0x42);
+        cpu.mem.write_byte(0x1100, 0x99);
+
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0x4232);
+    }
+
+    #[test]
+    fn jmp_indirect_cmos() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Cmos65C02;
+        cpu.mem.write_byte(0xFFFC, JMP(Addr::Indirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0xFFFE, 0x10); // 0x10FF (LE)
+        cpu.mem.write_byte(0x10FF, 0x32);
+        cpu.mem.write_byte(0x1000, 0x42);
+        cpu.mem.write_byte(0x1100, 0x99);
+
+        cpu.step().unwrap();
+
+        // The 65C02 fetches the high byte from the next page.
+        assert_eq!(cpu.pc, 0x9932);
+        assert_eq!(cpu.cycles, 6);
+    }
+
+    #[test]
+    fn jmp_absolute_x_indirect() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reg.x = 0x04;
+        cpu.mem.write_byte(0xFFFC, JMP(Addr::AbsoluteXIndirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x20);
+        cpu.mem.write_byte(0xFFFE, 0x01); // 0x0120 (LE)
+        cpu.mem.write_byte(0x0124, 0x32);
+        cpu.mem.write_byte(0x0125, 0x42); // 0x4232 (LE)
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.pc, 0x4232);
+        assert_eq!(cpu.cycles, 6);
+    }
+}
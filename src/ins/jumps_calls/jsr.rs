@@ -1,51 +1,67 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
+/// Jump to Subroutine - The JSR instruction pushes the address (minus one) of the return
+/// point on to the stack and then sets the program counter to the target memory address.
 pub struct JSR(pub Addr);
 
 impl Instruction for JSR {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 3B, 6C
-            JSR(Addr::Absolute) => {
-                let sub_addr = cpu.read_word(cpu.pc + 1);
-                // Save the previous pc on the stack so we can come back to it.
-                // cpu.write_word(cpu.sp as Word, cpu.pc);
-                cpu.pc = sub_addr;
-                // TODO check if increasing the stack pointer is the right thing to do.
-                // cpu.sp += 1;
-            },
-            _ => panic!("Addressing method not supported.")
+            JSR(Addr::Absolute) => cpu.cycles += 6,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        // Save the address of the last byte of this instruction on the stack, so
+        // RTS can come back to the instruction that follows it.
+        cpu.push_word(cpu.pc.wrapping_add(operand.len - 1));
+        cpu.pc = operand.address.expect("JSR operands have an address");
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            JSR(Addr::Absolute) => 0x20,
-            _ => panic!("Addressing method not supported.")
+            JSR(Addr::Absolute) => Ok(0x20),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "JSR"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cpu::ResetMode;
     use crate::cpu::CPU;
     use crate::mem::Addr;
 
     #[test]
     fn jsr_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, JSR(Addr::Absolute).code());
+        cpu.mem.write_byte(0xFFFC, JSR(Addr::Absolute).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x32);
         cpu.mem.write_byte(0xFFFE, 0x42); // 0x4232 (LE)
+
+        let cpu_start = cpu.clone();
         cpu.start();
 
-        // assert_eq!(cpu.sp, 0xFFFC);
         assert_eq!(cpu.pc, 0x4232);
+        assert_eq!(cpu.sp, cpu_start.sp.wrapping_sub(2));
+        // The return address (0xFFFE) is pushed high byte first.
+        assert_eq!(cpu.mem.read_byte(CPU::stack_address(cpu_start.sp)), 0xFF);
+        assert_eq!(cpu.mem.read_byte(CPU::stack_address(cpu_start.sp - 1)), 0xFE);
 
-        // TODO test flags
+        assert_eq!(cpu.flags, cpu_start.flags);
     }
 }
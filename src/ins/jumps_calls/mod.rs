@@ -1 +1,3 @@
+pub mod jmp;
 pub mod jsr;
+pub mod rts;
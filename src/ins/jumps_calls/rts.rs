@@ -0,0 +1,92 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Return from Subroutine - The RTS instruction is used at the end of a subroutine to
+/// return to the calling routine. It pulls the program counter (minus one) from the
+/// stack.
+pub struct RTS(pub Addr);
+
+impl Instruction for RTS {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, 6C
+            RTS(Addr::Implicit) => {
+                cpu.cycles += 6;
+                // JSR pushed the address of its last byte, so we resume right after it.
+                cpu.pc = cpu.pull_word().wrapping_add(1);
+            },
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            RTS(Addr::Implicit) => Ok(0x60),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "RTS"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+    use crate::ins::jumps_calls::jsr::JSR;
+
+    #[test]
+    fn rts_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, JSR(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x40); // 0x4000 (LE)
+        cpu.mem.write_byte(0x4000, RTS(Addr::Implicit).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0xFFFF);
+        assert_eq!(cpu.sp, cpu_start.sp);
+        assert_eq!(cpu.flags, cpu_start.flags);
+    }
+
+    #[test]
+    fn rts_nested() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, JSR(Addr::Absolute).code().unwrap());
+        cpu.mem.write_word(0xFFFD, 0x4000);
+        // 0x4000: JSR $5000; RTS
+        cpu.mem.write_byte(0x4000, JSR(Addr::Absolute).code().unwrap());
+        cpu.mem.write_word(0x4001, 0x5000);
+        cpu.mem.write_byte(0x4003, RTS(Addr::Implicit).code().unwrap());
+        // 0x5000: RTS
+        cpu.mem.write_byte(0x5000, RTS(Addr::Implicit).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+        cpu.start();
+        assert_eq!(cpu.pc, 0x5000);
+        assert_eq!(cpu.sp, cpu_start.sp.wrapping_sub(4));
+
+        cpu.start();
+        assert_eq!(cpu.pc, 0x4003);
+
+        cpu.start();
+        assert_eq!(cpu.pc, 0xFFFF);
+        assert_eq!(cpu.sp, cpu_start.sp);
+    }
+}
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Load Accummulator. Loads a byte of memory into the accumulator, setting the zero and
 /// negative flags as appropriate.
 pub struct LDA(pub Addr);
 
 impl LDA {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set if A = 0
         cpu.flags.z = cpu.reg.acc == 0;
         // Set if bit 7 of A is set
@@ -15,94 +16,73 @@ impl LDA {
 }
 
 impl Instruction for LDA {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 2C
-            LDA(Addr::Immediate) => {
-                cpu.reg.acc = cpu.read_byte(cpu.pc + 1);
-                cpu.pc += 2;
-            },
+            LDA(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            LDA(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                cpu.reg.acc = cpu.read_byte(zp_addr as Word);
-                cpu.pc += 2;
-            },
+            LDA(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            LDA(Addr::ZeroPageX) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.x);
-                cpu.reg.acc = cpu.read_byte(zp_addr as Word);
-                cpu.pc += 2;
-            },
+            LDA(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            LDA(Addr::Absolute) => {
-                let address = cpu.read_word(cpu.pc + 1);
-                cpu.reg.acc = cpu.read_byte(address);
-                cpu.pc += 3;
-            },
+            LDA(Addr::Absolute) => cpu.cycles += 4,
             // 3B, 4C (+1 if page crossed)
-            LDA(Addr::AbsoluteX) => {
-                let mut address = cpu.read_word(cpu.pc + 1);
-                address += cpu.reg.x as Word;
-                cpu.reg.acc = cpu.read_byte(address);
-                cpu.pc += 3;
-            },
+            LDA(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 3B, 4C (+1 if page crossed)
-            LDA(Addr::AbsoluteY) => {
-                let mut address = cpu.read_word(cpu.pc + 1);
-                address += cpu.reg.y as Word;
-                cpu.reg.acc = cpu.read_byte(address);
-                cpu.pc += 3;
-            },
+            LDA(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 2B, 6C
-            LDA(Addr::XIndirect) => {
-                let mut ptr = cpu.read_byte(cpu.pc + 1);
-                ptr = ptr.wrapping_add(cpu.reg.x);
-                let address = cpu.read_word(ptr as Word);
-                cpu.reg.acc = cpu.read_byte(address);
-                cpu.pc += 2;
-            },
+            LDA(Addr::XIndirect) => cpu.cycles += 6,
             // 2B, 5C (+1 if page crossed)
-            LDA(Addr::IndirectY) => {
-                let ptr = cpu.read_byte(cpu.pc + 1);
-                let mut address = cpu.read_word(ptr as Word);
-                address += cpu.reg.y as Word;
-                cpu.reg.acc = cpu.read_byte(address);
-                cpu.pc += 2;
-            },
-            _ => panic!("Operation not supported!")
+            LDA(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            LDA(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
-        LDA::set_flags(cpu);
+        cpu.reg.acc = cpu.read_operand(&operand);
+        Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            LDA(Addr::Immediate) => 0x49,
-            LDA(Addr::ZeroPage) => 0xA5,
-            LDA(Addr::ZeroPageX) => 0xB5,
-            LDA(Addr::Absolute) => 0xAD,
-            LDA(Addr::AbsoluteX) => 0xBD,
-            LDA(Addr::AbsoluteY) => 0xB9,
-            LDA(Addr::XIndirect) => 0xA1,
-            LDA(Addr::IndirectY) => 0xB1,
-            _ => panic!("Operation not supported!")
+            LDA(Addr::Immediate) => Ok(0xA9),
+            LDA(Addr::ZeroPage) => Ok(0xA5),
+            LDA(Addr::ZeroPageX) => Ok(0xB5),
+            LDA(Addr::Absolute) => Ok(0xAD),
+            LDA(Addr::AbsoluteX) => Ok(0xBD),
+            LDA(Addr::AbsoluteY) => Ok(0xB9),
+            LDA(Addr::XIndirect) => Ok(0xA1),
+            LDA(Addr::IndirectY) => Ok(0xB1),
+            LDA(Addr::ZeroPageIndirect) => Ok(0xB2),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "LDA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cpu::ResetMode;
+    use crate::cpu::Variant;
     use crate::cpu::CPU;
     use crate::mem::Addr;
 
     #[test]
     fn lda_immediate() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::Immediate).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::Immediate).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x84);
 
         let cpu_start = cpu.clone();
@@ -124,10 +104,10 @@ mod tests {
 
     #[test]
     fn lda_zero_page() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::ZeroPage).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::ZeroPage).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x42);
         cpu.mem.write_byte(0x0042, 0x84);
 
@@ -150,11 +130,11 @@ mod tests {
 
     #[test]
     fn lda_zero_page_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 0x02;
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::ZeroPageX).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::ZeroPageX).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0xFF);
         cpu.mem.write_byte(0x0001, 0x85); // 0xFF + 0x02 % 0xFF = 0x01
 
@@ -177,10 +157,10 @@ mod tests {
 
     #[test]
     fn lda_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::Absolute).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::Absolute).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x80);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
         cpu.mem.write_byte(0x4480, 0x37);
@@ -204,11 +184,11 @@ mod tests {
 
     #[test]
     fn lda_absolute_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 0x12;
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::AbsoluteX).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::AbsoluteX).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x00);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
         cpu.mem.write_byte(0x4412, 0x37);
@@ -232,11 +212,11 @@ mod tests {
 
     #[test]
     fn lda_absolute_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.y = 0x12;
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::AbsoluteY).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::AbsoluteY).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x00);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
         cpu.mem.write_byte(0x4412, 0x37);
@@ -260,11 +240,11 @@ mod tests {
 
     #[test]
     fn lda_x_indirect() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 0x04;
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::XIndirect).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::XIndirect).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x02); // 0x02 + 0x04 = 0x06
         cpu.mem.write_byte(0x0006, 0x00);
         cpu.mem.write_byte(0x0007, 0x80); // 0x8000 (LE)
@@ -289,11 +269,11 @@ mod tests {
 
     #[test]
     fn lda_indirect_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.y = 0x04;
-        cpu.mem.write_byte(0xFFFC, LDA(Addr::IndirectY).code());
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::IndirectY).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x02);
         cpu.mem.write_byte(0x0002, 0x00);
         cpu.mem.write_byte(0x0003, 0x80); // 0x8000 (LE)
@@ -315,4 +295,23 @@ mod tests {
         assert_eq!(cpu_start.pc, 0xFFFC);
         assert_eq!(cpu.pc, 0xFFFE);
     }
+
+    #[test]
+    fn lda_zero_page_indirect() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.variant = Variant::Cmos65C02;
+        cpu.mem.write_byte(0xFFFC, LDA(Addr::ZeroPageIndirect).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x02);
+        cpu.mem.write_byte(0x0002, 0x00);
+        cpu.mem.write_byte(0x0003, 0x80); // 0x8000 (LE)
+        cpu.mem.write_byte(0x8000, 0x37);
+
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.reg.acc, 0x37);
+        assert_eq!(cpu.pc, 0xFFFE);
+        assert_eq!(cpu.cycles, 5);
+    }
 }
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Load X Register. Loads a byte of memory into the X register setting the zero and
 /// negative flags as appropriate.
 pub struct LDX(pub Addr);
 
 impl LDX {
-    fn set_flags(&self, cpu: &mut CPU) {
+    fn set_flags(&self, cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if register X is 0
         cpu.flags.z = cpu.reg.x == 0;
         // Set negative flag is bit 7 of X is set
@@ -15,66 +16,58 @@ impl LDX {
 }
 
 impl Instruction for LDX {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 2C
-            LDX(Addr::Immediate) => {
-                cpu.reg.x = cpu.read_byte(cpu.pc + 1);
-                cpu.pc += 2;
-            },
+            LDX(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            LDX(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                cpu.reg.x = cpu.read_byte(zp_addr as Word);
-                cpu.pc += 2;
-            },
+            LDX(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            LDX(Addr::ZeroPageY) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.y);
-                cpu.reg.y = cpu.read_byte(zp_addr as Word);
-                cpu.pc += 2;
-            },
+            LDX(Addr::ZeroPageY) => cpu.cycles += 4,
             // 3B, 4C
-            LDX(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                cpu.reg.x = cpu.read_byte(addr);
-                cpu.pc += 3;
-            },
+            LDX(Addr::Absolute) => cpu.cycles += 4,
             // 3B, 4C (+1 if page crossed)
-            LDX(Addr::AbsoluteY) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.y as Word;
-                cpu.reg.x = cpu.read_byte(addr);
-                cpu.pc += 3;
-            }
-            _ => panic!("Operation not supported!")
+            LDX(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.reg.x = cpu.read_operand(&operand);
         self.set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            LDX(Addr::Immediate) => 0xA2,
-            LDX(Addr::ZeroPage) => 0xA6,
-            LDX(Addr::ZeroPageY) => 0xB6,
-            LDX(Addr::Absolute) => 0xAE,
-            LDX(Addr::AbsoluteY) => 0xBE,
-            _ => panic!("Operation not supported!")
+            LDX(Addr::Immediate) => Ok(0xA2),
+            LDX(Addr::ZeroPage) => Ok(0xA6),
+            LDX(Addr::ZeroPageY) => Ok(0xB6),
+            LDX(Addr::Absolute) => Ok(0xAE),
+            LDX(Addr::AbsoluteY) => Ok(0xBE),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "LDX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cpu::ResetMode;
 
     #[test]
     fn ldx_immediate() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDX(Addr::Immediate).code());
+        cpu.mem.write_byte(0xFFFC, LDX(Addr::Immediate).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x84);
 
         let cpu_start = cpu.clone();
@@ -96,10 +89,10 @@ mod tests {
 
     #[test]
     fn ldx_zero_page() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDX(Addr::ZeroPage).code());
+        cpu.mem.write_byte(0xFFFC, LDX(Addr::ZeroPage).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x42);
         cpu.mem.write_byte(0x0042, 0x84);
 
@@ -122,11 +115,11 @@ mod tests {
 
     #[test]
     fn ldx_zero_page_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.y = 0x02;
-        cpu.mem.write_byte(0xFFFC, LDX(Addr::ZeroPageY).code());
+        cpu.mem.write_byte(0xFFFC, LDX(Addr::ZeroPageY).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0xFF);
         cpu.mem.write_byte(0x0001, 0x00); // 0xFF + 0x02 % 0xFF = 0x01
 
@@ -149,10 +142,10 @@ mod tests {
 
     #[test]
     fn ldx_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDX(Addr::Absolute).code());
+        cpu.mem.write_byte(0xFFFC, LDX(Addr::Absolute).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x33);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4433 (LE)
         cpu.mem.write_byte(0x4433, 0x84);
@@ -176,11 +169,11 @@ mod tests {
 
     #[test]
     fn ldx_absolute_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.y = 0x02;
-        cpu.mem.write_byte(0xFFFC, LDX(Addr::AbsoluteY).code());
+        cpu.mem.write_byte(0xFFFC, LDX(Addr::AbsoluteY).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x33);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4433 (LE)
         cpu.mem.write_byte(0x4435, 0x84); // 0x4433 + 0x02
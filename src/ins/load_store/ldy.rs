@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Load Y Register. Loads a byte of memory into the Y register setting the zero and
 /// negative flags as appropriate.
 pub struct LDY(pub Addr);
 
 impl LDY {
-    fn set_flags(&self, cpu: &mut CPU) {
+    fn set_flags(&self, cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if register Y is 0
         cpu.flags.z = cpu.reg.y == 0;
         // Set negative flag is bit 7 of Y is set
@@ -15,66 +16,58 @@ impl LDY {
 }
 
 impl Instruction for LDY {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 2C
-            LDY(Addr::Immediate) => {
-                cpu.reg.y = cpu.read_byte(cpu.pc + 1);
-                cpu.pc += 2;
-            },
+            LDY(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            LDY(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                cpu.reg.y = cpu.read_byte(zp_addr as Word);
-                cpu.pc += 2;
-            },
+            LDY(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            LDY(Addr::ZeroPageX) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.x);
-                cpu.reg.y = cpu.read_byte(zp_addr as Word);
-                cpu.pc += 2;
-            },
+            LDY(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            LDY(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                cpu.reg.y = cpu.read_byte(addr);
-                cpu.pc += 3;
-            },
+            LDY(Addr::Absolute) => cpu.cycles += 4,
             // 3B, 4C (+1 if page crossed)
-            LDY(Addr::AbsoluteX) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.x as Word;
-                cpu.reg.y = cpu.read_byte(addr);
-                cpu.pc += 3;
-            },
-            _ => panic!("Operation not supported!")
+            LDY(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.reg.y = cpu.read_operand(&operand);
         self.set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            LDY(Addr::Immediate) => 0xA0,
-            LDY(Addr::ZeroPage) => 0xA4,
-            LDY(Addr::ZeroPageX) => 0xB4,
-            LDY(Addr::Absolute) => 0xAC,
-            LDY(Addr::AbsoluteX) => 0xBC,
-            _ => panic!("Operation not supported!")
+            LDY(Addr::Immediate) => Ok(0xA0),
+            LDY(Addr::ZeroPage) => Ok(0xA4),
+            LDY(Addr::ZeroPageX) => Ok(0xB4),
+            LDY(Addr::Absolute) => Ok(0xAC),
+            LDY(Addr::AbsoluteX) => Ok(0xBC),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "LDY"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cpu::ResetMode;
 
     #[test]
     fn ldy_immediate() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDY(Addr::Immediate).code());
+        cpu.mem.write_byte(0xFFFC, LDY(Addr::Immediate).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x84);
 
         let cpu_start = cpu.clone();
@@ -96,10 +89,10 @@ mod tests {
 
     #[test]
     fn ldy_zero_page() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDY(Addr::ZeroPage).code());
+        cpu.mem.write_byte(0xFFFC, LDY(Addr::ZeroPage).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x42);
         cpu.mem.write_byte(0x0042, 0x84);
 
@@ -122,11 +115,11 @@ mod tests {
 
     #[test]
     fn ldy_zero_page_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 0x02;
-        cpu.mem.write_byte(0xFFFC, LDY(Addr::ZeroPageX).code());
+        cpu.mem.write_byte(0xFFFC, LDY(Addr::ZeroPageX).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0xFF);
         cpu.mem.write_byte(0x0001, 0x00); // 0xFF + 0x02 % 0xFF = 0x01
 
@@ -149,10 +142,10 @@ mod tests {
 
     #[test]
     fn ldy_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
-        cpu.mem.write_byte(0xFFFC, LDY(Addr::Absolute).code());
+        cpu.mem.write_byte(0xFFFC, LDY(Addr::Absolute).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x33);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4433 (LE)
         cpu.mem.write_byte(0x4433, 0x84);
@@ -176,11 +169,11 @@ mod tests {
 
     #[test]
     fn ldy_absolute_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
 
         cpu.reset();
         cpu.reg.x = 0x02;
-        cpu.mem.write_byte(0xFFFC, LDY(Addr::AbsoluteX).code());
+        cpu.mem.write_byte(0xFFFC, LDY(Addr::AbsoluteX).code().unwrap());
         cpu.mem.write_byte(0xFFFD, 0x33);
         cpu.mem.write_byte(0xFFFE, 0x44); // 0x4433 (LE)
         cpu.mem.write_byte(0x4435, 0x84); // 0x4433 + 0x02
@@ -4,3 +4,4 @@ pub mod ldy;
 pub mod sta;
 pub mod stx;
 pub mod sty;
+pub mod stz;
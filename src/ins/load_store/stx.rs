@@ -1,42 +1,42 @@
 use crate::cpu::CPU;
-use crate::{ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Store X Register - Stores the contents of the X register into memory.
 pub struct STX(pub Addr);
 
 impl Instruction for STX {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 3C
-            STX(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                cpu.write_byte(zp_addr as Word, cpu.reg.x);
-                cpu.pc += 2;
-            },
+            STX(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            STX(Addr::ZeroPageY) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.y);
-                cpu.write_byte(zp_addr as Word, cpu.reg.x);
-                cpu.pc += 2;
-            },
+            STX(Addr::ZeroPageY) => cpu.cycles += 4,
             // 3B, 4C
-            STX(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                cpu.write_byte(addr, cpu.reg.x);
-                cpu.pc += 3;
-            },
-            _ => panic!("Operation not supported!")
+            STX(Addr::Absolute) => cpu.cycles += 4,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.write_operand(&operand, cpu.reg.x);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            STX(Addr::ZeroPage) => 0x86,
-            STX(Addr::ZeroPageY) => 0x96,
-            STX(Addr::Absolute) => 0x8E,
-            _ => panic!("Operation not supported")
+            STX(Addr::ZeroPage) => Ok(0x86),
+            STX(Addr::ZeroPageY) => Ok(0x96),
+            STX(Addr::Absolute) => Ok(0x8E),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "STX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,42 +1,42 @@
 use crate::cpu::CPU;
-use crate::{ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Store Y Register - Stores the contents of the Y register into memory.
 pub struct STY(pub Addr);
 
 impl Instruction for STY {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 3C
-            STY(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                cpu.write_byte(zp_addr as Word, cpu.reg.y);
-                cpu.pc += 2;
-            },
+            STY(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            STY(Addr::ZeroPageX) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.x);
-                cpu.write_byte(zp_addr as Word, cpu.reg.y);
-                cpu.pc += 2;
-            },
+            STY(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            STY(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                cpu.write_byte(addr, cpu.reg.y);
-                cpu.pc += 3;
-            },
-            _ => panic!("Operation not supported!")
+            STY(Addr::Absolute) => cpu.cycles += 4,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.write_operand(&operand, cpu.reg.y);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            STY(Addr::ZeroPage) => 0x84,
-            STY(Addr::ZeroPageX) => 0x94,
-            STY(Addr::Absolute) => 0x8C,
-            _ => panic!("Operation not supported")
+            STY(Addr::ZeroPage) => Ok(0x84),
+            STY(Addr::ZeroPageX) => Ok(0x94),
+            STY(Addr::Absolute) => Ok(0x8C),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "STY"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -0,0 +1,84 @@
+use crate::{ins::{ExecError, Instruction}, mem::Addr, cpu::CPU};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Store Zero - Store zero into memory. Only available on the 65C02.
+pub struct STZ(pub Addr);
+
+impl Instruction for STZ {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 3C
+            STZ(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            STZ(Addr::ZeroPageX) => cpu.cycles += 4,
+            // 3B, 4C
+            STZ(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 5C
+            STZ(Addr::AbsoluteX) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.write_operand(&operand, 0);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            STZ(Addr::ZeroPage) => Ok(0x64),
+            STZ(Addr::ZeroPageX) => Ok(0x74),
+            STZ(Addr::Absolute) => Ok(0x9C),
+            STZ(Addr::AbsoluteX) => Ok(0x9E),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "STZ"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn stz_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, STZ(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x22);
+        cpu.mem.write_byte(0x0022, 0x42);
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0022), 0x00);
+        assert_eq!(cpu.cycles, 3);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn stz_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.reg.x = 0x12;
+        cpu.mem.write_byte(0xFFFC, STZ(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0x42);
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x4412), 0x00);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
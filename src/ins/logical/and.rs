@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Logical AND - Performs a bitwise AND operation between the value in the Accumulator
 /// and the specified byte, storing the result in the Accumulator.
 pub struct AND(pub Addr);
 
 impl AND {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if acc = 0
         cpu.flags.z = cpu.reg.acc == 0;
         // Set negative flag if bit 7 of acc is set
@@ -15,86 +16,55 @@ impl AND {
 }
 
 impl Instruction for AND {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 2C
-            AND(Addr::Immediate) => {
-                let value = cpu.read_byte(cpu.pc + 1);
-                cpu.reg.acc &= value;
-                cpu.pc += 2;
-            },
+            AND(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            AND(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                let value = cpu.read_byte(zp_addr as Word);
-                cpu.reg.acc &= value;
-                cpu.pc += 2;
-            },
+            AND(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            AND(Addr::ZeroPageX) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.x);
-                let value = cpu.read_byte(zp_addr as Word);
-                cpu.reg.acc &= value;
-                cpu.pc += 2;
-            },
+            AND(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            AND(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc &= value;
-                cpu.pc += 3;
-            },
+            AND(Addr::Absolute) => cpu.cycles += 4,
             // 3B, 4C (+1 if page crossed)
-            AND(Addr::AbsoluteX) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.x as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc &= value;
-                cpu.pc += 3;
-            },
+            AND(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 3B, 4C (+1 if page crossed)
-            AND(Addr::AbsoluteY) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.y as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc &= value;
-                cpu.pc += 3;
-            },
+            AND(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 2B, 6C
-            AND(Addr::XIndirect) => {
-                let mut ptr = cpu.read_byte(cpu.pc + 1);
-                ptr = ptr.wrapping_add(cpu.reg.x);
-                let addr = cpu.read_word(ptr as Word);
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc &= value;
-                cpu.pc += 2;
-            },
+            AND(Addr::XIndirect) => cpu.cycles += 6,
             // 2B, 5C (+1 if page crossed)
-            AND(Addr::IndirectY) => {
-                let ptr = cpu.read_byte(cpu.pc + 1);
-                let mut addr = cpu.read_word(ptr as Word);
-                addr += cpu.reg.y as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc &= value;
-                cpu.pc += 2;
-            },
-            _ => panic!("Operation not supported!")
+            AND(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            AND(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.reg.acc &= cpu.read_operand(&operand);
         Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            AND(Addr::Immediate) => 0x29,
-            AND(Addr::ZeroPage) => 0x25,
-            AND(Addr::ZeroPageX) => 0x35,
-            AND(Addr::Absolute) => 0x2D,
-            AND(Addr::AbsoluteX) => 0x3D,
-            AND(Addr::AbsoluteY) => 0x39,
-            AND(Addr::XIndirect) => 0x21,
-            AND(Addr::IndirectY) => 0x31,
-            _ => panic!("Operation not supported!")
+            AND(Addr::Immediate) => Ok(0x29),
+            AND(Addr::ZeroPage) => Ok(0x25),
+            AND(Addr::ZeroPageX) => Ok(0x35),
+            AND(Addr::Absolute) => Ok(0x2D),
+            AND(Addr::AbsoluteX) => Ok(0x3D),
+            AND(Addr::AbsoluteY) => Ok(0x39),
+            AND(Addr::XIndirect) => Ok(0x21),
+            AND(Addr::IndirectY) => Ok(0x31),
+            AND(Addr::ZeroPageIndirect) => Ok(0x32),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "AND"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,13 +1,14 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Bit Test - Performs a bitwise AND operation between the value in the Accumulator and
 /// the specified byte in the CPU's address space. The value in the Accumulator is not
-/// updated.
+/// updated. The immediate mode of the 65C02 only affects the zero flag.
 pub struct BIT(pub Addr);
 
 impl BIT {
-    fn set_flags(cpu: &mut CPU, mem_value: Byte, result: Byte) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>, mem_value: Byte, result: Byte) {
         // Zero flag: Set if the result of the AND operation is zero (none of the bits
         // tested were set in both bytes), otherwise cleared.
         cpu.flags.z = result == 0;
@@ -19,33 +20,48 @@ impl BIT {
 }
 
 impl Instruction for BIT {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
+            // 2B, 2C (65C02 only)
+            BIT(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            BIT(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                let mem_value = cpu.read_byte(zp_addr as Word);
-                let result = cpu.reg.acc & mem_value;
-                Self::set_flags(cpu, mem_value, result);
-                cpu.pc += 2;
-            },
+            BIT(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C (65C02 only)
+            BIT(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            BIT(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                let mem_value = cpu.read_byte(addr);
-                let result = cpu.reg.acc & mem_value;
-                Self::set_flags(cpu, mem_value, result);
-                cpu.pc += 3;
-            },
-            _ => panic!("Operation not supported!")
+            BIT(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 4C (+1 if page crossed, 65C02 only)
+            BIT(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        let mem_value = cpu.read_operand(&operand);
+        let result = cpu.reg.acc & mem_value;
+        if let BIT(Addr::Immediate) = self {
+            cpu.flags.z = result == 0;
+        } else {
+            Self::set_flags(cpu, mem_value, result);
+        }
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            BIT(Addr::ZeroPage) => 0x24,
-            BIT(Addr::Absolute) => 0x2C,
-            _ => panic!("Operation not supported!")
+            BIT(Addr::Immediate) => Ok(0x89),
+            BIT(Addr::ZeroPage) => Ok(0x24),
+            BIT(Addr::ZeroPageX) => Ok(0x34),
+            BIT(Addr::Absolute) => Ok(0x2C),
+            BIT(Addr::AbsoluteX) => Ok(0x3C),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "BIT"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
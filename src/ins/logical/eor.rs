@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Logical EOR - Performs a bitwise EOR operation between the value in the Accumulator
 /// and the specified byte, storing the result in the Accumulator.
 pub struct EOR(pub Addr);
 
 impl EOR {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if acc = 0
         cpu.flags.z = cpu.reg.acc == 0;
         // Set negative flag if bit 7 of acc is set
@@ -15,86 +16,55 @@ impl EOR {
 }
 
 impl Instruction for EOR {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 2C
-            EOR(Addr::Immediate) => {
-                let value = cpu.read_byte(cpu.pc + 1);
-                cpu.reg.acc ^= value;
-                cpu.pc += 2;
-            },
+            EOR(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            EOR(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                let value = cpu.read_byte(zp_addr as Word);
-                cpu.reg.acc ^= value;
-                cpu.pc += 2;
-            },
+            EOR(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            EOR(Addr::ZeroPageX) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.x);
-                let value = cpu.read_byte(zp_addr as Word);
-                cpu.reg.acc ^= value;
-                cpu.pc += 2;
-            },
+            EOR(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            EOR(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc ^= value;
-                cpu.pc += 3;
-            },
+            EOR(Addr::Absolute) => cpu.cycles += 4,
             // 3B, 4C (+1 if page crossed)
-            EOR(Addr::AbsoluteX) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.x as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc ^= value;
-                cpu.pc += 3;
-            },
+            EOR(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 3B, 4C (+1 if page crossed)
-            EOR(Addr::AbsoluteY) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.y as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc ^= value;
-                cpu.pc += 3;
-            },
+            EOR(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 2B, 6C
-            EOR(Addr::XIndirect) => {
-                let mut ptr = cpu.read_byte(cpu.pc + 1);
-                ptr = ptr.wrapping_add(cpu.reg.x);
-                let addr = cpu.read_word(ptr as Word);
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc ^= value;
-                cpu.pc += 2;
-            },
+            EOR(Addr::XIndirect) => cpu.cycles += 6,
             // 2B, 5C (+1 if page crossed)
-            EOR(Addr::IndirectY) => {
-                let ptr = cpu.read_byte(cpu.pc + 1);
-                let mut addr = cpu.read_word(ptr as Word);
-                addr += cpu.reg.y as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc ^= value;
-                cpu.pc += 2;
-            },
-            _ => panic!("Operation not supported!")
+            EOR(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            EOR(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.reg.acc ^= cpu.read_operand(&operand);
         Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            EOR(Addr::Immediate) => 0x49,
-            EOR(Addr::ZeroPage) => 0x45,
-            EOR(Addr::ZeroPageX) => 0x55,
-            EOR(Addr::Absolute) => 0x4D,
-            EOR(Addr::AbsoluteX) => 0x5D,
-            EOR(Addr::AbsoluteY) => 0x59,
-            EOR(Addr::XIndirect) => 0x41,
-            EOR(Addr::IndirectY) => 0x51,
-            _ => panic!("Operation not supported!")
+            EOR(Addr::Immediate) => Ok(0x49),
+            EOR(Addr::ZeroPage) => Ok(0x45),
+            EOR(Addr::ZeroPageX) => Ok(0x55),
+            EOR(Addr::Absolute) => Ok(0x4D),
+            EOR(Addr::AbsoluteX) => Ok(0x5D),
+            EOR(Addr::AbsoluteY) => Ok(0x59),
+            EOR(Addr::XIndirect) => Ok(0x41),
+            EOR(Addr::IndirectY) => Ok(0x51),
+            EOR(Addr::ZeroPageIndirect) => Ok(0x52),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "EOR"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
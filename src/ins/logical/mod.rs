@@ -2,3 +2,7 @@ pub mod and;
 pub mod eor;
 pub mod ora;
 pub mod bit;
+pub mod trb;
+pub mod tsb;
+pub mod rmb;
+pub mod smb;
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
-use crate::{Byte, Word};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// Logical Inclusive OR - Performs a bitwise ORA operation between the value in the Accumulator
 /// and the specified byte, storing the result in the Accumulator.
 pub struct ORA(pub Addr);
 
 impl ORA {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if X = 0
         cpu.flags.z = cpu.reg.x == 0;
         // Set negative flag if bit 7 of X is set
@@ -15,86 +16,55 @@ impl ORA {
 }
 
 impl Instruction for ORA {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
         match self {
             // 2B, 2C
-            ORA(Addr::Immediate) => {
-                let value = cpu.read_byte(cpu.pc + 1);
-                cpu.reg.acc |= value;
-                cpu.pc += 2;
-            },
+            ORA(Addr::Immediate) => cpu.cycles += 2,
             // 2B, 3C
-            ORA(Addr::ZeroPage) => {
-                let zp_addr = cpu.read_byte(cpu.pc + 1);
-                let value = cpu.read_byte(zp_addr as Word);
-                cpu.reg.acc |= value;
-                cpu.pc += 2;
-            },
+            ORA(Addr::ZeroPage) => cpu.cycles += 3,
             // 2B, 4C
-            ORA(Addr::ZeroPageX) => {
-                let mut zp_addr = cpu.read_byte(cpu.pc + 1);
-                zp_addr = zp_addr.wrapping_add(cpu.reg.x);
-                let value = cpu.read_byte(zp_addr as Word);
-                cpu.reg.acc |= value;
-                cpu.pc += 2;
-            },
+            ORA(Addr::ZeroPageX) => cpu.cycles += 4,
             // 3B, 4C
-            ORA(Addr::Absolute) => {
-                let addr = cpu.read_word(cpu.pc + 1);
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc |= value;
-                cpu.pc += 3;
-            },
+            ORA(Addr::Absolute) => cpu.cycles += 4,
             // 3B, 4C (+1 if page crossed)
-            ORA(Addr::AbsoluteX) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.x as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc |= value;
-                cpu.pc += 3;
-            },
+            ORA(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 3B, 4C (+1 if page crossed)
-            ORA(Addr::AbsoluteY) => {
-                let mut addr = cpu.read_word(cpu.pc + 1);
-                addr += cpu.reg.y as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc |= value;
-                cpu.pc += 3;
-            },
+            ORA(Addr::AbsoluteY) => cpu.cycles += 4 + operand.page_crossed as u32,
             // 2B, 6C
-            ORA(Addr::XIndirect) => {
-                let mut ptr = cpu.read_byte(cpu.pc + 1);
-                ptr = ptr.wrapping_add(cpu.reg.x);
-                let addr = cpu.read_word(ptr as Word);
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc |= value;
-                cpu.pc += 2;
-            },
+            ORA(Addr::XIndirect) => cpu.cycles += 6,
             // 2B, 5C (+1 if page crossed)
-            ORA(Addr::IndirectY) => {
-                let ptr = cpu.read_byte(cpu.pc + 1);
-                let mut addr = cpu.read_word(ptr as Word);
-                addr += cpu.reg.y as Word;
-                let value = cpu.read_byte(addr);
-                cpu.reg.acc |= value;
-                cpu.pc += 2;
-            },
-            _ => panic!("Operation not supported!")
+            ORA(Addr::IndirectY) => cpu.cycles += 5 + operand.page_crossed as u32,
+            // 2B, 5C (65C02 only)
+            ORA(Addr::ZeroPageIndirect) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        cpu.reg.acc |= cpu.read_operand(&operand);
         Self::set_flags(cpu);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            ORA(Addr::Immediate) => 0x09,
-            ORA(Addr::ZeroPage) => 0x05,
-            ORA(Addr::ZeroPageX) => 0x15,
-            ORA(Addr::Absolute) => 0x0D,
-            ORA(Addr::AbsoluteX) => 0x1D,
-            ORA(Addr::AbsoluteY) => 0x19,
-            ORA(Addr::XIndirect) => 0x01,
-            ORA(Addr::IndirectY) => 0x11,
-            _ => panic!("Operation not supported!")
+            ORA(Addr::Immediate) => Ok(0x09),
+            ORA(Addr::ZeroPage) => Ok(0x05),
+            ORA(Addr::ZeroPageX) => Ok(0x15),
+            ORA(Addr::Absolute) => Ok(0x0D),
+            ORA(Addr::AbsoluteX) => Ok(0x1D),
+            ORA(Addr::AbsoluteY) => Ok(0x19),
+            ORA(Addr::XIndirect) => Ok(0x01),
+            ORA(Addr::IndirectY) => Ok(0x11),
+            ORA(Addr::ZeroPageIndirect) => Ok(0x12),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "ORA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -0,0 +1,68 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Set Memory Bit - Sets the given bit (0 to 7) of a byte in the zero page. No flags
+/// are affected. Only available on the Rockwell and WDC 65C02.
+pub struct SMB(pub Addr, pub Byte);
+
+impl Instruction for SMB {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            SMB(Addr::ZeroPage, 0..=7) => cpu.cycles += 5,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |_, value| value | (1 << self.1));
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            SMB(Addr::ZeroPage, bit @ 0..=7) => Ok(0x87 | (bit << 4)),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        const MNEMONICS: [&str; 8] = ["SMB0", "SMB1", "SMB2", "SMB3", "SMB4", "SMB5", "SMB6", "SMB7"];
+        MNEMONICS[self.1 as usize]
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn smb_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, SMB(Addr::ZeroPage, 5).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0x00);
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0b00100000);
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFFE);
+        assert_eq!(cpu.cycles, 5);
+    }
+
+    #[test]
+    fn smb_codes() {
+        let codes: Vec<Byte> = (0..8).map(|bit| SMB(Addr::ZeroPage, bit).code().unwrap()).collect();
+        assert_eq!(codes, [0x87, 0x97, 0xA7, 0xB7, 0xC7, 0xD7, 0xE7, 0xF7]);
+    }
+}
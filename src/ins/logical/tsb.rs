@@ -0,0 +1,90 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Test and Set Bits - Sets the bits of the memory contents which are set in the
+/// accumulator. The zero flag is set like BIT, from the bitwise AND of the accumulator
+/// and the memory contents before they are modified. Only available on the 65C02.
+pub struct TSB(pub Addr);
+
+impl Instruction for TSB {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 2B, 5C
+            TSB(Addr::ZeroPage) => cpu.cycles += 5,
+            // 3B, 6C
+            TSB(Addr::Absolute) => cpu.cycles += 6,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, |cpu, value| {
+            // Set zero flag if none of the bits tested were set in both bytes
+            cpu.flags.z = (cpu.reg.acc & value) == 0;
+            value | cpu.reg.acc
+        });
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            TSB(Addr::ZeroPage) => Ok(0x04),
+            TSB(Addr::Absolute) => Ok(0x0C),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "TSB"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn tsb_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.reg.acc = 0b00001111;
+        cpu.mem.write_byte(0xFFFC, TSB(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0b00111100);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0b00111111);
+        assert_eq!(cpu.reg.acc, 0b00001111);
+        assert!(!cpu.flags.z);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn tsb_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.reg.acc = 0b00001111;
+        cpu.mem.write_byte(0xFFFC, TSB(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0b11110000);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x4480), 0b11111111);
+        assert!(cpu.flags.z);
+        assert_eq!(cpu.cycles, 6);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
@@ -1,18 +1,40 @@
-use load_store::{lda::LDA, ldx::LDX, ldy::LDY, sta::STA, stx::STX, sty::STY};
+use load_store::{lda::LDA, ldx::LDX, ldy::LDY, sta::STA, stx::STX, sty::STY, stz::STZ};
 use reg_transfers::{tax::TAX, tay::TAY, txa::TXA, tya::TYA};
-use stack_ops::{tsx::TSX, txs::TXS, pha::PHA, php::PHP, pla::PLA, plp::PLP};
-use logical::{and::AND, eor::EOR, bit::BIT, ora::ORA};
+use stack_ops::{
+    tsx::TSX, txs::TXS, pha::PHA, php::PHP, pla::PLA, plp::PLP, phx::PHX, phy::PHY, plx::PLX,
+    ply::PLY,
+};
+use logical::{
+    and::AND, eor::EOR, bit::BIT, ora::ORA, trb::TRB, tsb::TSB, rmb::RMB, smb::SMB,
+};
+use arithmetic::{adc::ADC, sbc::SBC, cmp::CMP, cpx::CPX, cpy::CPY};
+use shifts::{asl::ASL, lsr::LSR, rol::ROL, ror::ROR};
 use inc_dec::{inc::INC, inx::INX, iny::INY, dec::DEC, dex::DEX, dey::DEY};
 use status_flags::{clc::CLC, cld::CLD, cli::CLI, clv::CLV, sec::SEC, sed::SED, sei::SEI};
-use jumps_calls::jsr::JSR;
-use sys_funcs::brk::BRK;
+use branches::{
+    bbr::BBR, bbs::BBS, bcc::BCC, bcs::BCS, beq::BEQ, bmi::BMI, bne::BNE, bpl::BPL, bra::BRA,
+    bvc::BVC, bvs::BVS,
+};
+use jumps_calls::{jmp::JMP, jsr::JSR, rts::RTS};
+use sys_funcs::{brk::BRK, nop::NOP, rsv::RSV, rti::RTI, stp::STP, wai::WAI};
+use illegal::{
+    alr::ALR, anc::ANC, ane::ANE, arr::ARR, dcp::DCP, isc::ISC, jam::JAM, las::LAS, lax::LAX,
+    lxa::LXA, rla::RLA, rra::RRA, sax::SAX, sbx::SBX, sha::SHA, shx::SHX, shy::SHY, slo::SLO,
+    sre::SRE, tas::TAS,
+};
+
+use thiserror::Error;
+
+use mos_6502_core::opcodes;
 
 use crate::mem::Addr;
-use crate::cpu::CPU;
-use crate::Byte;
+use crate::cpu::{CPU, Variant};
+use crate::{Byte, Word};
+use crate::bus::Bus;
 
 pub mod arithmetic;
 pub mod branches;
+pub mod illegal;
 pub mod inc_dec;
 pub mod jumps_calls;
 pub mod load_store;
@@ -23,166 +45,1132 @@ pub mod stack_ops;
 pub mod status_flags;
 pub mod sys_funcs;
 
-pub trait Instruction {
-    fn execute(&self, cpu: &mut CPU);
+pub trait Instruction: Sync {
+    /// Executes the instruction. The bus is type-erased so that decoded instructions can
+    /// run on a CPU connected to any [`Bus`].
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError>;
+
+    /// The opcode of the instruction, or an error if it does not support its addressing
+    /// mode.
+    fn code(&self) -> Result<Byte, ExecError>;
+
+    /// The mnemonic of the instruction in assembly, e.g. `LDA`.
+    fn mnemonic(&self) -> &'static str;
 
-    fn code(&self) -> Byte;
+    /// The addressing mode of the instruction.
+    fn mode(&self) -> Addr;
+}
+
+/// Reasons why an opcode cannot be decoded.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("illegal opcode ${0:02X}")]
+    IllegalOpcode(Byte),
+}
+
+/// Reasons why an instruction cannot be executed.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecError {
+    #[error("{source} at ${pc:04X}")]
+    Decode { pc: Word, source: DecodeError },
+    #[error("the instruction does not support the {0:?} addressing mode")]
+    UnsupportedMode(Addr),
 }
 
 pub struct InstructionDecoder;
 
+/// Instructions indexed by opcode, `None` for opcodes which do not correspond to any
+/// instruction supported by the CPU. Built at compile time so that decoding never
+/// allocates.
+static OPCODES: [Option<&'static dyn Instruction>; 256] = {
+    let mut table = [None; 256];
+    let mut code = 0;
+    while code < table.len() {
+        table[code] = InstructionDecoder::instruction(code as Byte);
+        code += 1;
+    }
+    table
+};
+
+/// Instructions of the 65C02 indexed by opcode. Every opcode is defined, the unused ones
+/// being reserved NOPs.
+static CMOS_OPCODES: [&'static dyn Instruction; 256] = {
+    let mut table: [&'static dyn Instruction; 256] = [&NOP(Addr::Implicit); 256];
+    let mut code = 0;
+    while code < table.len() {
+        table[code] = InstructionDecoder::cmos_instruction(code as Byte);
+        code += 1;
+    }
+    table
+};
+
+/// Instructions of the Rockwell 65C02 indexed by opcode, i.e. those of the 65C02 plus the
+/// bit manipulation instructions.
+static ROCKWELL_OPCODES: [&'static dyn Instruction; 256] = {
+    let mut table: [&'static dyn Instruction; 256] = [&NOP(Addr::Implicit); 256];
+    let mut code = 0;
+    while code < table.len() {
+        table[code] = InstructionDecoder::rockwell_instruction(code as Byte);
+        code += 1;
+    }
+    table
+};
+
+/// Instructions of the WDC 65C02 indexed by opcode, i.e. those of the Rockwell 65C02 plus
+/// WAI and STP.
+static WDC_OPCODES: [&'static dyn Instruction; 256] = {
+    let mut table: [&'static dyn Instruction; 256] = [&NOP(Addr::Implicit); 256];
+    let mut code = 0;
+    while code < table.len() {
+        table[code] = InstructionDecoder::wdc_instruction(code as Byte);
+        code += 1;
+    }
+    table
+};
+
+/// The reserved opcodes of the 65C02, each one knowing its own length and timing.
+static RESERVED: [RSV; 256] = {
+    let mut table = [const { RSV(0) }; 256];
+    let mut code = 0;
+    while code < table.len() {
+        table[code] = RSV(code as Byte);
+        code += 1;
+    }
+    table
+};
+
+/// Undocumented instructions of the NMOS 6502 indexed by opcode, `None` for documented
+/// opcodes.
+static ILLEGAL_OPCODES: [Option<&'static dyn Instruction>; 256] = {
+    let mut table = [None; 256];
+    let mut code = 0;
+    while code < table.len() {
+        table[code] = InstructionDecoder::illegal_instruction(code as Byte);
+        code += 1;
+    }
+    table
+};
+
 impl InstructionDecoder {
-    pub fn from_byte(code: Byte) -> Box<dyn Instruction> {
-        match code {
+    /// Decode an opcode, returning an error if it does not correspond to any instruction
+    /// supported by the CPU.
+    pub fn try_from_byte(code: Byte) -> Result<&'static dyn Instruction, DecodeError> {
+        OPCODES[code as usize].ok_or(DecodeError::IllegalOpcode(code))
+    }
+
+    /// Decode an opcode, panicking if it does not correspond to any instruction supported
+    /// by the CPU.
+    pub fn from_byte(code: Byte) -> &'static dyn Instruction {
+        Self::try_from_byte(code).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Decode an opcode for the given CPU `variant`. The NMOS 6502 and the Ricoh 2A03 share
+    /// the same instruction set, see [`InstructionDecoder::try_from_byte`].
+    pub fn try_from_byte_for(code: Byte, variant: Variant) -> Result<&'static dyn Instruction, DecodeError> {
+        match variant {
+            Variant::Nmos6502 | Variant::Ricoh2A03 => Self::try_from_byte(code),
+            Variant::Cmos65C02 => Ok(CMOS_OPCODES[code as usize]),
+            Variant::Rockwell65C02 => Ok(ROCKWELL_OPCODES[code as usize]),
+            Variant::Wdc65C02 => Ok(WDC_OPCODES[code as usize]),
+        }
+    }
+
+    /// Encode the instruction with the given mnemonic and addressing mode for the CPU
+    /// `variant`, i.e. the inverse of [`InstructionDecoder::try_from_byte_for`]. The
+    /// undocumented instructions of the NMOS 6502 can be encoded too, but not the reserved
+    /// opcodes of the 65C02.
+    pub fn encode(mnemonic: &str, mode: Addr, variant: Variant) -> Option<Byte> {
+        opcodes::encode(mnemonic, mode, variant)
+    }
+
+    /// Decode an opcode which does not correspond to any documented instruction, returning
+    /// its undocumented NMOS 6502 behaviour.
+    pub fn try_from_illegal_byte(code: Byte) -> Option<&'static dyn Instruction> {
+        ILLEGAL_OPCODES[code as usize]
+    }
+
+    /// Whether an undocumented opcode is unstable, i.e. its behaviour differs between chips
+    /// (ANE, LXA, SHA, SHX, SHY and TAS).
+    pub fn is_unstable(code: Byte) -> bool {
+        matches!(code, 0x8B | 0xAB | 0x93 | 0x9F | 0x9E | 0x9C | 0x9B)
+    }
+
+    /// The addressing mode of an opcode, following the layout of the NMOS 6502 opcode
+    /// matrix. This also holds for illegal opcodes, the ones which halt the CPU being
+    /// reported as implicit.
+    pub fn addressing_mode(code: Byte) -> Addr {
+        let row = code >> 5;
+        let column = (code >> 2) & 0b111;
+        // Stores and loads of X (rows 4 and 5) index with Y rather than X.
+        let uses_y = row == 4 || row == 5;
+
+        match (code & 0b11, column) {
+            (0b00, 0b000) if code == 0x20 => Addr::Absolute,
+            (0b00, 0b000) if row >= 4 => Addr::Immediate,
+            (0b00, 0b000 | 0b010 | 0b110) => Addr::Implicit,
+            (0b00, 0b011) if code == 0x6C => Addr::Indirect,
+            (0b00, 0b100) => Addr::Relative,
+            (0b10, 0b000) if row >= 4 => Addr::Immediate,
+            (0b10, 0b000 | 0b100 | 0b110) => Addr::Implicit,
+            (0b10, 0b010) if row < 4 => Addr::Accummulator,
+            (0b10, 0b010) => Addr::Implicit,
+            (0b10 | 0b11, 0b101) if uses_y => Addr::ZeroPageY,
+            (0b10 | 0b11, 0b111) if uses_y => Addr::AbsoluteY,
+            (0b01 | 0b11, 0b000) => Addr::XIndirect,
+            (0b01 | 0b11, 0b010) => Addr::Immediate,
+            (0b01 | 0b11, 0b100) => Addr::IndirectY,
+            (0b01 | 0b11, 0b110) => Addr::AbsoluteY,
+            (_, 0b001) => Addr::ZeroPage,
+            (_, 0b011) => Addr::Absolute,
+            (_, 0b101) => Addr::ZeroPageX,
+            (_, _) => Addr::AbsoluteX,
+        }
+    }
+
+    /// The instruction for each opcode, used to build [`OPCODES`].
+    const fn instruction(code: Byte) -> Option<&'static dyn Instruction> {
+        let ins: &'static dyn Instruction = match code {
             // Load / Store
-            0xA9 => Box::new(LDA(Addr::Immediate)),
-            0xA5 => Box::new(LDA(Addr::ZeroPage)),
-            0xB5 => Box::new(LDA(Addr::ZeroPageX)),
-            0xAD => Box::new(LDA(Addr::Absolute)),
-            0xBD => Box::new(LDA(Addr::AbsoluteX)),
-            0xB9 => Box::new(LDA(Addr::AbsoluteY)),
-            0xA1 => Box::new(LDA(Addr::XIndirect)),
-            0xB1 => Box::new(LDA(Addr::IndirectY)),
-
-            0xA2 => Box::new(LDX(Addr::Immediate)),
-            0xA6 => Box::new(LDX(Addr::ZeroPage)),
-            0xB6 => Box::new(LDX(Addr::ZeroPageY)),
-            0xAE => Box::new(LDX(Addr::Absolute)),
-            0xBE => Box::new(LDX(Addr::AbsoluteY)),
-
-            0xA0 => Box::new(LDY(Addr::Immediate)),
-            0xA4 => Box::new(LDY(Addr::ZeroPage)),
-            0xB4 => Box::new(LDY(Addr::ZeroPageX)),
-            0xAC => Box::new(LDY(Addr::Absolute)),
-            0xBC => Box::new(LDY(Addr::AbsoluteX)),
-
-            0x85 => Box::new(STA(Addr::ZeroPage)),
-            0x95 => Box::new(STA(Addr::ZeroPageX)),
-            0x8D => Box::new(STA(Addr::Absolute)),
-            0x9D => Box::new(STA(Addr::AbsoluteX)),
-            0x99 => Box::new(STA(Addr::AbsoluteY)),
-            0x81 => Box::new(STA(Addr::XIndirect)),
-            0x91 => Box::new(STA(Addr::IndirectY)),
-
-            0x86 => Box::new(STX(Addr::ZeroPage)),
-            0x96 => Box::new(STX(Addr::ZeroPageY)),
-            0x8E => Box::new(STX(Addr::Absolute)),
-
-            0x84 => Box::new(STY(Addr::ZeroPage)),
-            0x94 => Box::new(STY(Addr::ZeroPageX)),
-            0x8C => Box::new(STY(Addr::Absolute)),
+            0xA9 => &LDA(Addr::Immediate),
+            0xA5 => &LDA(Addr::ZeroPage),
+            0xB5 => &LDA(Addr::ZeroPageX),
+            0xAD => &LDA(Addr::Absolute),
+            0xBD => &LDA(Addr::AbsoluteX),
+            0xB9 => &LDA(Addr::AbsoluteY),
+            0xA1 => &LDA(Addr::XIndirect),
+            0xB1 => &LDA(Addr::IndirectY),
+
+            0xA2 => &LDX(Addr::Immediate),
+            0xA6 => &LDX(Addr::ZeroPage),
+            0xB6 => &LDX(Addr::ZeroPageY),
+            0xAE => &LDX(Addr::Absolute),
+            0xBE => &LDX(Addr::AbsoluteY),
+
+            0xA0 => &LDY(Addr::Immediate),
+            0xA4 => &LDY(Addr::ZeroPage),
+            0xB4 => &LDY(Addr::ZeroPageX),
+            0xAC => &LDY(Addr::Absolute),
+            0xBC => &LDY(Addr::AbsoluteX),
+
+            0x85 => &STA(Addr::ZeroPage),
+            0x95 => &STA(Addr::ZeroPageX),
+            0x8D => &STA(Addr::Absolute),
+            0x9D => &STA(Addr::AbsoluteX),
+            0x99 => &STA(Addr::AbsoluteY),
+            0x81 => &STA(Addr::XIndirect),
+            0x91 => &STA(Addr::IndirectY),
+
+            0x86 => &STX(Addr::ZeroPage),
+            0x96 => &STX(Addr::ZeroPageY),
+            0x8E => &STX(Addr::Absolute),
+
+            0x84 => &STY(Addr::ZeroPage),
+            0x94 => &STY(Addr::ZeroPageX),
+            0x8C => &STY(Addr::Absolute),
 
 
             // Register Transfers
-            0xAA => Box::new(TAX(Addr::Implicit)),
+            0xAA => &TAX(Addr::Implicit),
 
-            0xA8 => Box::new(TAY(Addr::Implicit)),
+            0xA8 => &TAY(Addr::Implicit),
 
-            0x8A => Box::new(TXA(Addr::Implicit)),
+            0x8A => &TXA(Addr::Implicit),
 
-            0x98 => Box::new(TYA(Addr::Implicit)),
+            0x98 => &TYA(Addr::Implicit),
 
 
             // Stack operations
-            0xBA => Box::new(TSX(Addr::Implicit)),
+            0xBA => &TSX(Addr::Implicit),
 
-            0x9A => Box::new(TXS(Addr::Implicit)),
+            0x9A => &TXS(Addr::Implicit),
 
-            0x48 => Box::new(PHA(Addr::Implicit)),
+            0x48 => &PHA(Addr::Implicit),
 
-            0x08 => Box::new(PHP(Addr::Implicit)),
+            0x08 => &PHP(Addr::Implicit),
 
-            0x68 => Box::new(PLA(Addr::Implicit)),
+            0x68 => &PLA(Addr::Implicit),
 
-            0x28 => Box::new(PLP(Addr::Implicit)),
+            0x28 => &PLP(Addr::Implicit),
 
 
             // Logical
-            0x29 => Box::new(AND(Addr::Immediate)),
-            0x25 => Box::new(AND(Addr::ZeroPage)),
-            0x35 => Box::new(AND(Addr::ZeroPageX)),
-            0x2D => Box::new(AND(Addr::Absolute)),
-            0x3D => Box::new(AND(Addr::AbsoluteX)),
-            0x39 => Box::new(AND(Addr::AbsoluteY)),
-            0x21 => Box::new(AND(Addr::XIndirect)),
-            0x31 => Box::new(AND(Addr::IndirectY)),
-
-            0x49 => Box::new(EOR(Addr::Immediate)),
-            0x45 => Box::new(EOR(Addr::ZeroPage)),
-            0x55 => Box::new(EOR(Addr::ZeroPageX)),
-            0x4D => Box::new(EOR(Addr::Absolute)),
-            0x5D => Box::new(EOR(Addr::AbsoluteX)),
-            0x59 => Box::new(EOR(Addr::AbsoluteY)),
-            0x41 => Box::new(EOR(Addr::XIndirect)),
-            0x51 => Box::new(EOR(Addr::IndirectY)),
-
-            0x09 => Box::new(ORA(Addr::Immediate)),
-            0x05 => Box::new(ORA(Addr::ZeroPage)),
-            0x15 => Box::new(ORA(Addr::ZeroPageX)),
-            0x0D => Box::new(ORA(Addr::Absolute)),
-            0x1D => Box::new(ORA(Addr::AbsoluteX)),
-            0x19 => Box::new(ORA(Addr::AbsoluteY)),
-            0x01 => Box::new(ORA(Addr::XIndirect)),
-            0x11 => Box::new(ORA(Addr::IndirectY)),
-
-            0x24 => Box::new(BIT(Addr::ZeroPage)),
-            0x2C => Box::new(BIT(Addr::Absolute)),
+            0x29 => &AND(Addr::Immediate),
+            0x25 => &AND(Addr::ZeroPage),
+            0x35 => &AND(Addr::ZeroPageX),
+            0x2D => &AND(Addr::Absolute),
+            0x3D => &AND(Addr::AbsoluteX),
+            0x39 => &AND(Addr::AbsoluteY),
+            0x21 => &AND(Addr::XIndirect),
+            0x31 => &AND(Addr::IndirectY),
+
+            0x49 => &EOR(Addr::Immediate),
+            0x45 => &EOR(Addr::ZeroPage),
+            0x55 => &EOR(Addr::ZeroPageX),
+            0x4D => &EOR(Addr::Absolute),
+            0x5D => &EOR(Addr::AbsoluteX),
+            0x59 => &EOR(Addr::AbsoluteY),
+            0x41 => &EOR(Addr::XIndirect),
+            0x51 => &EOR(Addr::IndirectY),
+
+            0x09 => &ORA(Addr::Immediate),
+            0x05 => &ORA(Addr::ZeroPage),
+            0x15 => &ORA(Addr::ZeroPageX),
+            0x0D => &ORA(Addr::Absolute),
+            0x1D => &ORA(Addr::AbsoluteX),
+            0x19 => &ORA(Addr::AbsoluteY),
+            0x01 => &ORA(Addr::XIndirect),
+            0x11 => &ORA(Addr::IndirectY),
+
+            0x24 => &BIT(Addr::ZeroPage),
+            0x2C => &BIT(Addr::Absolute),
+
+
+            // Arithmetic
+            0x69 => &ADC(Addr::Immediate),
+            0x65 => &ADC(Addr::ZeroPage),
+            0x75 => &ADC(Addr::ZeroPageX),
+            0x6D => &ADC(Addr::Absolute),
+            0x7D => &ADC(Addr::AbsoluteX),
+            0x79 => &ADC(Addr::AbsoluteY),
+            0x61 => &ADC(Addr::XIndirect),
+            0x71 => &ADC(Addr::IndirectY),
+
+            0xE9 => &SBC(Addr::Immediate),
+            0xE5 => &SBC(Addr::ZeroPage),
+            0xF5 => &SBC(Addr::ZeroPageX),
+            0xED => &SBC(Addr::Absolute),
+            0xFD => &SBC(Addr::AbsoluteX),
+            0xF9 => &SBC(Addr::AbsoluteY),
+            0xE1 => &SBC(Addr::XIndirect),
+            0xF1 => &SBC(Addr::IndirectY),
+
+            0xC9 => &CMP(Addr::Immediate),
+            0xC5 => &CMP(Addr::ZeroPage),
+            0xD5 => &CMP(Addr::ZeroPageX),
+            0xCD => &CMP(Addr::Absolute),
+            0xDD => &CMP(Addr::AbsoluteX),
+            0xD9 => &CMP(Addr::AbsoluteY),
+            0xC1 => &CMP(Addr::XIndirect),
+            0xD1 => &CMP(Addr::IndirectY),
+
+            0xE0 => &CPX(Addr::Immediate),
+            0xE4 => &CPX(Addr::ZeroPage),
+            0xEC => &CPX(Addr::Absolute),
+
+            0xC0 => &CPY(Addr::Immediate),
+            0xC4 => &CPY(Addr::ZeroPage),
+            0xCC => &CPY(Addr::Absolute),
 
 
             // Increments & Decrements
-            0xE6 => Box::new(INC(Addr::ZeroPage)),
-            0xF6 => Box::new(INC(Addr::ZeroPageX)),
-            0xEE => Box::new(INC(Addr::Absolute)),
-            0xFE => Box::new(INC(Addr::AbsoluteX)),
+            0xE6 => &INC(Addr::ZeroPage),
+            0xF6 => &INC(Addr::ZeroPageX),
+            0xEE => &INC(Addr::Absolute),
+            0xFE => &INC(Addr::AbsoluteX),
+
+            0xE8 => &INX(Addr::Implicit),
+
+            0xC8 => &INY(Addr::Implicit),
+
+            0xC6 => &DEC(Addr::ZeroPage),
+            0xD6 => &DEC(Addr::ZeroPageX),
+            0xCE => &DEC(Addr::Absolute),
+            0xDE => &DEC(Addr::AbsoluteX),
+
+            0xCA => &DEX(Addr::Implicit),
+
+            0x88 => &DEY(Addr::Implicit),
 
-            0xE8 => Box::new(INX(Addr::Implicit)),
 
-            0xC8 => Box::new(INY(Addr::Implicit)),
+            // Shifts
+            0x0A => &ASL(Addr::Accummulator),
+            0x06 => &ASL(Addr::ZeroPage),
+            0x16 => &ASL(Addr::ZeroPageX),
+            0x0E => &ASL(Addr::Absolute),
+            0x1E => &ASL(Addr::AbsoluteX),
 
-            0xC6 => Box::new(DEC(Addr::ZeroPage)),
-            0xD6 => Box::new(DEC(Addr::ZeroPageX)),
-            0xCE => Box::new(DEC(Addr::Absolute)),
-            0xDE => Box::new(DEC(Addr::AbsoluteX)),
+            0x4A => &LSR(Addr::Accummulator),
+            0x46 => &LSR(Addr::ZeroPage),
+            0x56 => &LSR(Addr::ZeroPageX),
+            0x4E => &LSR(Addr::Absolute),
+            0x5E => &LSR(Addr::AbsoluteX),
 
-            0xCA => Box::new(DEX(Addr::Implicit)),
+            0x2A => &ROL(Addr::Accummulator),
+            0x26 => &ROL(Addr::ZeroPage),
+            0x36 => &ROL(Addr::ZeroPageX),
+            0x2E => &ROL(Addr::Absolute),
+            0x3E => &ROL(Addr::AbsoluteX),
 
-            0x88 => Box::new(DEY(Addr::Implicit)),
+            0x6A => &ROR(Addr::Accummulator),
+            0x66 => &ROR(Addr::ZeroPage),
+            0x76 => &ROR(Addr::ZeroPageX),
+            0x6E => &ROR(Addr::Absolute),
+            0x7E => &ROR(Addr::AbsoluteX),
 
 
             // Jumps & Calls
-            0x20 => Box::new(JSR(Addr::Absolute)),
+            0x4C => &JMP(Addr::Absolute),
+            0x6C => &JMP(Addr::Indirect),
+
+            0x20 => &JSR(Addr::Absolute),
+
+            0x60 => &RTS(Addr::Implicit),
+
+
+            // Branches
+            0x90 => &BCC(Addr::Relative),
+
+            0xB0 => &BCS(Addr::Relative),
+
+            0xF0 => &BEQ(Addr::Relative),
+
+            0x30 => &BMI(Addr::Relative),
+
+            0xD0 => &BNE(Addr::Relative),
+
+            0x10 => &BPL(Addr::Relative),
+
+            0x50 => &BVC(Addr::Relative),
+
+            0x70 => &BVS(Addr::Relative),
 
 
             // Status Flag Changes
-            0x18 => Box::new(CLC(Addr::Implicit)),
+            0x18 => &CLC(Addr::Implicit),
 
-            0xD8 => Box::new(CLD(Addr::Implicit)),
+            0xD8 => &CLD(Addr::Implicit),
 
-            0x58 => Box::new(CLI(Addr::Implicit)),
+            0x58 => &CLI(Addr::Implicit),
 
-            0xB8 => Box::new(CLV(Addr::Implicit)),
+            0xB8 => &CLV(Addr::Implicit),
 
-            0x38 => Box::new(SEC(Addr::Implicit)),
+            0x38 => &SEC(Addr::Implicit),
 
-            0xF8 => Box::new(SED(Addr::Implicit)),
+            0xF8 => &SED(Addr::Implicit),
 
-            0x78 => Box::new(SEI(Addr::Implicit)),
+            0x78 => &SEI(Addr::Implicit),
 
 
             // System Functions
-            0x00 => Box::new(BRK(Addr::Implicit)),
-            _ => panic!()
+            0x00 => &BRK(Addr::Implicit),
+
+            0xEA => &NOP(Addr::Implicit),
+
+            0x40 => &RTI(Addr::Implicit),
+            _ => return None
+        };
+        Some(ins)
+    }
+
+    /// The instruction for each opcode of the 65C02, used to build [`CMOS_OPCODES`].
+    const fn cmos_instruction(code: Byte) -> &'static dyn Instruction {
+        match code {
+            // Load / Store
+            0xB2 => &LDA(Addr::ZeroPageIndirect),
+
+            0x92 => &STA(Addr::ZeroPageIndirect),
+
+            0x64 => &STZ(Addr::ZeroPage),
+            0x74 => &STZ(Addr::ZeroPageX),
+            0x9C => &STZ(Addr::Absolute),
+            0x9E => &STZ(Addr::AbsoluteX),
+
+
+            // Stack operations
+            0xDA => &PHX(Addr::Implicit),
+
+            0x5A => &PHY(Addr::Implicit),
+
+            0xFA => &PLX(Addr::Implicit),
+
+            0x7A => &PLY(Addr::Implicit),
+
+
+            // Logical
+            0x32 => &AND(Addr::ZeroPageIndirect),
+
+            0x52 => &EOR(Addr::ZeroPageIndirect),
+
+            0x12 => &ORA(Addr::ZeroPageIndirect),
+
+            0x89 => &BIT(Addr::Immediate),
+            0x34 => &BIT(Addr::ZeroPageX),
+            0x3C => &BIT(Addr::AbsoluteX),
+
+            0x14 => &TRB(Addr::ZeroPage),
+            0x1C => &TRB(Addr::Absolute),
+
+            0x04 => &TSB(Addr::ZeroPage),
+            0x0C => &TSB(Addr::Absolute),
+
+
+            // Arithmetic
+            0x72 => &ADC(Addr::ZeroPageIndirect),
+
+            0xF2 => &SBC(Addr::ZeroPageIndirect),
+
+            0xD2 => &CMP(Addr::ZeroPageIndirect),
+
+
+            // Increments & Decrements
+            0x1A => &INC(Addr::Accummulator),
+
+            0x3A => &DEC(Addr::Accummulator),
+
+
+            // Jumps & Calls
+            0x7C => &JMP(Addr::AbsoluteXIndirect),
+
+
+            // Branches
+            0x80 => &BRA(Addr::Relative),
+            _ => match Self::instruction(code) {
+                Some(ins) => ins,
+                None => &RESERVED[code as usize],
+            }
         }
     }
+
+    /// The instruction for each opcode of the Rockwell 65C02, used to build
+    /// [`ROCKWELL_OPCODES`].
+    const fn rockwell_instruction(code: Byte) -> &'static dyn Instruction {
+        match code {
+            // Bit manipulation
+            0x07 => &RMB(Addr::ZeroPage, 0),
+            0x17 => &RMB(Addr::ZeroPage, 1),
+            0x27 => &RMB(Addr::ZeroPage, 2),
+            0x37 => &RMB(Addr::ZeroPage, 3),
+            0x47 => &RMB(Addr::ZeroPage, 4),
+            0x57 => &RMB(Addr::ZeroPage, 5),
+            0x67 => &RMB(Addr::ZeroPage, 6),
+            0x77 => &RMB(Addr::ZeroPage, 7),
+
+            0x87 => &SMB(Addr::ZeroPage, 0),
+            0x97 => &SMB(Addr::ZeroPage, 1),
+            0xA7 => &SMB(Addr::ZeroPage, 2),
+            0xB7 => &SMB(Addr::ZeroPage, 3),
+            0xC7 => &SMB(Addr::ZeroPage, 4),
+            0xD7 => &SMB(Addr::ZeroPage, 5),
+            0xE7 => &SMB(Addr::ZeroPage, 6),
+            0xF7 => &SMB(Addr::ZeroPage, 7),
+
+
+            // Branches
+            0x0F => &BBR(Addr::ZeroPageRelative, 0),
+            0x1F => &BBR(Addr::ZeroPageRelative, 1),
+            0x2F => &BBR(Addr::ZeroPageRelative, 2),
+            0x3F => &BBR(Addr::ZeroPageRelative, 3),
+            0x4F => &BBR(Addr::ZeroPageRelative, 4),
+            0x5F => &BBR(Addr::ZeroPageRelative, 5),
+            0x6F => &BBR(Addr::ZeroPageRelative, 6),
+            0x7F => &BBR(Addr::ZeroPageRelative, 7),
+
+            0x8F => &BBS(Addr::ZeroPageRelative, 0),
+            0x9F => &BBS(Addr::ZeroPageRelative, 1),
+            0xAF => &BBS(Addr::ZeroPageRelative, 2),
+            0xBF => &BBS(Addr::ZeroPageRelative, 3),
+            0xCF => &BBS(Addr::ZeroPageRelative, 4),
+            0xDF => &BBS(Addr::ZeroPageRelative, 5),
+            0xEF => &BBS(Addr::ZeroPageRelative, 6),
+            0xFF => &BBS(Addr::ZeroPageRelative, 7),
+            _ => Self::cmos_instruction(code),
+        }
+    }
+
+    /// The instruction for each opcode of the WDC 65C02, used to build [`WDC_OPCODES`].
+    const fn wdc_instruction(code: Byte) -> &'static dyn Instruction {
+        match code {
+            // System Functions
+            0xCB => &WAI(Addr::Implicit),
+
+            0xDB => &STP(Addr::Implicit),
+            _ => Self::rockwell_instruction(code),
+        }
+    }
+
+    /// The undocumented instruction for each opcode, used to build [`ILLEGAL_OPCODES`].
+    const fn illegal_instruction(code: Byte) -> Option<&'static dyn Instruction> {
+        let ins: &'static dyn Instruction = match code {
+            // Combined read-modify-write
+            0x07 => &SLO(Addr::ZeroPage),
+            0x17 => &SLO(Addr::ZeroPageX),
+            0x0F => &SLO(Addr::Absolute),
+            0x1F => &SLO(Addr::AbsoluteX),
+            0x1B => &SLO(Addr::AbsoluteY),
+            0x03 => &SLO(Addr::XIndirect),
+            0x13 => &SLO(Addr::IndirectY),
+
+            0x27 => &RLA(Addr::ZeroPage),
+            0x37 => &RLA(Addr::ZeroPageX),
+            0x2F => &RLA(Addr::Absolute),
+            0x3F => &RLA(Addr::AbsoluteX),
+            0x3B => &RLA(Addr::AbsoluteY),
+            0x23 => &RLA(Addr::XIndirect),
+            0x33 => &RLA(Addr::IndirectY),
+
+            0x47 => &SRE(Addr::ZeroPage),
+            0x57 => &SRE(Addr::ZeroPageX),
+            0x4F => &SRE(Addr::Absolute),
+            0x5F => &SRE(Addr::AbsoluteX),
+            0x5B => &SRE(Addr::AbsoluteY),
+            0x43 => &SRE(Addr::XIndirect),
+            0x53 => &SRE(Addr::IndirectY),
+
+            0x67 => &RRA(Addr::ZeroPage),
+            0x77 => &RRA(Addr::ZeroPageX),
+            0x6F => &RRA(Addr::Absolute),
+            0x7F => &RRA(Addr::AbsoluteX),
+            0x7B => &RRA(Addr::AbsoluteY),
+            0x63 => &RRA(Addr::XIndirect),
+            0x73 => &RRA(Addr::IndirectY),
+
+            0xC7 => &DCP(Addr::ZeroPage),
+            0xD7 => &DCP(Addr::ZeroPageX),
+            0xCF => &DCP(Addr::Absolute),
+            0xDF => &DCP(Addr::AbsoluteX),
+            0xDB => &DCP(Addr::AbsoluteY),
+            0xC3 => &DCP(Addr::XIndirect),
+            0xD3 => &DCP(Addr::IndirectY),
+
+            0xE7 => &ISC(Addr::ZeroPage),
+            0xF7 => &ISC(Addr::ZeroPageX),
+            0xEF => &ISC(Addr::Absolute),
+            0xFF => &ISC(Addr::AbsoluteX),
+            0xFB => &ISC(Addr::AbsoluteY),
+            0xE3 => &ISC(Addr::XIndirect),
+            0xF3 => &ISC(Addr::IndirectY),
+
+
+            // Load / Store
+            0xA7 => &LAX(Addr::ZeroPage),
+            0xB7 => &LAX(Addr::ZeroPageY),
+            0xAF => &LAX(Addr::Absolute),
+            0xBF => &LAX(Addr::AbsoluteY),
+            0xA3 => &LAX(Addr::XIndirect),
+            0xB3 => &LAX(Addr::IndirectY),
+
+            0x87 => &SAX(Addr::ZeroPage),
+            0x97 => &SAX(Addr::ZeroPageY),
+            0x8F => &SAX(Addr::Absolute),
+            0x83 => &SAX(Addr::XIndirect),
+
+            0xBB => &LAS(Addr::AbsoluteY),
+
+
+            // Immediate
+            0x0B | 0x2B => &ANC(Addr::Immediate),
+
+            0x4B => &ALR(Addr::Immediate),
+
+            0x6B => &ARR(Addr::Immediate),
+
+            0xCB => &SBX(Addr::Immediate),
+
+            0xEB => &SBC(Addr::Immediate),
+
+
+            // Unstable
+            0x8B => &ANE(Addr::Immediate),
+
+            0xAB => &LXA(Addr::Immediate),
+
+            0x9F => &SHA(Addr::AbsoluteY),
+            0x93 => &SHA(Addr::IndirectY),
+
+            0x9E => &SHX(Addr::AbsoluteY),
+
+            0x9C => &SHY(Addr::AbsoluteX),
+
+            0x9B => &TAS(Addr::AbsoluteY),
+
+
+            // No Operation
+            0x1A | 0x3A | 0x5A | 0x7A | 0xDA | 0xFA => &NOP(Addr::Implicit),
+            0x80 | 0x82 | 0x89 | 0xC2 | 0xE2 => &NOP(Addr::Immediate),
+            0x04 | 0x44 | 0x64 => &NOP(Addr::ZeroPage),
+            0x14 | 0x34 | 0x54 | 0x74 | 0xD4 | 0xF4 => &NOP(Addr::ZeroPageX),
+            0x0C => &NOP(Addr::Absolute),
+            0x1C | 0x3C | 0x5C | 0x7C | 0xDC | 0xFC => &NOP(Addr::AbsoluteX),
+
+
+            // Halt
+            0x02 | 0x12 | 0x22 | 0x32 | 0x42 | 0x52 | 0x62 | 0x72 | 0x92 | 0xB2 | 0xD2 | 0xF2 => {
+                &JAM(Addr::Implicit)
+            },
+            _ => return None
+        };
+        Some(ins)
+    }
 }
 
 pub trait DecodeIns {
     /// Decode instruction.
-    fn decode(self) -> Box<dyn Instruction>;
+    fn decode(self) -> &'static dyn Instruction;
+
+    /// Decode instruction for the given CPU `variant`.
+    fn decode_for(self, variant: Variant) -> &'static dyn Instruction;
 }
 
 impl DecodeIns for Byte {
-    fn decode(self) -> Box<dyn Instruction> {
+    fn decode(self) -> &'static dyn Instruction {
         InstructionDecoder::from_byte(self)
     }
+
+    fn decode_for(self, variant: Variant) -> &'static dyn Instruction {
+        InstructionDecoder::try_from_byte_for(self, variant).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+    use crate::cpu::{IllegalOpcodePolicy, ResetMode};
+
+    /// Every opcode documented for the NMOS 6502.
+    const DOCUMENTED_OPCODES: [Byte; 151] = [
+        0x00, 0x01, 0x05, 0x06, 0x08, 0x09, 0x0A, 0x0D, 0x0E, 0x10, 0x11, 0x15, 0x16, 0x18,
+        0x19, 0x1D, 0x1E, 0x20, 0x21, 0x24, 0x25, 0x26, 0x28, 0x29, 0x2A, 0x2C, 0x2D, 0x2E,
+        0x30, 0x31, 0x35, 0x36, 0x38, 0x39, 0x3D, 0x3E, 0x40, 0x41, 0x45, 0x46, 0x48, 0x49,
+        0x4A, 0x4C, 0x4D, 0x4E, 0x50, 0x51, 0x55, 0x56, 0x58, 0x59, 0x5D, 0x5E, 0x60, 0x61,
+        0x65, 0x66, 0x68, 0x69, 0x6A, 0x6C, 0x6D, 0x6E, 0x70, 0x71, 0x75, 0x76, 0x78, 0x79,
+        0x7D, 0x7E, 0x81, 0x84, 0x85, 0x86, 0x88, 0x8A, 0x8C, 0x8D, 0x8E, 0x90, 0x91, 0x94,
+        0x95, 0x96, 0x98, 0x99, 0x9A, 0x9D, 0xA0, 0xA1, 0xA2, 0xA4, 0xA5, 0xA6, 0xA8, 0xA9,
+        0xAA, 0xAC, 0xAD, 0xAE, 0xB0, 0xB1, 0xB4, 0xB5, 0xB6, 0xB8, 0xB9, 0xBA, 0xBC, 0xBD,
+        0xBE, 0xC0, 0xC1, 0xC4, 0xC5, 0xC6, 0xC8, 0xC9, 0xCA, 0xCC, 0xCD, 0xCE, 0xD0, 0xD1,
+        0xD5, 0xD6, 0xD8, 0xD9, 0xDD, 0xDE, 0xE0, 0xE1, 0xE4, 0xE5, 0xE6, 0xE8, 0xE9, 0xEA,
+        0xEC, 0xED, 0xEE, 0xF0, 0xF1, 0xF5, 0xF6, 0xF8, 0xF9, 0xFD, 0xFE,
+    ];
+
+    /// Extra cycles an instruction may take on top of its base timing.
+    enum Penalty {
+        /// The timing never changes.
+        Fixed,
+        /// +1 if indexing crosses a page boundary.
+        PageCross,
+        /// +1 if the branch is taken, +2 if it is taken to a new page.
+        Branch,
+    }
+
+    use Penalty::*;
+
+    /// Published timings of every documented opcode.
+    const TIMINGS: [(Byte, u32, Penalty); 151] = [
+        // ADC
+        (0x69, 2, Fixed), (0x65, 3, Fixed), (0x75, 4, Fixed), (0x6D, 4, Fixed),
+        (0x7D, 4, PageCross), (0x79, 4, PageCross), (0x61, 6, Fixed), (0x71, 5, PageCross),
+        // AND
+        (0x29, 2, Fixed), (0x25, 3, Fixed), (0x35, 4, Fixed), (0x2D, 4, Fixed),
+        (0x3D, 4, PageCross), (0x39, 4, PageCross), (0x21, 6, Fixed), (0x31, 5, PageCross),
+        // ASL
+        (0x0A, 2, Fixed), (0x06, 5, Fixed), (0x16, 6, Fixed), (0x0E, 6, Fixed),
+        (0x1E, 7, Fixed),
+        // BCC
+        (0x90, 2, Branch),
+        // BCS
+        (0xB0, 2, Branch),
+        // BEQ
+        (0xF0, 2, Branch),
+        // BIT
+        (0x24, 3, Fixed), (0x2C, 4, Fixed),
+        // BMI
+        (0x30, 2, Branch),
+        // BNE
+        (0xD0, 2, Branch),
+        // BPL
+        (0x10, 2, Branch),
+        // BRK
+        (0x00, 7, Fixed),
+        // BVC
+        (0x50, 2, Branch),
+        // BVS
+        (0x70, 2, Branch),
+        // CLC
+        (0x18, 2, Fixed),
+        // CLD
+        (0xD8, 2, Fixed),
+        // CLI
+        (0x58, 2, Fixed),
+        // CLV
+        (0xB8, 2, Fixed),
+        // CMP
+        (0xC9, 2, Fixed), (0xC5, 3, Fixed), (0xD5, 4, Fixed), (0xCD, 4, Fixed),
+        (0xDD, 4, PageCross), (0xD9, 4, PageCross), (0xC1, 6, Fixed), (0xD1, 5, PageCross),
+        // CPX
+        (0xE0, 2, Fixed), (0xE4, 3, Fixed), (0xEC, 4, Fixed),
+        // CPY
+        (0xC0, 2, Fixed), (0xC4, 3, Fixed), (0xCC, 4, Fixed),
+        // DEC
+        (0xC6, 5, Fixed), (0xD6, 6, Fixed), (0xCE, 6, Fixed), (0xDE, 7, Fixed),
+        // DEX
+        (0xCA, 2, Fixed),
+        // DEY
+        (0x88, 2, Fixed),
+        // EOR
+        (0x49, 2, Fixed), (0x45, 3, Fixed), (0x55, 4, Fixed), (0x4D, 4, Fixed),
+        (0x5D, 4, PageCross), (0x59, 4, PageCross), (0x41, 6, Fixed), (0x51, 5, PageCross),
+        // INC
+        (0xE6, 5, Fixed), (0xF6, 6, Fixed), (0xEE, 6, Fixed), (0xFE, 7, Fixed),
+        // INX
+        (0xE8, 2, Fixed),
+        // INY
+        (0xC8, 2, Fixed),
+        // JMP
+        (0x4C, 3, Fixed), (0x6C, 5, Fixed),
+        // JSR
+        (0x20, 6, Fixed),
+        // LDA
+        (0xA9, 2, Fixed), (0xA5, 3, Fixed), (0xB5, 4, Fixed), (0xAD, 4, Fixed),
+        (0xBD, 4, PageCross), (0xB9, 4, PageCross), (0xA1, 6, Fixed), (0xB1, 5, PageCross),
+        // LDX
+        (0xA2, 2, Fixed), (0xA6, 3, Fixed), (0xB6, 4, Fixed), (0xAE, 4, Fixed),
+        (0xBE, 4, PageCross),
+        // LDY
+        (0xA0, 2, Fixed), (0xA4, 3, Fixed), (0xB4, 4, Fixed), (0xAC, 4, Fixed),
+        (0xBC, 4, PageCross),
+        // LSR
+        (0x4A, 2, Fixed), (0x46, 5, Fixed), (0x56, 6, Fixed), (0x4E, 6, Fixed),
+        (0x5E, 7, Fixed),
+        // NOP
+        (0xEA, 2, Fixed),
+        // ORA
+        (0x09, 2, Fixed), (0x05, 3, Fixed), (0x15, 4, Fixed), (0x0D, 4, Fixed),
+        (0x1D, 4, PageCross), (0x19, 4, PageCross), (0x01, 6, Fixed), (0x11, 5, PageCross),
+        // PHA
+        (0x48, 3, Fixed),
+        // PHP
+        (0x08, 3, Fixed),
+        // PLA
+        (0x68, 4, Fixed),
+        // PLP
+        (0x28, 4, Fixed),
+        // ROL
+        (0x2A, 2, Fixed), (0x26, 5, Fixed), (0x36, 6, Fixed), (0x2E, 6, Fixed),
+        (0x3E, 7, Fixed),
+        // ROR
+        (0x6A, 2, Fixed), (0x66, 5, Fixed), (0x76, 6, Fixed), (0x6E, 6, Fixed),
+        (0x7E, 7, Fixed),
+        // RTI
+        (0x40, 6, Fixed),
+        // RTS
+        (0x60, 6, Fixed),
+        // SBC
+        (0xE9, 2, Fixed), (0xE5, 3, Fixed), (0xF5, 4, Fixed), (0xED, 4, Fixed),
+        (0xFD, 4, PageCross), (0xF9, 4, PageCross), (0xE1, 6, Fixed), (0xF1, 5, PageCross),
+        // SEC
+        (0x38, 2, Fixed),
+        // SED
+        (0xF8, 2, Fixed),
+        // SEI
+        (0x78, 2, Fixed),
+        // STA
+        (0x85, 3, Fixed), (0x95, 4, Fixed), (0x8D, 4, Fixed), (0x9D, 5, Fixed),
+        (0x99, 5, Fixed), (0x81, 6, Fixed), (0x91, 6, Fixed),
+        // STX
+        (0x86, 3, Fixed), (0x96, 4, Fixed), (0x8E, 4, Fixed),
+        // STY
+        (0x84, 3, Fixed), (0x94, 4, Fixed), (0x8C, 4, Fixed),
+        // TAX
+        (0xAA, 2, Fixed),
+        // TAY
+        (0xA8, 2, Fixed),
+        // TSX
+        (0xBA, 2, Fixed),
+        // TXA
+        (0x8A, 2, Fixed),
+        // TXS
+        (0x9A, 2, Fixed),
+        // TYA
+        (0x98, 2, Fixed),
+    ];
+
+    /// Published timings of every undocumented opcode which does not halt the CPU.
+    const ILLEGAL_TIMINGS: [(Byte, u32, Penalty); 93] = [
+        // SLO
+        (0x07, 5, Fixed), (0x17, 6, Fixed), (0x0F, 6, Fixed), (0x1F, 7, Fixed),
+        (0x1B, 7, Fixed), (0x03, 8, Fixed), (0x13, 8, Fixed),
+        // RLA
+        (0x27, 5, Fixed), (0x37, 6, Fixed), (0x2F, 6, Fixed), (0x3F, 7, Fixed),
+        (0x3B, 7, Fixed), (0x23, 8, Fixed), (0x33, 8, Fixed),
+        // SRE
+        (0x47, 5, Fixed), (0x57, 6, Fixed), (0x4F, 6, Fixed), (0x5F, 7, Fixed),
+        (0x5B, 7, Fixed), (0x43, 8, Fixed), (0x53, 8, Fixed),
+        // RRA
+        (0x67, 5, Fixed), (0x77, 6, Fixed), (0x6F, 6, Fixed), (0x7F, 7, Fixed),
+        (0x7B, 7, Fixed), (0x63, 8, Fixed), (0x73, 8, Fixed),
+        // DCP
+        (0xC7, 5, Fixed), (0xD7, 6, Fixed), (0xCF, 6, Fixed), (0xDF, 7, Fixed),
+        (0xDB, 7, Fixed), (0xC3, 8, Fixed), (0xD3, 8, Fixed),
+        // ISC
+        (0xE7, 5, Fixed), (0xF7, 6, Fixed), (0xEF, 6, Fixed), (0xFF, 7, Fixed),
+        (0xFB, 7, Fixed), (0xE3, 8, Fixed), (0xF3, 8, Fixed),
+        // LAX
+        (0xA7, 3, Fixed), (0xB7, 4, Fixed), (0xAF, 4, Fixed), (0xBF, 4, PageCross),
+        (0xA3, 6, Fixed), (0xB3, 5, PageCross),
+        // SAX
+        (0x87, 3, Fixed), (0x97, 4, Fixed), (0x8F, 4, Fixed), (0x83, 6, Fixed),
+        // LAS
+        (0xBB, 4, PageCross),
+        // ANC
+        (0x0B, 2, Fixed), (0x2B, 2, Fixed),
+        // ALR
+        (0x4B, 2, Fixed),
+        // ARR
+        (0x6B, 2, Fixed),
+        // SBX
+        (0xCB, 2, Fixed),
+        // SBC
+        (0xEB, 2, Fixed),
+        // ANE
+        (0x8B, 2, Fixed),
+        // LXA
+        (0xAB, 2, Fixed),
+        // SHA
+        (0x9F, 5, Fixed), (0x93, 6, Fixed),
+        // SHX
+        (0x9E, 5, Fixed),
+        // SHY
+        (0x9C, 5, Fixed),
+        // TAS
+        (0x9B, 5, Fixed),
+        // NOP
+        (0x1A, 2, Fixed), (0x3A, 2, Fixed), (0x5A, 2, Fixed), (0x7A, 2, Fixed),
+        (0xDA, 2, Fixed), (0xFA, 2, Fixed), (0x80, 2, Fixed), (0x82, 2, Fixed),
+        (0x89, 2, Fixed), (0xC2, 2, Fixed), (0xE2, 2, Fixed), (0x04, 3, Fixed),
+        (0x44, 3, Fixed), (0x64, 3, Fixed), (0x14, 4, Fixed), (0x34, 4, Fixed),
+        (0x54, 4, Fixed), (0x74, 4, Fixed), (0xD4, 4, Fixed), (0xF4, 4, Fixed),
+        (0x0C, 4, Fixed), (0x1C, 4, PageCross), (0x3C, 4, PageCross), (0x5C, 4, PageCross),
+        (0x7C, 4, PageCross), (0xDC, 4, PageCross), (0xFC, 4, PageCross),
+    ];
+
+    /// Published timings of the 65C02 opcodes which are new, or differ from the NMOS 6502.
+    const CMOS_TIMINGS: [(Byte, u32, Penalty); 46] = [
+        // ADC, AND, CMP, EOR, LDA, ORA, SBC, STA (zp)
+        (0x72, 5, Fixed), (0x32, 5, Fixed), (0xD2, 5, Fixed), (0x52, 5, Fixed),
+        (0xB2, 5, Fixed), (0x12, 5, Fixed), (0xF2, 5, Fixed), (0x92, 5, Fixed),
+        // ASL, LSR, ROL, ROR abs,X
+        (0x1E, 6, PageCross), (0x5E, 6, PageCross), (0x3E, 6, PageCross), (0x7E, 6, PageCross),
+        // BIT
+        (0x89, 2, Fixed), (0x34, 4, Fixed), (0x3C, 4, PageCross),
+        // BRA
+        (0x80, 3, Fixed),
+        // DEC A, INC A
+        (0x3A, 2, Fixed), (0x1A, 2, Fixed),
+        // JMP
+        (0x6C, 6, Fixed), (0x7C, 6, Fixed),
+        // PHX, PHY, PLX, PLY
+        (0xDA, 3, Fixed), (0x5A, 3, Fixed), (0xFA, 4, Fixed), (0x7A, 4, Fixed),
+        // STZ
+        (0x64, 3, Fixed), (0x74, 4, Fixed), (0x9C, 4, Fixed), (0x9E, 5, Fixed),
+        // TRB, TSB
+        (0x14, 5, Fixed), (0x1C, 6, Fixed), (0x04, 5, Fixed), (0x0C, 6, Fixed),
+        // Reserved
+        (0x02, 2, Fixed), (0x22, 2, Fixed), (0x42, 2, Fixed), (0x62, 2, Fixed),
+        (0x82, 2, Fixed), (0xC2, 2, Fixed), (0xE2, 2, Fixed), (0x44, 3, Fixed),
+        (0x54, 4, Fixed), (0xD4, 4, Fixed), (0xF4, 4, Fixed), (0x5C, 8, Fixed),
+        (0xDC, 4, Fixed), (0xFC, 4, Fixed),
+    ];
+
+    /// Published timings of the bit manipulation instructions of the Rockwell and WDC 65C02.
+    /// The zero page byte tested by BBR and BBS is $FF, so only BBS branches (to the next
+    /// instruction, which is on the same page).
+    const BIT_TIMINGS: [(Byte, u32, Penalty); 32] = [
+        // RMB
+        (0x07, 5, Fixed), (0x17, 5, Fixed), (0x27, 5, Fixed), (0x37, 5, Fixed),
+        (0x47, 5, Fixed), (0x57, 5, Fixed), (0x67, 5, Fixed), (0x77, 5, Fixed),
+        // SMB
+        (0x87, 5, Fixed), (0x97, 5, Fixed), (0xA7, 5, Fixed), (0xB7, 5, Fixed),
+        (0xC7, 5, Fixed), (0xD7, 5, Fixed), (0xE7, 5, Fixed), (0xF7, 5, Fixed),
+        // BBR
+        (0x0F, 5, Fixed), (0x1F, 5, Fixed), (0x2F, 5, Fixed), (0x3F, 5, Fixed),
+        (0x4F, 5, Fixed), (0x5F, 5, Fixed), (0x6F, 5, Fixed), (0x7F, 5, Fixed),
+        // BBS
+        (0x8F, 6, Fixed), (0x9F, 6, Fixed), (0xAF, 6, Fixed), (0xBF, 6, Fixed),
+        (0xCF, 6, Fixed), (0xDF, 6, Fixed), (0xEF, 6, Fixed), (0xFF, 6, Fixed),
+    ];
+
+    /// Runs a single instruction at $FFFC with operand bytes $FF and $00 following it.
+    /// With `index` set to 0 no page is crossed, with `index` set to 1 every indexed mode
+    /// crosses from page 0 to page 1 (the zero page pointer at $FF also points to $00FF).
+    fn run_timed(variant: Variant, code: Byte, index: Byte, flags: Byte) -> u32 {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+        cpu.variant = variant;
+
+        cpu.mem.write_byte(0xFFFC, code);
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0xFFFE, 0x00);
+        cpu.mem.write_byte(0x00FF, 0xFF);
+        cpu.reg.x = index;
+        cpu.reg.y = index;
+        cpu.flags = flags.into();
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Emulate;
+        cpu.step().unwrap();
+
+        cpu.cycles
+    }
+
+    fn assert_timings(variant: Variant, timings: impl IntoIterator<Item = (Byte, u32, Penalty)>) {
+        let run_timed = |code, index, flags| run_timed(variant, code, index, flags);
+        for (code, cycles, penalty) in timings {
+            match penalty {
+                Penalty::Fixed => {
+                    assert_eq!(run_timed(code, 0, 0), cycles, "opcode {code:#04X}");
+                    assert_eq!(run_timed(code, 1, 0), cycles, "opcode {code:#04X} (page crossed)");
+                },
+                Penalty::PageCross => {
+                    assert_eq!(run_timed(code, 0, 0), cycles, "opcode {code:#04X}");
+                    assert_eq!(run_timed(code, 1, 0), cycles + 1, "opcode {code:#04X} (page crossed)");
+                },
+                Penalty::Branch => {
+                    // The branch is taken with one of the flag settings, and jumps back
+                    // 1 byte from $FFFE, so it stays on the same page.
+                    let clear = run_timed(code, 0, 0x00);
+                    let set = run_timed(code, 0, 0xFF);
+                    assert_eq!(clear.min(set), cycles, "opcode {code:#04X} (not taken)");
+                    assert_eq!(clear.max(set), cycles + 1, "opcode {code:#04X} (taken)");
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn documented_opcode_timings() {
+        assert_timings(Variant::Nmos6502, TIMINGS);
+        assert_timings(Variant::Ricoh2A03, TIMINGS);
+    }
+
+    #[test]
+    fn illegal_opcode_timings() {
+        assert_timings(Variant::Nmos6502, ILLEGAL_TIMINGS);
+        assert_timings(Variant::Ricoh2A03, ILLEGAL_TIMINGS);
+    }
+
+    #[test]
+    fn cmos_opcode_timings() {
+        let changed = CMOS_TIMINGS.map(|(code, _, _)| code);
+        for variant in [Variant::Cmos65C02, Variant::Rockwell65C02, Variant::Wdc65C02] {
+            let unchanged = TIMINGS.into_iter().filter(|(code, _, _)| !changed.contains(code));
+            assert_timings(variant, unchanged);
+            assert_timings(variant, CMOS_TIMINGS);
+        }
+        assert_timings(Variant::Rockwell65C02, BIT_TIMINGS);
+        assert_timings(Variant::Wdc65C02, BIT_TIMINGS);
+        assert_timings(Variant::Wdc65C02, [(0xCB, 3, Fixed), (0xDB, 3, Fixed)]);
+
+        // The remaining reserved opcodes take a single byte and cycle.
+        for code in (0..=0xFF).filter(|code| code & 0b11 == 0b11) {
+            assert_eq!(run_timed(Variant::Cmos65C02, code, 0, 0), 1, "opcode {code:#04X}");
+            if code & 0b111 == 0b011 {
+                assert_eq!(run_timed(Variant::Rockwell65C02, code, 0, 0), 1, "opcode {code:#04X}");
+            }
+        }
+    }
+
+    #[test]
+    fn decode_documented_opcodes() {
+        for code in DOCUMENTED_OPCODES {
+            // Decoding must not panic, and the decoded instruction must encode back to the
+            // same opcode.
+            assert_eq!(code.decode().code().unwrap(), code, "opcode {code:#04X}");
+        }
+    }
+
+    #[test]
+    fn code_unsupported_mode() {
+        assert_eq!(TAX(Addr::Absolute).code(), Err(ExecError::UnsupportedMode(Addr::Absolute)));
+        assert_eq!(JSR(Addr::Indirect).code(), Err(ExecError::UnsupportedMode(Addr::Indirect)));
+        assert_eq!(RMB(Addr::ZeroPage, 8).code(), Err(ExecError::UnsupportedMode(Addr::ZeroPage)));
+        assert_eq!(JAM(Addr::Absolute).code(), Err(ExecError::UnsupportedMode(Addr::Absolute)));
+    }
+
+    #[test]
+    fn decode_illegal_opcodes() {
+        for code in 0..=0xFF {
+            // Every opcode is either documented or has an undocumented behaviour.
+            let documented = InstructionDecoder::try_from_byte(code).is_ok();
+            let illegal = InstructionDecoder::try_from_illegal_byte(code).is_some();
+            assert!(documented != illegal, "opcode {code:#04X}");
+            assert!(!InstructionDecoder::is_unstable(code) || illegal, "opcode {code:#04X}");
+        }
+    }
+
+    #[test]
+    fn decode_cmos_opcodes() {
+        for variant in [Variant::Cmos65C02, Variant::Rockwell65C02, Variant::Wdc65C02] {
+            for code in 0..=0xFF {
+                // Every opcode of the 65C02 is defined.
+                let ins = InstructionDecoder::try_from_byte_for(code, variant).unwrap();
+                assert_eq!(ins.code().unwrap(), code, "opcode {code:#04X} ({variant:?})");
+            }
+        }
+    }
+
+    #[test]
+    fn opcode_table() {
+        let variants = [
+            Variant::Nmos6502, Variant::Ricoh2A03, Variant::Cmos65C02, Variant::Rockwell65C02,
+            Variant::Wdc65C02,
+        ];
+        for variant in variants {
+            for code in 0..=0xFF {
+                let ins = match variant {
+                    Variant::Nmos6502 | Variant::Ricoh2A03 => {
+                        Some(InstructionDecoder::try_from_illegal_byte(code).unwrap_or_else(|| code.decode()))
+                    },
+                    _ => Some(InstructionDecoder::try_from_byte_for(code, variant).unwrap())
+                        .filter(|ins| !ptr::addr_eq(*ins, &RESERVED[code as usize])),
+                };
+                assert_eq!(
+                    opcodes::opcode(code, variant),
+                    ins.map(|ins| (ins.mnemonic(), ins.mode())),
+                    "opcode {code:#04X} ({variant:?})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encode_opcodes() {
+        let variants = [
+            Variant::Nmos6502, Variant::Ricoh2A03, Variant::Cmos65C02, Variant::Rockwell65C02,
+            Variant::Wdc65C02,
+        ];
+        for variant in variants {
+            for code in 0..=0xFF {
+                let ins = match variant {
+                    Variant::Nmos6502 | Variant::Ricoh2A03 => {
+                        InstructionDecoder::try_from_illegal_byte(code).unwrap_or_else(|| code.decode())
+                    },
+                    _ => InstructionDecoder::try_from_byte_for(code, variant).unwrap(),
+                };
+                let encoded = InstructionDecoder::encode(ins.mnemonic(), ins.mode(), variant);
+                if variant.is_cmos() && ptr::addr_eq(ins, &RESERVED[code as usize]) {
+                    // At best the documented NOP is encoded instead.
+                    assert_ne!(encoded, Some(code), "opcode {code:#04X} ({variant:?})");
+                } else {
+                    // Duplicate undocumented opcodes are encoded to the canonical one.
+                    assert_eq!(encoded, Some(ins.code().unwrap()), "opcode {code:#04X} ({variant:?})");
+                }
+            }
+        }
+
+        assert_eq!(InstructionDecoder::encode("LDA", Addr::IndirectY, Variant::Nmos6502), Some(0xB1));
+        assert_eq!(InstructionDecoder::encode("LDA", Addr::ZeroPageIndirect, Variant::Nmos6502), None);
+        assert_eq!(InstructionDecoder::encode("STZ", Addr::ZeroPage, Variant::Nmos6502), None);
+        assert_eq!(InstructionDecoder::encode("STZ", Addr::ZeroPage, Variant::Cmos65C02), Some(0x64));
+        assert_eq!(InstructionDecoder::encode("BBS2", Addr::ZeroPageRelative, Variant::Cmos65C02), None);
+        assert_eq!(InstructionDecoder::encode("BBS2", Addr::ZeroPageRelative, Variant::Wdc65C02), Some(0xAF));
+    }
+
+    #[test]
+    fn decode_ricoh_opcodes() {
+        for code in 0..=0xFF {
+            assert_eq!(
+                InstructionDecoder::try_from_byte_for(code, Variant::Ricoh2A03).map(|ins| ins.code().unwrap()),
+                InstructionDecoder::try_from_byte(code).map(|ins| ins.code().unwrap()),
+                "opcode {code:#04X}"
+            );
+        }
+    }
 }
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Transfer Accumulator to X - Copies the current contents of the accumulator into the X
 /// register and sets the zero and negative flags as appropriate.
 pub struct TAX(pub Addr);
 
 impl TAX {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if X = 0
         cpu.flags.z = cpu.reg.x == 0;
         // Set negative flag if bit 7 of X is set
@@ -15,22 +16,32 @@ impl TAX {
 }
 
 impl Instruction for TAX {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             TAX(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.x = cpu.reg.acc;
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
         Self::set_flags(cpu);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            TAX(Addr::Implicit) => 0xAA,
-            _ => panic!("Operation not supported!")
+            TAX(Addr::Implicit) => Ok(0xAA),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "TAX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
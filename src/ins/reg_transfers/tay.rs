@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Transfer Accumulator to Y - Copies the current contents of the accumulator into the Y
 /// register and sets the zero and negative flags as appropriate.
 pub struct TAY(pub Addr);
 
 impl TAY {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if Y = 0
         cpu.flags.z = cpu.reg.y == 0;
         // Set negative flag if bit 7 of Y is set
@@ -15,22 +16,32 @@ impl TAY {
 }
 
 impl Instruction for TAY {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             TAY(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.y = cpu.reg.acc;
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
         Self::set_flags(cpu);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            TAY(Addr::Implicit) => 0xA8,
-            _ => panic!("Operation not supported!")
+            TAY(Addr::Implicit) => Ok(0xA8),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "TAY"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Transfer X to Accumulator - Copies the current contents of the X register into the
 /// accumulator and sets the zero and negative flags as appropriate.
 pub struct TXA(pub Addr);
 
 impl TXA {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if A = 0
         cpu.flags.z = cpu.reg.acc == 0;
         // Set negative flag if bit 7 of A is set
@@ -15,22 +16,32 @@ impl TXA {
 }
 
 impl Instruction for TXA {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             TXA(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.acc = cpu.reg.x;
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
         Self::set_flags(cpu);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            TXA(Addr::Implicit) => 0x8A,
-            _ => panic!("Operation not supported!")
+            TXA(Addr::Implicit) => Ok(0x8A),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "TXA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
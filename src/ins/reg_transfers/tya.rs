@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Transfer Y to Accumulator - Copies the current contents of the Y register into the
 /// accumulator and sets the zero and negative flags as appropriate.
 pub struct TYA(pub Addr);
 
 impl TYA {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set zero flag if A = 0
         cpu.flags.z = cpu.reg.acc == 0;
         // Set negative flag if bit 7 of A is set
@@ -15,22 +16,32 @@ impl TYA {
 }
 
 impl Instruction for TYA {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             TYA(Addr::Implicit) => {
+                cpu.cycles += 2;
                 cpu.reg.acc = cpu.reg.y;
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
         Self::set_flags(cpu);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            TYA(Addr::Implicit) => 0x98,
-            _ => panic!("Operation not supported!")
+            TYA(Addr::Implicit) => Ok(0x98),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "TYA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -0,0 +1,177 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Logical Shift Right - Each of the bits in the accumulator or memory contents is shifted
+/// one place to the right. The bit that was in bit 0 is shifted into the carry flag. Bit 7
+/// is set to zero.
+pub struct LSR(pub Addr);
+
+impl LSR {
+    fn set_flags<B: Bus + ?Sized>(cpu: &mut CPU<B>, result: Byte) {
+        // Set zero flag if the result is 0
+        cpu.flags.z = result == 0;
+        // Set negative flag if bit 7 of the result is set
+        cpu.flags.n = (result & 0b10000000) > 0;
+    }
+
+    /// Shifts `value` one bit to the right, updating the carry, zero and negative flags.
+    pub fn shift<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) -> Byte {
+        let result = value >> 1;
+        // Set carry flag to the contents of the old bit 0
+        cpu.flags.c = (value & 0b00000001) > 0;
+        Self::set_flags(cpu, result);
+        result
+    }
+}
+
+impl Instruction for LSR {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 1B, 2C
+            LSR(Addr::Accummulator) => cpu.cycles += 2,
+            // 2B, 5C
+            LSR(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            LSR(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            LSR(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C (65C02: 6C, +1 if page crossed)
+            LSR(Addr::AbsoluteX) if cpu.variant.is_cmos() => {
+                cpu.cycles += 6 + operand.page_crossed as u32
+            },
+            LSR(Addr::AbsoluteX) => cpu.cycles += 7,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, Self::shift);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            LSR(Addr::Accummulator) => Ok(0x4A),
+            LSR(Addr::ZeroPage) => Ok(0x46),
+            LSR(Addr::ZeroPageX) => Ok(0x56),
+            LSR(Addr::Absolute) => Ok(0x4E),
+            LSR(Addr::AbsoluteX) => Ok(0x5E),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "LSR"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn lsr_accumulator() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0b10000010;
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, LSR(Addr::Accummulator).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0b01000001);
+
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert_eq!(cpu.flags.v, cpu_start.flags.v);
+        assert!(!cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFD);
+    }
+
+    #[test]
+    fn lsr_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, LSR(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0b00000001);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn lsr_zero_page_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x02;
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, LSR(Addr::ZeroPageX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0x0001, 0b10000010); // 0xFF + 0x02 % 0xFF = 0x01
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0001), 0b01000001);
+        assert!(!cpu.flags.c);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn lsr_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, LSR(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0b10000010);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x4480), 0b01000001);
+        assert!(!cpu.flags.c);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn lsr_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x12;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, LSR(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0b00000001);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x4412), 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
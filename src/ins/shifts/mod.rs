@@ -0,0 +1,4 @@
+pub mod asl;
+pub mod lsr;
+pub mod rol;
+pub mod ror;
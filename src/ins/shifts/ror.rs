@@ -0,0 +1,178 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Rotate Right - Move each of the bits in either the accumulator or memory contents one
+/// place to the right. Bit 7 is filled with the current value of the carry flag whilst
+/// the old bit 0 becomes the new carry flag value.
+pub struct ROR(pub Addr);
+
+impl ROR {
+    fn set_flags<B: Bus + ?Sized>(cpu: &mut CPU<B>, result: Byte) {
+        // Set zero flag if the result is 0
+        cpu.flags.z = result == 0;
+        // Set negative flag if bit 7 of the result is set
+        cpu.flags.n = (result & 0b10000000) > 0;
+    }
+
+    /// Rotates `value` one bit to the right through the carry flag, updating the carry,
+    /// zero and negative flags.
+    pub fn rotate<B: Bus + ?Sized>(cpu: &mut CPU<B>, value: Byte) -> Byte {
+        let result = (value >> 1) | ((cpu.flags.c as Byte) << 7);
+        // Set carry flag to the contents of the old bit 0
+        cpu.flags.c = (value & 0b00000001) > 0;
+        Self::set_flags(cpu, result);
+        result
+    }
+}
+
+impl Instruction for ROR {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 1B, 2C
+            ROR(Addr::Accummulator) => cpu.cycles += 2,
+            // 2B, 5C
+            ROR(Addr::ZeroPage) => cpu.cycles += 5,
+            // 2B, 6C
+            ROR(Addr::ZeroPageX) => cpu.cycles += 6,
+            // 3B, 6C
+            ROR(Addr::Absolute) => cpu.cycles += 6,
+            // 3B, 7C (65C02: 6C, +1 if page crossed)
+            ROR(Addr::AbsoluteX) if cpu.variant.is_cmos() => {
+                cpu.cycles += 6 + operand.page_crossed as u32
+            },
+            ROR(Addr::AbsoluteX) => cpu.cycles += 7,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        cpu.modify_operand(&operand, Self::rotate);
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            ROR(Addr::Accummulator) => Ok(0x6A),
+            ROR(Addr::ZeroPage) => Ok(0x66),
+            ROR(Addr::ZeroPageX) => Ok(0x76),
+            ROR(Addr::Absolute) => Ok(0x6E),
+            ROR(Addr::AbsoluteX) => Ok(0x7E),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "ROR"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn ror_accumulator() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.acc = 0b00000010;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, ROR(Addr::Accummulator).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.reg.acc, 0b10000001);
+
+        assert!(!cpu.flags.c);
+        assert!(!cpu.flags.z);
+        assert_eq!(cpu.flags.i, cpu_start.flags.i);
+        assert_eq!(cpu.flags.d, cpu_start.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert_eq!(cpu.flags.v, cpu_start.flags.v);
+        assert!(cpu.flags.n);
+
+        assert_eq!(cpu_start.pc, 0xFFFC);
+        assert_eq!(cpu.pc, 0xFFFD);
+    }
+
+    #[test]
+    fn ror_zero_page() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, ROR(Addr::ZeroPage).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x42);
+        cpu.mem.write_byte(0x0042, 0b00000001);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0042), 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.n);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn ror_zero_page_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x02;
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, ROR(Addr::ZeroPageX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xFF);
+        cpu.mem.write_byte(0x0001, 0b00000010); // 0xFF + 0x02 % 0xFF = 0x01
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x0001), 0b10000001);
+        assert!(!cpu.flags.c);
+        assert_eq!(cpu.pc, 0xFFFE);
+    }
+
+    #[test]
+    fn ror_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, ROR(Addr::Absolute).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x80);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4480 (LE)
+        cpu.mem.write_byte(0x4480, 0b00000010);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x4480), 0b10000001);
+        assert!(!cpu.flags.c);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn ror_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x12;
+        cpu.flags.c = false;
+        cpu.mem.write_byte(0xFFFC, ROR(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0x00);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x4400 (LE)
+        cpu.mem.write_byte(0x4412, 0b00000001);
+
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x4412), 0x00);
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
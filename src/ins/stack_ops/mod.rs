@@ -4,3 +4,7 @@ pub mod pha;
 pub mod php;
 pub mod pla;
 pub mod plp;
+pub mod phx;
+pub mod phy;
+pub mod plx;
+pub mod ply;
@@ -1,27 +1,37 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Push Accumulator - Pushes a copy of the accumulator on to the stack.
 pub struct PHA(pub Addr);
 
 impl Instruction for PHA {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
+            // 1B, 3C
             PHA(Addr::Implicit) => {
-                cpu.write_byte(CPU::stack_address(cpu.sp), cpu.reg.acc);
-                // Decrease stack pointer (PUSH)
-                cpu.sp -= 1;
+                cpu.cycles += 3;
+                cpu.push_byte(cpu.reg.acc);
                 // Increase program counter
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            PHA(Addr::Implicit) => 0x48,
-            _ => panic!("Operation not supported!")
+            PHA(Addr::Implicit) => Ok(0x48),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "PHA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,27 +1,39 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Push Processor Status - Pushes a copy of the status flags on to the stack.
 pub struct PHP(pub Addr);
 
 impl Instruction for PHP {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
+            // 1B, 3C
             PHP(Addr::Implicit) => {
-                cpu.write_byte(CPU::stack_address(cpu.sp), cpu.flags.to_owned().into());
-                // Decrease stack pointer (PUSH)
-                cpu.sp -= 1;
+                cpu.cycles += 3;
+                // The pushed status always has the break flag and the unused bit 5 set.
+                let status: Byte = cpu.flags.to_owned().into();
+                cpu.push_byte(status | 0b00110000);
                 // Increase program counter
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            PHP(Addr::Implicit) => 0x48,
-            _ => panic!("Operation not supported!")
+            PHP(Addr::Implicit) => Ok(0x08),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "PHP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
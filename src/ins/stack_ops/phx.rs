@@ -0,0 +1,63 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Push X Register - Pushes a copy of the X register on to the stack. Only available
+/// on the 65C02.
+pub struct PHX(pub Addr);
+
+impl Instruction for PHX {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, 3C
+            PHX(Addr::Implicit) => {
+                cpu.cycles += 3;
+                cpu.push_byte(cpu.reg.x);
+                // Increase program counter
+                cpu.pc = cpu.pc.wrapping_add(1);
+            },
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            PHX(Addr::Implicit) => Ok(0xDA),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "PHX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn phx_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.reg.x = 0x42;
+        cpu.mem.write_byte(0xFFFC, PHX(Addr::Implicit).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.mem.read_byte(0x01FD), 0x42);
+        assert_eq!(cpu.sp, 0xFC);
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.cycles, 3);
+        assert_eq!(cpu.pc, 0xFFFD);
+    }
+}
@@ -1,31 +1,39 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Pull Accumulator - Pops the topmost byte from the stack and stores it in the
 /// accumulator.
 pub struct PLA(pub Addr);
 
 impl Instruction for PLA {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 4C
             PLA(Addr::Implicit) => {
-                // TODO handle overflow case (when stack is empty)
+                cpu.cycles += 4;
                 // Read the value at the top of the stack
-                cpu.reg.acc = cpu.read_byte(CPU::stack_address(cpu.sp + 1));
-                // Increment stack pointer (POP)
-                cpu.sp += 1;
+                cpu.reg.acc = cpu.pull_byte();
                 // Increment program counter
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            PLA(Addr::Implicit) => 0x68,
-            _ => panic!("Operation not supported!")
+            PLA(Addr::Implicit) => Ok(0x68),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "PLA"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,29 +1,39 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Pull Processor Status - Pulls an 8 bit value from the stack and into the processor
 /// flags. The flags will take on new states as determined by the value pulled.
 pub struct PLP(pub Addr);
 
 impl Instruction for PLP {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
+            // 1B, 4C
             PLP(Addr::Implicit) => {
+                cpu.cycles += 4;
                 // Read the value from the top of the stack and transform it into `StatusFlags`
-                cpu.flags = cpu.read_byte(CPU::stack_address(cpu.sp + 1)).into();
-                // Increment stack pointer (POP)
-                cpu.sp += 1;
+                cpu.flags = cpu.pull_byte().into();
                 // Increment program counter
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            PLP(Addr::Implicit) => 0x28,
-            _ => panic!("Operation not supported!")
+            PLP(Addr::Implicit) => Ok(0x28),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "PLP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -0,0 +1,76 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Pull Y Register - Pops the topmost byte from the stack and stores it in the Y
+/// register, setting the zero and negative flags as appropriate. Only available on the
+/// 65C02.
+pub struct PLY(pub Addr);
+
+impl PLY {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
+        // Set if Y = 0
+        cpu.flags.z = cpu.reg.y == 0;
+        // Set if bit 7 of Y is set
+        cpu.flags.n = (cpu.reg.y & 0b10000000) > 0;
+    }
+}
+
+impl Instruction for PLY {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, 4C
+            PLY(Addr::Implicit) => {
+                cpu.cycles += 4;
+                // Read the value at the top of the stack
+                cpu.reg.y = cpu.pull_byte();
+                // Increment program counter
+                cpu.pc = cpu.pc.wrapping_add(1);
+            },
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Self::set_flags(cpu);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            PLY(Addr::Implicit) => Ok(0x7A),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "PLY"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn ply_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.sp = 0xFC;
+        cpu.mem.write_byte(0x01FD, 0x84);
+        cpu.mem.write_byte(0xFFFC, PLY(Addr::Implicit).code().unwrap());
+
+        cpu.start();
+
+        assert_eq!(cpu.reg.y, 0x84);
+        assert_eq!(cpu.sp, 0xFD);
+        assert!(!cpu.flags.z);
+        assert!(cpu.flags.n);
+        assert_eq!(cpu.cycles, 4);
+        assert_eq!(cpu.pc, 0xFFFD);
+    }
+}
@@ -1,12 +1,13 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Transfer Stack Pointer to X - Copies the current contents of the stack pointer into
 /// the X register and sets the zero and negative flags as appropriate.
 pub struct TSX(pub Addr);
 
 impl TSX {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         // Set if X = 0
         cpu.flags.z = cpu.reg.x == 0;
         // Set if bit 7 of X is set
@@ -15,25 +16,33 @@ impl TSX {
 }
 
 impl Instruction for TSX {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             TSX(Addr::Implicit) => {
-                // We assume it's actually the stack pointer value and not necessarily the
-                // top of the stack.
-                cpu.reg.x = cpu.read_byte(CPU::stack_address(cpu.sp));
+                cpu.cycles += 2;
+                cpu.reg.x = cpu.sp;
                 // Increment program counter
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
         Self::set_flags(cpu);
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            TSX(Addr::Implicit) => 0xBA,
-            _ => panic!("Operation not supported!")
+            TSX(Addr::Implicit) => Ok(0xBA),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "TSX"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,27 +1,38 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Transfer X to Stack Pointer - Copies the current contents of the X register into the
 /// stack pointer.
 pub struct TXS(pub Addr);
 
 impl Instruction for TXS {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             TXS(Addr::Implicit) => {
-                cpu.write_byte(CPU::stack_address(cpu.sp), cpu.reg.x);
+                cpu.cycles += 2;
+                cpu.sp = cpu.reg.x;
                 // Increment program counter
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            TXS(Addr::Implicit) => 0x9A,
-            _ => panic!("Operation not supported!")
+            TXS(Addr::Implicit) => Ok(0x9A),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "TXS"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
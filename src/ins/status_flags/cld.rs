@@ -1,31 +1,42 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Clear Decimal Mode - Sets the decimal mode flag to zero.
 pub struct CLD(pub Addr);
 
 impl CLD {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         cpu.flags.d = false;
     }
 }
 
 impl Instruction for CLD {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             CLD(Addr::Implicit) => {
+                cpu.cycles += 2;
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            CLD(Addr::Implicit) => 0xD8,
-            _ => panic!("Operation not supported!")
+            CLD(Addr::Implicit) => Ok(0xD8),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "CLD"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
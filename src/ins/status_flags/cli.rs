@@ -1,32 +1,43 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Clear Interrupt Disable - Clears the interrupt disable flag allowing normal interrupt
 /// requests to be serviced.
 pub struct CLI(pub Addr);
 
 impl CLI {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         cpu.flags.i = false;
     }
 }
 
 impl Instruction for CLI {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             CLI(Addr::Implicit) => {
+                cpu.cycles += 2;
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            CLI(Addr::Implicit) => 0x58,
-            _ => panic!("Operation not supported!")
+            CLI(Addr::Implicit) => Ok(0x58),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "CLI"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
@@ -1,31 +1,42 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Clear Overflow Flag - Clears the Overflow flag of the Processor Status register.
 pub struct CLV(pub Addr);
 
 impl CLV {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         cpu.flags.v = false;
     }
 }
 
 impl Instruction for CLV {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             CLV(Addr::Implicit) => {
+                cpu.cycles += 2;
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            CLV(Addr::Implicit) => 0xB8,
-            _ => panic!("Operation not supported!")
+            CLV(Addr::Implicit) => Ok(0xB8),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "CLV"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
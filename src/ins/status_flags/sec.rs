@@ -1,31 +1,42 @@
-use crate::{cpu::CPU, ins::Instruction, mem::Addr};
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
 use crate::Byte;
+use crate::bus::Bus;
 
 /// Set Carry Flag - Sets the carry flag to one.
 pub struct SEC(pub Addr);
 
 impl SEC {
-    fn set_flags(cpu: &mut CPU) {
+    fn set_flags(cpu: &mut CPU<dyn Bus>) {
         cpu.flags.c = true;
     }
 }
 
 impl Instruction for SEC {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
             // 1B, 2C
             SEC(Addr::Implicit) => {
+                cpu.cycles += 2;
                 Self::set_flags(cpu);
-                cpu.pc += 1;
+                cpu.pc = cpu.pc.wrapping_add(1);
             },
-            _ => panic!("Operation not supported!")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            SEC(Addr::Implicit) => 0x38,
-            _ => panic!("Operation not supported!")
+            SEC(Addr::Implicit) => Ok(0x38),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "SEC"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
 }
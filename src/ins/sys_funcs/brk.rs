@@ -1,35 +1,72 @@
-use crate::{ins::Instruction, mem::Addr};
-use crate::cpu::CPU;
+use crate::{ins::{ExecError, Instruction}, mem::Addr};
+use crate::cpu::{CPU, IRQ_VECTOR};
+use crate::Byte;
+use crate::bus::Bus;
 
 /// The BRK instruction forces the generation of an interrupt request. The program counter
 /// and processor status are pushed on the stack then the IRQ interrupt vector at $FFFE/F
 /// is loaded into the PC and the break flag in the status set to one.
 pub struct BRK(pub Addr);
 
-impl BRK {
-    fn set_flags(cpu: &mut CPU) {
-        // Set break command flag
-        cpu.flags.b = true;
-    }
-}
-
 impl Instruction for BRK {
-    fn execute(&self, cpu: &mut CPU) {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
         match self {
+            // 1B, 7C
             BRK(Addr::Implicit) => {
-                // TODO push the pc and processor status onto the stack. Check if the
-                // following is correct.
-                cpu.pc = 0xFFFE;
-                Self::set_flags(cpu);
+                // BRK is followed by a padding byte, so the return address skips it.
+                cpu.interrupt(cpu.pc.wrapping_add(2), IRQ_VECTOR, true);
+                // Set break command flag
+                cpu.flags.b = true;
             },
-            _ => panic!("Addressing method not supported")
+            _ => return Err(ExecError::UnsupportedMode(self.0))
         }
+        Ok(())
     }
 
-    fn code(&self) -> crate::Byte {
+    fn code(&self) -> Result<Byte, ExecError> {
         match self {
-            BRK(Addr::Implicit) => 0x00,
-            _ => panic!("Operation not supported")
+            BRK(Addr::Implicit) => Ok(0x00),
+            _ => Err(ExecError::UnsupportedMode(self.0))
         }
     }
+
+    fn mnemonic(&self) -> &'static str {
+        "BRK"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn brk_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.flags.c = true;
+        cpu.mem.write_byte(0xFFFC, BRK(Addr::Implicit).code().unwrap());
+        // Overlap the IRQ vector with the padding byte and beyond, as in the other tests.
+        cpu.mem.write_byte(0xFFFE, 0x32);
+        cpu.mem.write_byte(0xFFFF, 0x42); // 0x4232 (LE)
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0x4232);
+        assert_eq!(cpu.sp, cpu_start.sp.wrapping_sub(3));
+        // Return address 0xFFFE (0xFFFC + 2), high byte first, then the status.
+        assert_eq!(cpu.mem.read_byte(CPU::stack_address(cpu_start.sp)), 0xFF);
+        assert_eq!(cpu.mem.read_byte(CPU::stack_address(cpu_start.sp - 1)), 0xFE);
+        assert_eq!(cpu.mem.read_byte(CPU::stack_address(cpu_start.sp - 2)), 0b00110101);
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.i);
+        assert!(cpu.flags.b);
+    }
 }
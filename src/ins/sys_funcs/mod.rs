@@ -1 +1,6 @@
 pub mod brk;
+pub mod nop;
+pub mod rti;
+pub mod rsv;
+pub mod stp;
+pub mod wai;
@@ -0,0 +1,96 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// No Operation - The NOP instruction causes no changes to the processor other than the
+/// normal incrementing of the program counter to the next instruction. The undocumented
+/// NOPs with an operand read it (or skip it when immediate) without using it.
+pub struct NOP(pub Addr);
+
+impl Instruction for NOP {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let operand = cpu.resolve(&self.0);
+        match self {
+            // 1B, 2C
+            NOP(Addr::Implicit) => cpu.cycles += 2,
+            // 2B, 2C
+            NOP(Addr::Immediate) => cpu.cycles += 2,
+            // 2B, 3C
+            NOP(Addr::ZeroPage) => cpu.cycles += 3,
+            // 2B, 4C
+            NOP(Addr::ZeroPageX) => cpu.cycles += 4,
+            // 3B, 4C
+            NOP(Addr::Absolute) => cpu.cycles += 4,
+            // 3B, 4C (+1 if page crossed)
+            NOP(Addr::AbsoluteX) => cpu.cycles += 4 + operand.page_crossed as u32,
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        if operand.address.is_some() {
+            // The dummy read may have side effects on memory-mapped devices.
+            cpu.read_operand(&operand);
+        }
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            NOP(Addr::Implicit) => Ok(0xEA),
+            NOP(Addr::Immediate) => Ok(0x80),
+            NOP(Addr::ZeroPage) => Ok(0x04),
+            NOP(Addr::ZeroPageX) => Ok(0x14),
+            NOP(Addr::Absolute) => Ok(0x0C),
+            NOP(Addr::AbsoluteX) => Ok(0x1C),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "NOP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn nop_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, NOP(Addr::Implicit).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.reg.acc, cpu_start.reg.acc);
+        assert_eq!(cpu.sp, cpu_start.sp);
+        assert_eq!(cpu.pc, 0xFFFD);
+    }
+
+    #[test]
+    fn nop_absolute_x() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.reg.x = 0x20;
+        cpu.mem.write_byte(0xFFFC, NOP(Addr::AbsoluteX).code().unwrap());
+        cpu.mem.write_byte(0xFFFD, 0xF0);
+        cpu.mem.write_byte(0xFFFE, 0x44); // 0x44F0 (LE)
+
+        let cpu_start = cpu.clone();
+        NOP(Addr::AbsoluteX).execute(&mut cpu).unwrap();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.reg.acc, cpu_start.reg.acc);
+        assert_eq!(cpu.cycles, 5);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+}
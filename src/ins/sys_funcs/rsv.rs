@@ -0,0 +1,100 @@
+use crate::{cpu::CPU, ins::{DecodeError, ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Reserved - One of the unused opcodes of the 65C02, which are guaranteed to do nothing
+/// but skip their operand. Unlike the undocumented NOPs of the NMOS 6502, the length and
+/// timing depend on the opcode itself: most of the single byte ones take a single cycle.
+pub struct RSV(pub Byte);
+
+impl RSV {
+    /// The addressing mode and cycle count of the reserved opcode.
+    fn timing(&self) -> Option<(Addr, u32)> {
+        match self.0 {
+            // 2B, 2C
+            0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => Some((Addr::Immediate, 2)),
+            // 2B, 3C
+            0x44 => Some((Addr::ZeroPage, 3)),
+            // 2B, 4C
+            0x54 | 0xD4 | 0xF4 => Some((Addr::ZeroPageX, 4)),
+            // 3B, 8C
+            0x5C => Some((Addr::Absolute, 8)),
+            // 3B, 4C
+            0xDC | 0xFC => Some((Addr::Absolute, 4)),
+            // 1B, 1C
+            code if code & 0b11 == 0b11 => Some((Addr::Implicit, 1)),
+            _ => None,
+        }
+    }
+}
+
+impl Instruction for RSV {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        let Some((mode, cycles)) = self.timing() else {
+            return Err(ExecError::Decode { pc: cpu.pc, source: DecodeError::IllegalOpcode(self.0) });
+        };
+        let operand = cpu.resolve(&mode);
+        cpu.cycles += cycles;
+        cpu.pc = cpu.pc.wrapping_add(operand.len);
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        Ok(self.0)
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "NOP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.timing().map_or(Addr::Implicit, |(mode, _)| mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn rsv_single_byte() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, RSV(0x03).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.reg.acc, cpu_start.reg.acc);
+        assert_eq!(cpu.cycles, 1);
+        assert_eq!(cpu.pc, 0xFFFD);
+    }
+
+    #[test]
+    fn rsv_absolute() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Cmos65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, RSV(0x5C).code().unwrap());
+
+        cpu.start();
+
+        assert_eq!(cpu.cycles, 8);
+        assert_eq!(cpu.pc, 0xFFFF);
+    }
+
+    #[test]
+    fn rsv_not_reserved() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        assert_eq!(
+            RSV(0xEA).execute(&mut cpu),
+            Err(ExecError::Decode { pc: 0xFFFC, source: DecodeError::IllegalOpcode(0xEA) })
+        );
+    }
+}
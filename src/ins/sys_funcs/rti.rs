@@ -0,0 +1,72 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Return from Interrupt - The RTI instruction is used at the end of an interrupt
+/// processing routine. It pulls the processor flags from the stack followed by the
+/// program counter.
+pub struct RTI(pub Addr);
+
+impl Instruction for RTI {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, 6C
+            RTI(Addr::Implicit) => {
+                cpu.cycles += 6;
+                // The break flag only exists on the stack, so it is not restored.
+                let b = cpu.flags.b;
+                cpu.flags = cpu.pull_byte().into();
+                cpu.flags.b = b;
+                // Unlike RTS, the pulled address is the exact return point.
+                cpu.pc = cpu.pull_word();
+            },
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            RTI(Addr::Implicit) => Ok(0x40),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "RTI"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::ResetMode;
+
+    #[test]
+    fn rti_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.reset();
+        cpu.push_word(0x4232);
+        cpu.push_byte(0b11000011); // N, V, Z, C
+        cpu.mem.write_byte(0xFFFC, RTI(Addr::Implicit).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert_eq!(cpu.pc, 0x4232);
+        assert_eq!(cpu.sp, cpu_start.sp.wrapping_add(3));
+
+        assert!(cpu.flags.c);
+        assert!(cpu.flags.z);
+        assert!(!cpu.flags.i);
+        assert!(!cpu.flags.d);
+        assert_eq!(cpu.flags.b, cpu_start.flags.b);
+        assert!(cpu.flags.v);
+        assert!(cpu.flags.n);
+    }
+}
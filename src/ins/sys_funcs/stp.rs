@@ -0,0 +1,56 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Stop the Clock - Stops the processor, which no longer fetches instructions nor responds
+/// to interrupts until it is reset. Only available on the WDC 65C02.
+pub struct STP(pub Addr);
+
+impl Instruction for STP {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, 3C, the program counter is left on the opcode
+            STP(Addr::Implicit) => {
+                cpu.cycles += 3;
+                cpu.stop();
+            },
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            STP(Addr::Implicit) => Ok(0xDB),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "STP"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, StopReason, Variant};
+
+    #[test]
+    fn stp_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, STP(Addr::Implicit).code().unwrap());
+
+        assert_eq!(cpu.step(), Ok(Some(StopReason::Stop(0xFFFC))));
+        assert!(cpu.stopped());
+        assert_eq!(cpu.pc, 0xFFFC);
+        assert_eq!(cpu.cycles, 3);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{cpu::CPU, ins::{ExecError, Instruction}, mem::Addr};
+use crate::Byte;
+use crate::bus::Bus;
+
+/// Wait for Interrupt - Puts the processor to sleep until an interrupt arrives. An NMI, or
+/// an IRQ while the interrupt disable flag is clear, is then serviced as usual, whereas a
+/// masked IRQ resumes execution with the next instruction. Only available on the WDC
+/// 65C02.
+pub struct WAI(pub Addr);
+
+impl Instruction for WAI {
+    fn execute(&self, cpu: &mut CPU<dyn Bus>) -> Result<(), ExecError> {
+        match self {
+            // 1B, 3C
+            WAI(Addr::Implicit) => {
+                cpu.cycles += 3;
+                cpu.wait();
+                cpu.pc = cpu.pc.wrapping_add(1);
+            },
+            _ => return Err(ExecError::UnsupportedMode(self.0))
+        }
+        Ok(())
+    }
+
+    fn code(&self) -> Result<Byte, ExecError> {
+        match self {
+            WAI(Addr::Implicit) => Ok(0xCB),
+            _ => Err(ExecError::UnsupportedMode(self.0))
+        }
+    }
+
+    fn mnemonic(&self) -> &'static str {
+        "WAI"
+    }
+
+    fn mode(&self) -> Addr {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{ResetMode, Variant};
+
+    #[test]
+    fn wai_implicit() {
+        let mut cpu = CPU::with_reset_mode(ResetMode::Legacy);
+
+        cpu.variant = Variant::Wdc65C02;
+        cpu.reset();
+        cpu.mem.write_byte(0xFFFC, WAI(Addr::Implicit).code().unwrap());
+
+        let cpu_start = cpu.clone();
+        cpu.start();
+
+        assert!(cpu.waiting());
+        assert_eq!(cpu.flags, cpu_start.flags);
+        assert_eq!(cpu.pc, 0xFFFD);
+        assert_eq!(cpu.cycles, 3);
+    }
+}
@@ -1,8 +1,14 @@
+pub mod bus;
 pub mod cpu;
 pub mod mem;
+pub mod map;
+pub mod device;
+pub mod mapper;
 pub mod ins;
 pub mod asm;
 
+pub use asm::asm;
+
 // These represent the types of the emulated 6502 CPU.
 type Byte = u8;
 type Word = u16;
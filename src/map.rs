@@ -0,0 +1,419 @@
+use std::ops::RangeInclusive;
+
+use thiserror::Error;
+
+use crate::{Byte, Word};
+use crate::bus::Bus;
+use crate::device::Device;
+use crate::mapper::Mapper;
+
+/// A declarative description of what lives where in the address space of a machine. Each
+/// region is either RAM, ROM, a bank switching [`Mapper`], a memory-mapped [`Device`], a
+/// mirror of another region or open bus. Addresses not covered by any region behave as
+/// open bus, i.e. reads return the last value that was on the data bus.
+///
+/// ```
+/// use mos_6502::map::MemoryMap;
+///
+/// // 2KiB of RAM mirrored across $0000-$1FFF, and 32KiB of ROM at the top.
+/// let map = MemoryMap::builder()
+///     .ram(0x0000..=0x07FF)
+///     .mirror(0x0800..=0x1FFF, 0x0000..=0x07FF)
+///     .rom(0x8000, vec![0xEA; 0x8000])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct MemoryMap {
+    /// Regions sorted by start address, never overlapping.
+    regions: Vec<Region>,
+    /// What happens to writes targeting ROM.
+    rom_writes: RomWrites,
+    /// Writes to ROM recorded with [`RomWrites::Record`].
+    recorded: Vec<(Word, Byte)>,
+    /// Last value on the data bus, returned by reads from open bus.
+    data_bus: Byte,
+}
+
+/// Builder for a [`MemoryMap`], see [`MemoryMap::builder`].
+#[derive(Default)]
+pub struct MemoryMapBuilder {
+    /// Regions in registration order, as (start address, length, contents).
+    regions: Vec<(Word, usize, Kind)>,
+    rom_writes: RomWrites,
+}
+
+/// What happens to writes targeting a ROM region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RomWrites {
+    /// The write is silently dropped, like on real hardware.
+    #[default]
+    Ignore,
+    /// The write is dropped but recorded, see [`MemoryMap::take_rom_writes`].
+    Record,
+}
+
+/// Reasons why a [`MemoryMap`] cannot be built.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    #[error("region ${start:04X}-${end:04X} overlaps region ${other_start:04X}-${other_end:04X}")]
+    Overlap { start: Word, end: Word, other_start: Word, other_end: Word },
+    #[error("region starting at ${start:04X} is empty")]
+    Empty { start: Word },
+    #[error("region of {len} bytes starting at ${start:04X} goes past the end of the address space")]
+    OutOfRange { start: Word, len: usize },
+}
+
+#[derive(Clone)]
+struct Region {
+    start: Word,
+    end: Word,
+    kind: Kind,
+}
+
+#[derive(Clone)]
+enum Kind {
+    Ram(Vec<Byte>),
+    Rom(Vec<Byte>),
+    Mapper(Box<dyn Mapper>),
+    Device(Box<dyn Device>),
+    /// Repeats the `len` bytes starting at `source`, which must not be a mirror.
+    Mirror { source: Word, len: usize },
+    OpenBus,
+}
+
+/// Number of addresses in `range`.
+fn range_len(range: &RangeInclusive<Word>) -> usize {
+    (*range.end() as usize + 1).saturating_sub(*range.start() as usize)
+}
+
+impl MemoryMapBuilder {
+    /// Maps zeroed RAM at `range`.
+    pub fn ram(mut self, range: RangeInclusive<Word>) -> Self {
+        let len = range_len(&range);
+        self.regions.push((*range.start(), len, Kind::Ram(vec![0; len])));
+        self
+    }
+
+    /// Maps `data` as read-only memory starting at `start`.
+    pub fn rom(mut self, start: Word, data: impl Into<Vec<Byte>>) -> Self {
+        let data = data.into();
+        self.regions.push((start, data.len(), Kind::Rom(data)));
+        self
+    }
+
+    /// Hands `range` over to `mapper`, which receives accesses as offsets from the start
+    /// of the range.
+    pub fn mapper(mut self, range: RangeInclusive<Word>, mapper: impl Mapper + 'static) -> Self {
+        self.regions.push((*range.start(), range_len(&range), Kind::Mapper(Box::new(mapper))));
+        self
+    }
+
+    /// Attaches `device` at `range`, which receives accesses as offsets from the start of
+    /// the range. The device is ticked once per CPU cycle.
+    pub fn device(mut self, range: RangeInclusive<Word>, device: impl Device + 'static) -> Self {
+        self.regions.push((*range.start(), range_len(&range), Kind::Device(Box::new(device))));
+        self
+    }
+
+    /// Maps `range` as a mirror of `source`, which is repeated as many times as needed to
+    /// fill `range`. The source must be mapped as RAM, ROM, to a mapper or to a device,
+    /// any other address reads as open bus.
+    pub fn mirror(mut self, range: RangeInclusive<Word>, source: RangeInclusive<Word>) -> Self {
+        let kind = Kind::Mirror { source: *source.start(), len: range_len(&source) };
+        self.regions.push((*range.start(), range_len(&range), kind));
+        self
+    }
+
+    /// Maps `range` as open bus, where reads return the last value on the data bus and
+    /// writes are ignored.
+    pub fn open_bus(mut self, range: RangeInclusive<Word>) -> Self {
+        self.regions.push((*range.start(), range_len(&range), Kind::OpenBus));
+        self
+    }
+
+    /// Sets what happens to writes targeting ROM.
+    pub fn rom_writes(mut self, rom_writes: RomWrites) -> Self {
+        self.rom_writes = rom_writes;
+        self
+    }
+
+    /// Builds the memory map, checking that every region fits in the address space and
+    /// that no two regions overlap.
+    pub fn build(self) -> Result<MemoryMap, MapError> {
+        let mut regions: Vec<Region> = Vec::with_capacity(self.regions.len());
+
+        for (start, len, kind) in self.regions {
+            if len == 0 {
+                return Err(MapError::Empty { start });
+            }
+            if let Kind::Mirror { source, len: 0 } = kind {
+                return Err(MapError::Empty { start: source });
+            }
+            if start as usize + len > 0x10000 {
+                return Err(MapError::OutOfRange { start, len });
+            }
+            let end = (start as usize + len - 1) as Word;
+            if let Some(other) = regions.iter().find(|r| r.start <= end && start <= r.end) {
+                return Err(MapError::Overlap {
+                    start,
+                    end,
+                    other_start: other.start,
+                    other_end: other.end,
+                });
+            }
+            regions.push(Region { start, end, kind });
+        }
+        regions.sort_by_key(|r| r.start);
+
+        Ok(MemoryMap {
+            regions,
+            rom_writes: self.rom_writes,
+            recorded: Vec::new(),
+            data_bus: 0,
+        })
+    }
+}
+
+impl MemoryMap {
+    /// Starts building an empty memory map.
+    pub fn builder() -> MemoryMapBuilder {
+        MemoryMapBuilder::default()
+    }
+
+    /// Returns the writes to ROM recorded since the last call, as (address, data) pairs.
+    /// Always empty unless built with [`RomWrites::Record`].
+    pub fn take_rom_writes(&mut self) -> Vec<(Word, Byte)> {
+        std::mem::take(&mut self.recorded)
+    }
+
+    /// Returns the index of the region containing `address`.
+    fn find(&self, address: Word) -> Option<usize> {
+        let i = self.regions.partition_point(|r| r.start <= address);
+        i.checked_sub(1).filter(|&i| self.regions[i].end >= address)
+    }
+
+    /// Returns the index of the region backing `address`, following mirrors, and the
+    /// offset of `address` within it.
+    fn resolve(&self, address: Word) -> Option<(usize, usize)> {
+        let i = self.find(address)?;
+        let region = &self.regions[i];
+        let offset = (address - region.start) as usize;
+
+        match region.kind {
+            Kind::Mirror { source, len } => {
+                let address = source.wrapping_add((offset % len) as Word);
+                let i = self.find(address)?;
+                let region = &self.regions[i];
+                match region.kind {
+                    Kind::Mirror { .. } | Kind::OpenBus => None,
+                    _ => Some((i, (address - region.start) as usize)),
+                }
+            },
+            _ => Some((i, offset)),
+        }
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: Word) -> Byte {
+        // Mappers and devices may react to reads, everything else is read as if peeked.
+        if let Some((i, offset)) = self.resolve(address) {
+            match &mut self.regions[i].kind {
+                Kind::Mapper(mapper) => {
+                    self.data_bus = mapper.read(offset as Word).unwrap_or(self.data_bus);
+                    return self.data_bus;
+                },
+                Kind::Device(device) => {
+                    self.data_bus = device.read(offset as Word);
+                    return self.data_bus;
+                },
+                _ => {},
+            }
+        }
+        self.data_bus = self.peek(address);
+        self.data_bus
+    }
+
+    fn write(&mut self, address: Word, data: Byte) {
+        self.data_bus = data;
+        let Some((i, offset)) = self.resolve(address) else {
+            return;
+        };
+        match &mut self.regions[i].kind {
+            Kind::Ram(bytes) => bytes[offset] = data,
+            Kind::Mapper(mapper) => mapper.write(offset as Word, data),
+            Kind::Device(device) => device.write(offset as Word, data),
+            Kind::Rom(_) if self.rom_writes == RomWrites::Record => {
+                self.recorded.push((address, data));
+            },
+            _ => {},
+        }
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        match self.resolve(address).map(|(i, offset)| (&self.regions[i].kind, offset)) {
+            Some((Kind::Ram(bytes) | Kind::Rom(bytes), offset)) => bytes[offset],
+            Some((Kind::Mapper(mapper), offset)) => mapper.peek(offset as Word).unwrap_or(self.data_bus),
+            Some((Kind::Device(device), offset)) => device.peek(offset as Word),
+            _ => self.data_bus,
+        }
+    }
+
+    fn tick(&mut self, cycles: u32) {
+        for region in &mut self.regions {
+            if let Kind::Device(device) = &mut region.kind {
+                (0..cycles).for_each(|_| device.tick());
+            }
+        }
+    }
+
+    fn irq(&self) -> bool {
+        self.regions.iter().any(|region| matches!(&region.kind, Kind::Device(device) if device.irq()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::{CPU, ResetMode, RESET_VECTOR};
+
+    #[test]
+    fn ram() {
+        let mut map = MemoryMap::builder()
+            .ram(0x0000..=0x07FF)
+            .build()
+            .unwrap();
+
+        map.write(0x0000, 0x12);
+        map.write(0x07FF, 0x34);
+
+        assert_eq!(map.read(0x0000), 0x12);
+        assert_eq!(map.read(0x07FF), 0x34);
+        assert_eq!(map.peek(0x0001), 0x00);
+    }
+
+    #[test]
+    fn rom_writes_ignored() {
+        let mut map = MemoryMap::builder()
+            .rom(0xFF00, vec![0xAA; 0x100])
+            .build()
+            .unwrap();
+
+        map.write(0xFF10, 0x55);
+
+        assert_eq!(map.read(0xFF10), 0xAA);
+        assert!(map.take_rom_writes().is_empty());
+    }
+
+    #[test]
+    fn rom_writes_recorded() {
+        let mut map = MemoryMap::builder()
+            .rom(0xFF00, vec![0xAA; 0x100])
+            .rom_writes(RomWrites::Record)
+            .build()
+            .unwrap();
+
+        map.write(0xFF10, 0x55);
+        map.write(0xFFFF, 0x66);
+
+        assert_eq!(map.read(0xFF10), 0xAA);
+        assert_eq!(map.take_rom_writes(), vec![(0xFF10, 0x55), (0xFFFF, 0x66)]);
+        assert!(map.take_rom_writes().is_empty());
+    }
+
+    #[test]
+    fn mirror() {
+        let mut map = MemoryMap::builder()
+            .ram(0x0000..=0x07FF)
+            .mirror(0x0800..=0x1FFF, 0x0000..=0x07FF)
+            .build()
+            .unwrap();
+
+        map.write(0x0042, 0x12);
+        assert_eq!(map.read(0x0842), 0x12);
+        assert_eq!(map.read(0x1042), 0x12);
+        assert_eq!(map.read(0x1842), 0x12);
+
+        // Writes through a mirror land in the source region.
+        map.write(0x1FFF, 0x34);
+        assert_eq!(map.read(0x07FF), 0x34);
+    }
+
+    #[test]
+    fn open_bus() {
+        let mut map = MemoryMap::builder()
+            .ram(0x0000..=0x00FF)
+            .open_bus(0x4000..=0x4FFF)
+            .build()
+            .unwrap();
+
+        map.write(0x0010, 0x12);
+        assert_eq!(map.read(0x0010), 0x12);
+        assert_eq!(map.read(0x4000), 0x12);
+        // Unmapped addresses behave the same way.
+        assert_eq!(map.read(0x8000), 0x12);
+
+        map.write(0x4000, 0x34);
+        assert_eq!(map.peek(0x4000), 0x34);
+        assert_eq!(map.read(0x0010), 0x12);
+        assert_eq!(map.peek(0x4000), 0x12);
+    }
+
+    #[test]
+    fn overlap() {
+        let result = MemoryMap::builder()
+            .ram(0x0000..=0x07FF)
+            .rom(0x8000, vec![0; 0x8000])
+            .ram(0x0700..=0x08FF)
+            .build();
+
+        assert_eq!(result.err(), Some(MapError::Overlap {
+            start: 0x0700,
+            end: 0x08FF,
+            other_start: 0x0000,
+            other_end: 0x07FF,
+        }));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let result = MemoryMap::builder()
+            .rom(0xFF00, vec![0; 0x101])
+            .build();
+
+        assert_eq!(result.err(), Some(MapError::OutOfRange { start: 0xFF00, len: 0x101 }));
+    }
+
+    #[test]
+    fn empty() {
+        let result = MemoryMap::builder()
+            .rom(0x8000, vec![])
+            .build();
+
+        assert_eq!(result.err(), Some(MapError::Empty { start: 0x8000 }));
+    }
+
+    #[test]
+    fn run_from_rom() {
+        let mut rom = vec![0; 0x100];
+        rom[0x00] = 0xA9; // LDA #$42
+        rom[0x01] = 0x42;
+        rom[0x02] = 0x85; // STA $10
+        rom[0x03] = 0x10;
+        rom[0x04] = 0x00; // BRK
+        rom[(RESET_VECTOR - 0xFF00) as usize] = 0x00;
+        rom[(RESET_VECTOR - 0xFF00) as usize + 1] = 0xFF;
+
+        let map = MemoryMap::builder()
+            .ram(0x0000..=0x07FF)
+            .rom(0xFF00, rom)
+            .build()
+            .unwrap();
+        let mut cpu = CPU::with_bus(map, ResetMode::Vector);
+
+        cpu.run_for_instructions(2).unwrap();
+
+        assert_eq!(cpu.peek_byte(0x0010), 0x42);
+    }
+}
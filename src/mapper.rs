@@ -0,0 +1,299 @@
+use crate::{Byte, Word};
+
+/// A bank switching scheme, paging banks of ROM or RAM into the window of the address
+/// space it is registered at in a [`MemoryMap`](crate::map::MemoryMap). Addresses are
+/// given as offsets from the start of that window, and writes to the mapper's control
+/// registers swap the banks that are visible.
+pub trait Mapper: MapperClone {
+    /// Read the byte at `offset` without any side effects, or `None` if nothing is mapped
+    /// there (i.e. open bus).
+    fn peek(&self, offset: Word) -> Option<Byte>;
+
+    /// Read the byte at `offset`. Mappers which react to reads should override this.
+    fn read(&mut self, offset: Word) -> Option<Byte> {
+        self.peek(offset)
+    }
+
+    /// Write a byte of data at `offset`, either to a control register or to the bank
+    /// mapped there.
+    fn write(&mut self, offset: Word, data: Byte);
+}
+
+/// Allows cloning boxed mappers, implemented for every [`Mapper`] which is [`Clone`].
+pub trait MapperClone {
+    fn clone_box(&self) -> Box<dyn Mapper>;
+}
+
+impl<T: Mapper + Clone + 'static> MapperClone for T {
+    fn clone_box(&self) -> Box<dyn Mapper> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Mapper> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+const BANK_16K: usize = 0x4000;
+const BANK_8K: usize = 0x2000;
+
+/// Returns the number of `size` banks in `rom`, which must be a non-empty multiple of it.
+fn bank_count(rom: &[Byte], size: usize) -> usize {
+    assert!(!rom.is_empty() && rom.len().is_multiple_of(size), "ROM size must be a multiple of {size} bytes");
+    rom.len() / size
+}
+
+/// A 32KiB window made of a switchable 16KiB ROM bank followed by one fixed to the last
+/// bank (e.g. the NES UxROM boards). Writing anywhere in the window selects the bank
+/// visible in its first half.
+#[derive(Clone)]
+pub struct Switchable16K {
+    rom: Vec<Byte>,
+    bank: usize,
+}
+
+impl Switchable16K {
+    /// Creates the mapper with the first bank selected. Panics unless `rom` is made of
+    /// whole 16KiB banks.
+    pub fn new(rom: impl Into<Vec<Byte>>) -> Self {
+        let rom = rom.into();
+        bank_count(&rom, BANK_16K);
+        Self { rom, bank: 0 }
+    }
+
+    /// Currently selected bank.
+    pub fn bank(&self) -> usize {
+        self.bank
+    }
+}
+
+impl Mapper for Switchable16K {
+    fn peek(&self, offset: Word) -> Option<Byte> {
+        let offset = offset as usize;
+        let bank = match offset / BANK_16K {
+            0 => self.bank,
+            1 => bank_count(&self.rom, BANK_16K) - 1,
+            _ => return None,
+        };
+        Some(self.rom[bank * BANK_16K + offset % BANK_16K])
+    }
+
+    fn write(&mut self, _offset: Word, data: Byte) {
+        self.bank = data as usize % bank_count(&self.rom, BANK_16K);
+    }
+}
+
+/// A window made of independently switchable 8KiB ROM banks. Writing anywhere in one of
+/// the 8KiB slots selects the bank visible in that slot.
+#[derive(Clone)]
+pub struct Windowed8K {
+    rom: Vec<Byte>,
+    slots: Vec<usize>,
+}
+
+impl Windowed8K {
+    /// Creates the mapper with `slots` 8KiB slots, showing the first banks in order.
+    /// Panics unless `rom` is made of whole 8KiB banks.
+    pub fn new(rom: impl Into<Vec<Byte>>, slots: usize) -> Self {
+        let rom = rom.into();
+        let banks = bank_count(&rom, BANK_8K);
+        Self { rom, slots: (0..slots).map(|slot| slot % banks).collect() }
+    }
+
+    /// Bank currently visible in each slot.
+    pub fn banks(&self) -> &[usize] {
+        &self.slots
+    }
+}
+
+impl Mapper for Windowed8K {
+    fn peek(&self, offset: Word) -> Option<Byte> {
+        let offset = offset as usize;
+        let bank = self.slots.get(offset / BANK_8K)?;
+        Some(self.rom[bank * BANK_8K + offset % BANK_8K])
+    }
+
+    fn write(&mut self, offset: Word, data: Byte) {
+        let banks = bank_count(&self.rom, BANK_8K);
+        if let Some(bank) = self.slots.get_mut(offset as usize / BANK_8K) {
+            *bank = data as usize % banks;
+        }
+    }
+}
+
+/// The on-chip I/O port of the 6510 (as wired in the Commodore 64), covering the whole
+/// address space. $00 is the data direction register and $01 the data port, whose three
+/// low lines (LORAM, HIRAM and CHAREN) choose whether the BASIC ROM ($A000-$BFFF), the
+/// KERNAL ROM ($E000-$FFFF) and the character ROM or I/O area ($D000-$DFFF) hide the RAM
+/// underneath. Writes always go to RAM, and the I/O area reads as open bus.
+#[derive(Clone)]
+pub struct ProcessorPort {
+    ram: Vec<Byte>,
+    basic: Vec<Byte>,
+    kernal: Vec<Byte>,
+    chargen: Vec<Byte>,
+    /// Data direction register, a set bit makes the matching line an output.
+    ddr: Byte,
+    /// Data port.
+    port: Byte,
+}
+
+impl ProcessorPort {
+    const LORAM: Byte = 0b001;
+    const HIRAM: Byte = 0b010;
+    const CHAREN: Byte = 0b100;
+
+    /// Creates the port with zeroed RAM and every line as an input, so that all ROMs are
+    /// visible (as after power on). Panics if the BASIC or KERNAL ROMs are not 8KiB, or
+    /// the character ROM is not 4KiB.
+    pub fn new(basic: impl Into<Vec<Byte>>, kernal: impl Into<Vec<Byte>>, chargen: impl Into<Vec<Byte>>) -> Self {
+        let (basic, kernal, chargen) = (basic.into(), kernal.into(), chargen.into());
+        assert_eq!(basic.len(), 0x2000, "BASIC ROM must be 8KiB");
+        assert_eq!(kernal.len(), 0x2000, "KERNAL ROM must be 8KiB");
+        assert_eq!(chargen.len(), 0x1000, "character ROM must be 4KiB");
+        Self { ram: vec![0; 0x10000], basic, kernal, chargen, ddr: 0, port: 0 }
+    }
+
+    /// State of the port lines. Lines configured as inputs are pulled high.
+    fn lines(&self) -> Byte {
+        (self.port & self.ddr) | !self.ddr
+    }
+}
+
+impl Mapper for ProcessorPort {
+    fn peek(&self, offset: Word) -> Option<Byte> {
+        let lines = self.lines();
+        let loram = lines & Self::LORAM != 0;
+        let hiram = lines & Self::HIRAM != 0;
+        let charen = lines & Self::CHAREN != 0;
+        let addr = offset as usize;
+
+        match offset {
+            0x0000 => Some(self.ddr),
+            0x0001 => Some(lines),
+            0xA000..=0xBFFF if loram && hiram => Some(self.basic[addr - 0xA000]),
+            0xD000..=0xDFFF if (loram || hiram) && charen => None,
+            0xD000..=0xDFFF if loram || hiram => Some(self.chargen[addr - 0xD000]),
+            0xE000..=0xFFFF if hiram => Some(self.kernal[addr - 0xE000]),
+            _ => Some(self.ram[addr]),
+        }
+    }
+
+    fn write(&mut self, offset: Word, data: Byte) {
+        match offset {
+            0x0000 => self.ddr = data,
+            0x0001 => self.port = data,
+            _ => self.ram[offset as usize] = data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::cpu::{CPU, ResetMode};
+    use crate::map::MemoryMap;
+
+    /// Returns `count` banks of `size` bytes, each filled with its own index.
+    fn banks(count: usize, size: usize) -> Vec<Byte> {
+        (0..count).flat_map(|bank| vec![bank as Byte; size]).collect()
+    }
+
+    #[test]
+    fn switchable_16k() {
+        let mut map = MemoryMap::builder()
+            .mapper(0x8000..=0xFFFF, Switchable16K::new(banks(4, BANK_16K)))
+            .build()
+            .unwrap();
+
+        assert_eq!(map.read(0x8000), 0);
+        assert_eq!(map.read(0xC000), 3);
+
+        map.write(0x8000, 2);
+        assert_eq!(map.read(0xBFFF), 2);
+        assert_eq!(map.read(0xFFFF), 3);
+
+        // Out of range banks wrap around.
+        map.write(0xFFFF, 5);
+        assert_eq!(map.read(0x8000), 1);
+    }
+
+    #[test]
+    fn windowed_8k() {
+        let mut map = MemoryMap::builder()
+            .mapper(0x8000..=0xFFFF, Windowed8K::new(banks(8, BANK_8K), 4))
+            .build()
+            .unwrap();
+
+        assert_eq!(map.read(0x8000), 0);
+        assert_eq!(map.read(0xA000), 1);
+        assert_eq!(map.read(0xC000), 2);
+        assert_eq!(map.read(0xE000), 3);
+
+        map.write(0xA123, 7);
+        map.write(0xE000, 4);
+        assert_eq!(map.read(0x8000), 0);
+        assert_eq!(map.read(0xA000), 7);
+        assert_eq!(map.read(0xC000), 2);
+        assert_eq!(map.read(0xFFFF), 4);
+    }
+
+    #[test]
+    fn processor_port() {
+        let mut map = MemoryMap::builder()
+            .mapper(0x0000..=0xFFFF, ProcessorPort::new([0xBA; 0x2000], [0xEE; 0x2000], [0xCC; 0x1000]))
+            .build()
+            .unwrap();
+
+        // All lines are inputs after power on, so every ROM is visible. Writes go to
+        // the RAM underneath.
+        map.write(0xA000, 0x11);
+        map.write(0xD000, 0x22);
+        map.write(0xE000, 0x33);
+        assert_eq!(map.read(0xA000), 0xBA);
+        assert_eq!(map.read(0xE000), 0xEE);
+
+        // LORAM low: BASIC is banked out, the I/O area is still visible.
+        map.write(0x0000, 0b00000111);
+        map.write(0x0001, 0b00000110);
+        assert_eq!(map.read(0xA000), 0x11);
+        assert_eq!(map.read(0xE000), 0xEE);
+        map.write(0x0002, 0x44);
+        assert_eq!(map.read(0xD000), 0x44);
+
+        // CHAREN low: the character ROM replaces the I/O area.
+        map.write(0x0001, 0b00000011);
+        assert_eq!(map.read(0xD000), 0xCC);
+        assert_eq!(map.read(0xA000), 0xBA);
+
+        // LORAM and HIRAM low: RAM everywhere.
+        map.write(0x0001, 0b00000000);
+        assert_eq!(map.read(0xA000), 0x11);
+        assert_eq!(map.read(0xD000), 0x22);
+        assert_eq!(map.read(0xE000), 0x33);
+    }
+
+    #[test]
+    fn bank_switch_from_sta() {
+        let mut rom = banks(4, BANK_16K);
+        // The fixed bank starts executing at $C000: LDA #$02, STA $8000, LDX $8000, BRK
+        let fixed = 3 * BANK_16K;
+        rom[fixed..fixed + 9].copy_from_slice(&[0xA9, 0x02, 0x8D, 0x00, 0x80, 0xAE, 0x00, 0x80, 0x00]);
+        rom[fixed + 0x3FFC] = 0x00;
+        rom[fixed + 0x3FFD] = 0xC0;
+
+        let map = MemoryMap::builder()
+            .ram(0x0000..=0x07FF)
+            .mapper(0x8000..=0xFFFF, Switchable16K::new(rom))
+            .build()
+            .unwrap();
+        let mut cpu = CPU::with_bus(map, ResetMode::Vector);
+
+        cpu.run_for_instructions(3).unwrap();
+
+        assert_eq!(cpu.reg.x, 0x02);
+    }
+}